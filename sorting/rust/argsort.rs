@@ -0,0 +1,219 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: argsort.rs
+
+   Description:
+      This module lets the sorting programs compute the permutation that
+      sorts their input instead of sorting the values themselves. Each value
+      is tagged with its original position, the tagged values are sorted by
+      value alone with the program's own algorithm, and the positions are
+      read back in sorted order. Whether equal values keep their original
+      order therefore depends on the algorithm, exactly as it does for the
+      values.
+
+      It also reads input while remembering the line each value came from,
+      and prints those line numbers for the --indices mode, so the sorted
+      order can be joined back to other columns of the original file.
+
+   Usage:
+      mod argsort;
+      use argsort::{argsort_with, Tagged};
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::io::{self, BufRead, BufWriter, Write};
+
+/// A value tagged with its position in the original input.
+///
+/// Comparisons look only at `value`, so two tags with equal values are
+/// equal no matter where they came from.
+#[derive(Clone, Copy, Debug)]
+pub struct Tagged {
+    /// The value being sorted
+    pub value: i32,
+    /// The position of the value in the original input
+    pub index: usize,
+}
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Tagged) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Tagged {}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Tagged) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Tagged) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Tags every value with its index.
+///
+/// # Arguments
+/// * `numbers` - The values to tag
+///
+/// # Returns
+/// * `Vec<Tagged>` - One tag per value, in the original order
+pub fn tag(numbers: &[i32]) -> Vec<Tagged> {
+    numbers
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| Tagged { value, index })
+        .collect()
+}
+
+/// Extracts the original indices from sorted tags.
+///
+/// # Arguments
+/// * `tagged` - Tags in sorted order
+///
+/// # Returns
+/// * `Vec<usize>` - The permutation: entry i is the original index of the
+///   i-th smallest value
+pub fn indices(tagged: &[Tagged]) -> Vec<usize> {
+    tagged.iter().map(|t| t.index).collect()
+}
+
+/// Computes the permutation that sorts `numbers` using the given sort.
+///
+/// # Arguments
+/// * `numbers` - The values to sort
+/// * `sort` - Sorts a slice of tags in place
+///
+/// # Returns
+/// * `Vec<usize>` - The permutation: entry i is the original index of the
+///   i-th smallest value. Equal values appear in their original order if
+///   `sort` is stable.
+pub fn argsort_with<F: FnOnce(&mut [Tagged])>(numbers: &[i32], sort: F) -> Vec<usize> {
+    let mut tagged = tag(numbers);
+    sort(&mut tagged);
+    indices(&tagged)
+}
+
+/// Reads integers from standard input, one per line, along with the line
+/// number each one came from.
+///
+/// Empty lines and whitespace-only lines are skipped, as in the programs'
+/// `read_input`, but they still count toward the line numbers.
+///
+/// # Returns
+/// * `io::Result<(Vec<i32>, Vec<usize>)>` - The integers and their 1-based
+///   line numbers
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+pub fn read_input_with_lines() -> io::Result<(Vec<i32>, Vec<usize>)> {
+    let stdin = io::stdin();
+    read_lines(stdin.lock())
+}
+
+/// Reads integers and their line numbers from any buffered reader.
+///
+/// # Arguments
+/// * `reader` - The input to read
+///
+/// # Returns
+/// * `io::Result<(Vec<i32>, Vec<usize>)>` - The integers and their 1-based
+///   line numbers
+fn read_lines<R: BufRead>(reader: R) -> io::Result<(Vec<i32>, Vec<usize>)> {
+    let mut numbers = Vec::new();
+    let mut lines = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => {
+                    numbers.push(num);
+                    lines.push(number + 1);
+                }
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok((numbers, lines))
+}
+
+/// Prints the line numbers of the values in sorted order, one per line.
+///
+/// # Arguments
+/// * `order` - The sorting permutation, as returned by an `argsort`
+/// * `lines` - The line number of each value
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+pub fn print_line_numbers(order: &[usize], lines: &[usize]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &index in order {
+        writeln!(writer, "{}", lines[index])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tagged_compares_values_only() {
+        let a = Tagged { value: 3, index: 0 };
+        let b = Tagged { value: 3, index: 9 };
+        let c = Tagged { value: 4, index: 1 };
+        assert_eq!(a, b);
+        assert!(a < c);
+        assert_eq!(b.cmp(&a), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_tag_and_indices_round_trip() {
+        let tagged = tag(&[5, -1, 5]);
+        assert_eq!(tagged[1].value, -1);
+        assert_eq!(indices(&tagged), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_argsort_with_stable_sort() {
+        let order = argsort_with(&[2, 1, 2, 0, 1], |tagged| tagged.sort());
+        assert_eq!(order, vec![3, 1, 4, 0, 2]);
+    }
+
+    #[test]
+    fn test_read_lines_counts_blank_lines() {
+        let input = "7\n\n  \n-2\n3\n";
+        let (numbers, lines) = read_lines(input.as_bytes()).unwrap();
+        assert_eq!(numbers, vec![7, -2, 3]);
+        assert_eq!(lines, vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn test_read_lines_rejects_garbage() {
+        assert!(read_lines("1\nabc\n".as_bytes()).is_err());
+    }
+}
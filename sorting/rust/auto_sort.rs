@@ -0,0 +1,745 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: autoSort.rs
+
+   Description:
+      This program sorts a list of integers with whichever algorithm suits
+      the input best. It reads integers from standard input (one per line),
+      looks at how they are arranged, picks an algorithm, and prints the
+      sorted result to standard output (one integer per line). Designed for
+      use with input/output redirection and benchmarking.
+
+      One linear pass counts the ascending runs and finds the value range.
+      A random sample of 1024 values, kept in input order, gives the share
+      of inverted pairs (which estimates the inversions of the whole input)
+      and the number of distinct values. From these it picks:
+
+        * insertion sort for tiny inputs and inputs that are already sorted
+          or have O(n) inversions, where it runs in near-linear time,
+        * counting sort when the value range is small next to the input,
+        * three-way quick sort when the sample is full of repeated values
+          spread over a wide range,
+        * LSD radix sort for large inputs too disordered for insertion sort,
+        * and quick sort for everything else.
+
+      The chosen algorithm and the reason for the choice are printed to
+      stderr, so the sorted output is unchanged.
+
+      Passing --analyze prints exact measures of disorder for the whole
+      input instead of sorting it: inversions, runs, Rem, Osc, Max and the
+      number of distinct values (see presortedness.rs).
+
+      Passing --stats counts the operations of the chosen algorithm (not
+      of the analysis) and prints them to stderr. Counting sort and radix
+      sort work on the integer keys directly, so apart from finding the
+      range they make no comparisons at all.
+
+      Passing --timings reports how long reading the input, sorting (the
+      analysis and the chosen algorithm together) and printing the output
+      took, to stderr (see phaseTimer.rs). It times an uncounted sort, so
+      it cannot be combined with --stats.
+
+   Usage:
+      ./autoSort < input.txt > output.txt
+      ./autoSort --analyze < input.txt
+      ./autoSort --stats < input.txt > output.txt
+      ./autoSort --timings < input.txt > output.txt
+      cat input.txt | ./autoSort
+*/
+
+mod counting_merge;
+mod insertion;
+mod op_counter;
+mod phase_timer;
+mod presortedness;
+mod three_way_partition;
+mod xorshift;
+
+use insertion::insertion_sort;
+use op_counter::{count, Counted, Tally};
+use phase_timer::PhaseTimer;
+use presortedness::Metrics;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+use three_way_partition::partition_three_way;
+use xorshift::{XorShift64, DEFAULT_SEED};
+
+/// The number of values sampled for the inversion and distinct estimates.
+const SAMPLE_SIZE: usize = 1024;
+
+/// Inputs up to this length are always insertion sorted.
+const SMALL_INPUT: usize = 32;
+
+/// Insertion sort is picked while the estimated inversions stay below this
+/// many per value.
+const NEARLY_SORTED_INVERSIONS: f64 = 4.0;
+
+/// Counting sort is picked when the value range is at most the input length
+/// divided by this, so the count array is small next to the input.
+const COUNTING_RANGE_DIVISOR: u64 = 4;
+
+/// Three-way quick sort is picked when the sample holds at most its length
+/// divided by this many distinct values.
+const FEW_DISTINCT_DIVISOR: usize = 16;
+
+/// Radix sort is picked for disordered inputs of at least this length;
+/// below it, its fixed passes over the data cost more than they save.
+const RADIX_MIN_INPUT: usize = 4096;
+
+/// The algorithms the program can choose from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Strategy {
+    Insertion,
+    Counting,
+    Quick,
+    Radix,
+}
+
+impl Strategy {
+    /// Returns the name printed for the strategy.
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::Insertion => "insertion sort",
+            Strategy::Counting => "counting sort",
+            Strategy::Quick => "quick sort",
+            Strategy::Radix => "radix sort",
+        }
+    }
+
+    /// Sorts a slice with this strategy.
+    ///
+    /// # Arguments
+    /// * `numbers` - A mutable slice of integers to sort
+    fn sort<T: Key>(self, numbers: &mut [T]) {
+        match self {
+            Strategy::Insertion => insertion_sort(numbers),
+            Strategy::Counting => counting_sort(numbers),
+            Strategy::Quick => quick_sort(numbers),
+            Strategy::Radix => radix_sort(numbers),
+        }
+    }
+}
+
+/// A value that counting sort and radix sort can take apart into its
+/// integer key and rebuild from it.
+trait Key: Tally {
+    /// Returns the integer the value sorts by.
+    fn key(self) -> i32;
+
+    /// Builds a value from its integer key.
+    fn from_key(key: i32) -> Self;
+}
+
+impl Key for i32 {
+    fn key(self) -> i32 {
+        self
+    }
+
+    fn from_key(key: i32) -> i32 {
+        key
+    }
+}
+
+impl Key for Counted {
+    fn key(self) -> i32 {
+        self.0
+    }
+
+    fn from_key(key: i32) -> Counted {
+        Counted(key)
+    }
+}
+
+/// What the analysis learned about an input.
+#[derive(Debug, PartialEq)]
+struct Profile {
+    /// The number of values
+    len: usize,
+    /// The number of maximal non-decreasing runs
+    runs: usize,
+    /// The smallest value, or 0 for empty input
+    min: i32,
+    /// The largest value, or 0 for empty input
+    max: i32,
+    /// The number of values sampled
+    sample_len: usize,
+    /// The number of inverted pairs within the sample
+    sample_inversions: u64,
+    /// The number of distinct values within the sample
+    sample_distinct: usize,
+}
+
+impl Profile {
+    /// Returns the number of values between `min` and `max`, inclusive.
+    fn range(&self) -> u64 {
+        (self.max as i64 - self.min as i64) as u64 + 1
+    }
+
+    /// Returns the share of sampled pairs that are inverted: 0 for sorted
+    /// input, about 0.5 for random input and 1 for reverse sorted input.
+    fn inversion_ratio(&self) -> f64 {
+        let pairs = self.sample_len * self.sample_len.saturating_sub(1) / 2;
+        if pairs == 0 {
+            0.0
+        } else {
+            self.sample_inversions as f64 / pairs as f64
+        }
+    }
+
+    /// Estimates the inversions of the whole input from the sample.
+    fn estimated_inversions(&self) -> f64 {
+        let pairs = self.len as f64 * (self.len as f64 - 1.0) / 2.0;
+        self.inversion_ratio() * pairs.max(0.0)
+    }
+}
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Measures the runs, range, sampled inversions and sampled distinct values
+/// of an input.
+///
+/// The sample positions are drawn with a fixed seed, so the same input
+/// always gets the same profile.
+///
+/// # Time Complexity
+/// * O(n + s²) for a sample of s values
+///
+/// # Space Complexity
+/// * O(s)
+///
+/// # Arguments
+/// * `numbers` - The input to analyze
+///
+/// # Returns
+/// * `Profile` - The measurements
+fn analyze(numbers: &[i32]) -> Profile {
+    let len = numbers.len();
+    let mut runs = if len == 0 { 0 } else { 1 };
+    let mut min = numbers.first().copied().unwrap_or(0);
+    let mut max = min;
+
+    for pair in numbers.windows(2) {
+        if pair[1] < pair[0] {
+            runs += 1;
+        }
+    }
+    for &value in numbers {
+        min = min.min(value);
+        max = max.max(value);
+    }
+
+    // Sample positions in increasing order so the sample keeps input order
+    let mut positions: Vec<usize> = if len <= SAMPLE_SIZE {
+        (0..len).collect()
+    } else {
+        let mut rng = XorShift64::new(DEFAULT_SEED);
+        (0..SAMPLE_SIZE).map(|_| rng.below(len)).collect()
+    };
+    positions.sort_unstable();
+    positions.dedup();
+    let sample: Vec<i32> = positions.iter().map(|&i| numbers[i]).collect();
+
+    let mut sample_inversions = 0;
+    for (i, &a) in sample.iter().enumerate() {
+        sample_inversions += sample[i + 1..].iter().filter(|&&b| b < a).count() as u64;
+    }
+
+    let mut distinct = sample.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    Profile {
+        len,
+        runs,
+        min,
+        max,
+        sample_len: sample.len(),
+        sample_inversions,
+        sample_distinct: distinct.len(),
+    }
+}
+
+/// Picks the algorithm for an input and explains why.
+///
+/// # Arguments
+/// * `profile` - The input's measurements
+///
+/// # Returns
+/// * `(Strategy, String)` - The algorithm and the reason it was picked
+fn choose(profile: &Profile) -> (Strategy, String) {
+    let n = profile.len;
+
+    if n <= SMALL_INPUT {
+        return (Strategy::Insertion, format!("only {} values", n));
+    }
+
+    if profile.runs <= 1 {
+        return (Strategy::Insertion, "the input is already sorted, in 1 run".to_string());
+    }
+
+    let inversions = profile.estimated_inversions();
+    if inversions <= NEARLY_SORTED_INVERSIONS * n as f64 {
+        let reason = format!(
+            "about {:.0} inversions in {} runs, so insertion sort runs in near-linear time",
+            inversions, profile.runs
+        );
+        return (Strategy::Insertion, reason);
+    }
+
+    let range = profile.range();
+    if range <= n as u64 / COUNTING_RANGE_DIVISOR {
+        let reason = format!(
+            "only {} possible values, {} to {}, for {} values",
+            range, profile.min, profile.max, n
+        );
+        return (Strategy::Counting, reason);
+    }
+
+    if profile.sample_distinct <= profile.sample_len / FEW_DISTINCT_DIVISOR {
+        let reason = format!(
+            "only {} distinct values in a sample of {}, spread over a range of {}",
+            profile.sample_distinct, profile.sample_len, range
+        );
+        return (Strategy::Quick, reason);
+    }
+
+    let disorder = format!(
+        "{} values over a range of {} with {:.1}% of sampled pairs inverted",
+        n,
+        range,
+        100.0 * profile.inversion_ratio()
+    );
+    if n >= RADIX_MIN_INPUT {
+        (Strategy::Radix, disorder)
+    } else {
+        (Strategy::Quick, format!("{}, too few for radix sort", disorder))
+    }
+}
+
+/// Sorts a slice of integers in ascending order using counting sort.
+///
+/// Counts how often each value between the minimum and maximum occurs and
+/// writes the values back in order.
+///
+/// # Time Complexity
+/// * O(n + k) for a range of k values
+///
+/// # Space Complexity
+/// * O(k) - one count per possible value
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of integers to sort
+fn counting_sort<T: Key>(numbers: &mut [T]) {
+    let (min, max) = match (numbers.iter().min(), numbers.iter().max()) {
+        (Some(&min), Some(&max)) => (min.key(), max.key()),
+        _ => return,
+    };
+
+    let offset = |value: i32| (value as i64 - min as i64) as usize;
+    let mut counts = vec![0usize; offset(max) + 1];
+    for &value in numbers.iter() {
+        counts[offset(value.key())] += 1;
+    }
+
+    let mut index = 0;
+    for (slot, &count) in counts.iter().enumerate() {
+        let value = (min as i64 + slot as i64) as i32;
+        numbers[index..index + count].fill(T::from_key(value));
+        index += count;
+    }
+    for slot in numbers.iter() {
+        T::wrote(slot);
+    }
+}
+
+/// Sorts a slice of integers in ascending order using LSD radix sort.
+///
+/// Flipping the sign bit maps i32 order onto u32 order. The keys are then
+/// distributed by one byte at a time, least significant first; each pass
+/// is stable, so after four passes the keys are sorted. A pass whose byte
+/// is the same for every key would not move anything and is skipped.
+///
+/// # Time Complexity
+/// * O(n) - at most four counting passes
+///
+/// # Space Complexity
+/// * O(n) - one buffer the size of the input
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of integers to sort
+fn radix_sort<T: Key>(numbers: &mut [T]) {
+    let mut keys: Vec<u32> = numbers.iter().map(|&x| x.key() as u32 ^ 0x8000_0000).collect();
+    let mut buffer = vec![0u32; keys.len()];
+    T::copied(keys.len());
+
+    for shift in (0..32).step_by(8) {
+        let mut counts = [0usize; 256];
+        for &key in keys.iter() {
+            counts[(key >> shift) as usize & 0xFF] += 1;
+        }
+        if counts.contains(&keys.len()) {
+            continue;
+        }
+
+        // Turn the counts into the first output position of each byte
+        let mut next = 0;
+        for count in counts.iter_mut() {
+            let start = next;
+            next += *count;
+            *count = start;
+        }
+
+        for &key in keys.iter() {
+            let byte = (key >> shift) as usize & 0xFF;
+            buffer[counts[byte]] = key;
+            counts[byte] += 1;
+        }
+        T::copied(keys.len());
+        std::mem::swap(&mut keys, &mut buffer);
+    }
+
+    for (number, &key) in numbers.iter_mut().zip(keys.iter()) {
+        *number = T::from_key((key ^ 0x8000_0000) as i32);
+    }
+    T::copied(keys.len());
+}
+
+/// Sorts a slice of integers in ascending order using three-way quick sort.
+///
+/// Each pass splits the slice around a median-of-three pivot into values
+/// less than, equal to and greater than it with the partition from
+/// threeWayPartition.rs, so repeated values are placed once and never
+/// looked at again. Short slices are insertion sorted, and
+/// the loop recurses only into the smaller side to bound the stack depth.
+///
+/// # Time Complexity
+/// * O(n log n) on average, O(n log d) for d distinct values
+///
+/// # Space Complexity
+/// * O(log n) - recursion stack
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of integers to sort
+fn quick_sort<T: Tally>(mut numbers: &mut [T]) {
+    while numbers.len() > SMALL_INPUT {
+        let len = numbers.len();
+        let mut candidates = [numbers[0], numbers[len / 2], numbers[len - 1]];
+        candidates.sort_unstable();
+        let pivot = candidates[1];
+
+        // [..less] < pivot, [less..greater] == pivot, [greater..] > pivot
+        let (less, greater) = partition_three_way(numbers, pivot);
+
+        let (left, rest) = numbers.split_at_mut(less);
+        let right = &mut rest[greater - less..];
+        if left.len() < right.len() {
+            quick_sort(left);
+            numbers = right;
+        } else {
+            quick_sort(right);
+            numbers = left;
+        }
+    }
+
+    insertion_sort(numbers);
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Main entry point for the adaptive sort program.
+///
+/// Orchestrates reading from stdin, choosing an algorithm, sorting, and
+/// writing to stdout. Exits with status code 1 if any errors occur during
+/// execution.
+fn main() {
+    // Check for the --analyze flag to report disorder instead of sorting
+    let analyze_only = env::args().skip(1).any(|arg| arg == "--analyze");
+
+    // Check for the --stats flag to count the operations of the chosen sort
+    let stats = env::args().skip(1).any(|arg| arg == "--stats");
+
+    // Check for the --timings flag to time reading, sorting and printing; it
+    // times a plain sort, so it cannot be combined with counting one
+    let timings = env::args().skip(1).any(|arg| arg == "--timings");
+    if timings && stats {
+        eprintln!("Error: --timings cannot be combined with --stats");
+        process::exit(1);
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // Measure the whole input instead of sorting it
+    if analyze_only {
+        let stdout = io::stdout();
+        if let Err(e) = Metrics::measure(&numbers).report(BufWriter::new(stdout.lock())) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Pick an algorithm and report the choice on stderr
+    let (strategy, reason) = choose(&analyze(&numbers));
+    eprintln!("auto: {} ({})", strategy.name(), reason);
+
+    if stats {
+        let counts = count(&mut numbers, |slice| strategy.sort(slice));
+        eprint!("{}", counts.summary(strategy.name(), numbers.len()));
+    } else {
+        strategy.sort(&mut numbers);
+    }
+
+    timer.lap("sort");
+
+    // Print the sorted numbers to standard output
+    if let Err(e) = print_output(&numbers) {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if timings {
+        eprint!("{}", timer.summary(strategy.name(), numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Strategy; 4] =
+        [Strategy::Insertion, Strategy::Counting, Strategy::Quick, Strategy::Radix];
+
+    /// Returns the strategy chosen for an input.
+    fn chosen(numbers: &[i32]) -> Strategy {
+        choose(&analyze(numbers)).0
+    }
+
+    #[test]
+    fn test_every_strategy_sorts() {
+        let mut rng = XorShift64::new(12345);
+        let mut inputs = vec![
+            vec![],
+            vec![7],
+            vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5],
+            vec![-3, 5, -1, 0, -9, 2],
+            vec![i32::MAX, i32::MIN, 0, -1, 1, i32::MIN, i32::MAX],
+            rng.values(5000, 0..1 << 24),
+            rng.values(5000, 0..7),
+            (0..300).rev().collect(),
+        ];
+        inputs.push(inputs[5].iter().map(|&x| x - (1 << 23)).collect());
+
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+            for &strategy in ALL.iter() {
+                // Counting sort's array would cover the full i32 range here
+                if strategy == Strategy::Counting && input.contains(&i32::MAX) {
+                    continue;
+                }
+                let mut actual = input.clone();
+                strategy.sort(&mut actual);
+                assert_eq!(actual, expected, "{}", strategy.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_analyze_small_input() {
+        let profile = analyze(&[3, 1, 2, 2, -4]);
+        assert_eq!(
+            profile,
+            Profile {
+                len: 5,
+                runs: 3,
+                min: -4,
+                max: 3,
+                sample_len: 5,
+                sample_inversions: 7,
+                sample_distinct: 4,
+            }
+        );
+        assert_eq!(profile.range(), 8);
+        assert!((profile.inversion_ratio() - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_extreme_range() {
+        let profile = analyze(&[i32::MAX, i32::MIN]);
+        assert_eq!(profile.range(), 1 << 32);
+        assert_eq!(profile.inversion_ratio(), 1.0);
+        assert_eq!(analyze(&[]).runs, 0);
+    }
+
+    #[test]
+    fn test_sample_estimates_inversions() {
+        let sorted: Vec<i32> = (0..100_000).collect();
+        let reverse: Vec<i32> = sorted.iter().rev().copied().collect();
+        assert_eq!(analyze(&sorted).inversion_ratio(), 0.0);
+        assert_eq!(analyze(&reverse).inversion_ratio(), 1.0);
+
+        let random = XorShift64::new(12345).values(100_000, 0..1 << 30);
+        let ratio = analyze(&random).inversion_ratio();
+        assert!((0.45..0.55).contains(&ratio), "{}", ratio);
+    }
+
+    #[test]
+    fn test_sample_agrees_with_exact_metrics() {
+        let input = XorShift64::new(12345).values(50_000, 0..1 << 20);
+        let profile = analyze(&input);
+        let metrics = Metrics::measure(&input);
+        assert_eq!(profile.runs, metrics.runs);
+        assert!((profile.inversion_ratio() - metrics.inversion_ratio()).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_choices_for_benchmark_distributions() {
+        let n = 100_000;
+        let sorted: Vec<i32> = (0..n).collect();
+        let reverse: Vec<i32> = (0..n).rev().collect();
+
+        // Built like generate_nearly_sorted in test_suite_generate.py: 1% of
+        // the positions swapped with random partners anywhere in the input.
+        // Long-distance swaps leave Θ(n²) inversions, too many for
+        // insertion sort, as the real nearly_sorted.txt files show
+        let mut nearly_sorted = sorted.clone();
+        let mut rng = XorShift64::new(DEFAULT_SEED);
+        for _ in 0..n / 100 {
+            let (i, j) = (rng.below(n as usize), rng.below(n as usize));
+            nearly_sorted.swap(i, j);
+        }
+
+        // Swapping neighbours adds only one inversion per swap
+        let mut adjacent_swaps = sorted.clone();
+        for i in (0..n as usize).step_by(1000) {
+            adjacent_swaps.swap(i, i + 1);
+        }
+
+        assert_eq!(chosen(&sorted), Strategy::Insertion);
+        assert_eq!(chosen(&vec![42; n as usize]), Strategy::Insertion);
+        assert_eq!(chosen(&adjacent_swaps), Strategy::Insertion);
+        assert_eq!(chosen(&nearly_sorted), Strategy::Radix);
+        assert_eq!(chosen(&rng.values(n as usize, 0..10)), Strategy::Counting);
+        assert_eq!(chosen(&rng.values(n as usize, 0..n)), Strategy::Radix);
+        assert_eq!(chosen(&reverse), Strategy::Radix);
+    }
+
+    #[test]
+    fn test_choices_for_other_inputs() {
+        assert_eq!(chosen(&[5, 4, 3, 2, 1]), Strategy::Insertion);
+
+        // Few distinct values spread far apart
+        let mut rng = XorShift64::new(12345);
+        let spread: Vec<i32> = rng.values(100_000, 0..8).iter().map(|&x| x * 100_000_000).collect();
+        assert_eq!(chosen(&spread), Strategy::Quick);
+
+        // Disordered, but too short for radix sort
+        assert_eq!(chosen(&rng.values(1000, 0..1 << 30)), Strategy::Quick);
+    }
+
+    #[test]
+    fn test_reason_mentions_measurements() {
+        let (_, reason) = choose(&analyze(&XorShift64::new(12345).values(100_000, 0..10)));
+        assert_eq!(reason, "only 10 possible values, 0 to 9, for 100000 values");
+    }
+
+    #[test]
+    fn test_stats_of_each_strategy() {
+        let input = XorShift64::new(12345).values(4096, 0..1 << 20);
+        let stats = |strategy: Strategy| {
+            let mut arr = input.clone();
+            let counts = count(&mut arr, |slice| strategy.sort(slice));
+            assert!(arr.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", strategy);
+            counts
+        };
+
+        // Radix sort compares nothing; three passes move the keys, since
+        // values below 2^20 share their top byte
+        let radix = stats(Strategy::Radix);
+        assert_eq!(radix.comparisons, 0);
+        assert_eq!(radix.buffer_copies, 4096 * 5);
+
+        // Counting sort only compares to find the range
+        let counting = stats(Strategy::Counting);
+        assert_eq!(counting.comparisons, 2 * 4095);
+        assert_eq!(counting.writes, 4096);
+
+        // Quick sort stays near n log2 n, insertion sort near n²/4
+        let quick = stats(Strategy::Quick);
+        assert!(quick.comparisons < 2 * 4096 * 12, "{:?}", quick);
+        let insertion = stats(Strategy::Insertion);
+        assert!(insertion.comparisons > 4096 * 4095 / 5, "{:?}", insertion);
+    }
+}
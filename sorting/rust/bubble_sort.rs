@@ -12,14 +12,68 @@
       integer per line). Designed for use with input/output redirection and
       benchmarking.
 
+      Passing --indices prints the original line number of each value in
+      sorted order instead of the values, for joining the result back to
+      other columns of the input. It replaces the output, so it cannot be
+      combined with --stats, --trace, --sound or --timings.
+
+      Passing --stats also prints how many comparisons, swaps, element
+      writes and buffer copies the sort performed to stderr, along with
+      n log2 n and n(n-1)/2 for comparing them with the documented cases.
+
+      Passing --trace writes every comparison and swap to stderr as JSON
+      Lines (see opTrace.rs), and --trace=FILE writes them to FILE, for
+      animating the passes in a visualizer.
+
+      Passing --sound=FILE writes the sound of the sort to FILE as a WAV
+      file (see opSound.rs): each value compared or swapped is heard as a
+      tone whose pitch rises with the value.
+
+      Passing --timings reports to stderr how long reading the input, the
+      sort itself and printing the output took (see phaseTimer.rs). It
+      times a plain sort, so it cannot be combined with --stats, --trace
+      or --sound.
+
    Usage:
       ./bubbleSort < input.txt > output.txt
+      ./bubbleSort --indices < input.txt > order.txt
+      ./bubbleSort --stats < input.txt > output.txt
+      ./bubbleSort --trace=trace.jsonl < input.txt > output.txt
+      ./bubbleSort --sound=bubble.wav < input.txt > output.txt
+      ./bubbleSort --timings < input.txt > output.txt
       cat input.txt | ./bubbleSort
 */
 
+mod argsort;
+mod op_counter;
+mod op_sound;
+mod op_trace;
+mod phase_timer;
+
+use argsort::{argsort_with, print_line_numbers, read_input_with_lines, Tagged};
+use op_counter::{count, Tally};
+use op_trace::Target;
+use phase_timer::PhaseTimer;
+use std::env;
+use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::process;
 
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Whether to print original line numbers instead of sorted values
+    indices: bool,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Where to write a trace of the sort's operations, if anywhere
+    trace: Option<Target>,
+    /// Where to write the sound of the sort's array accesses, if anywhere
+    sound: Option<String>,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
+
 /// Reads integers from standard input, one per line.
 ///
 /// Uses a buffered reader for efficient I/O when handling large input files.
@@ -80,8 +134,8 @@ fn read_input() -> io::Result<Vec<i32>> {
 /// * O(1) - sorts in place
 ///
 /// # Arguments
-/// * `numbers` - A mutable slice of i32 integers to sort
-fn bubble_sort(numbers: &mut [i32]) {
+/// * `numbers` - A mutable slice of values to sort
+fn bubble_sort<T: Tally>(numbers: &mut [T]) {
     let n = numbers.len();
     
     // Outer loop: controls the number of passes
@@ -94,6 +148,7 @@ fn bubble_sort(numbers: &mut [i32]) {
             if numbers[j] > numbers[j + 1] {
                 // Swap adjacent elements if they're out of order
                 numbers.swap(j, j + 1);
+                T::swapped(&numbers[j], &numbers[j + 1]);
                 swapped = true;
             }
         }
@@ -105,6 +160,23 @@ fn bubble_sort(numbers: &mut [i32]) {
     }
 }
 
+// Tagged values are sorted for --indices, where nothing is counted
+impl Tally for Tagged {}
+
+/// Returns the permutation that sorts `numbers`, computed with bubble sort.
+///
+/// Bubble sort only swaps neighbors that are strictly out of order, so it
+/// is stable: equal values keep their original relative order.
+///
+/// # Arguments
+/// * `numbers` - The values to sort; they are not modified
+///
+/// # Returns
+/// * `Vec<usize>` - Entry i is the original index of the i-th smallest value
+fn argsort(numbers: &[i32]) -> Vec<usize> {
+    argsort_with(numbers, bubble_sort)
+}
+
 /// Prints each integer in the vector to standard output, one per line.
 ///
 /// Uses a buffered writer for efficient I/O when handling large output.
@@ -129,12 +201,103 @@ fn print_output(numbers: &[i32]) -> io::Result<()> {
     Ok(())
 }
 
+/// Parses the command-line arguments.
+///
+/// Recognizes `--indices`, `--stats`, `--trace[=FILE]`, `--sound=FILE`
+/// and `--timings`; anything else is an error. `--indices` replaces the
+/// output, so it cannot be combined with the others. `--trace` and
+/// `--sound` record a plain sort, so neither can be combined with `--stats`
+/// or with each other. `--timings` times a plain sort too, so it cannot be
+/// combined with `--stats`, `--trace` or `--sound`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        indices: false,
+        stats: false,
+        trace: None,
+        sound: None,
+        timings: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--indices" => options.indices = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            other => {
+                if let Some(target) = Target::from_arg(other) {
+                    options.trace = Some(target);
+                } else if let Some(path) = op_sound::from_arg(other) {
+                    options.sound = Some(path);
+                } else {
+                    return Err(format!("Unknown argument: {}", other));
+                }
+            }
+        }
+    }
+
+    if options.stats && options.indices {
+        return Err("--stats cannot be combined with --indices".to_string());
+    }
+
+    if options.trace.is_some() && (options.indices || options.stats) {
+        return Err("--trace cannot be combined with --stats or --indices".to_string());
+    }
+
+    let recorded = options.stats || options.trace.is_some();
+    if options.sound.is_some() && (options.indices || recorded) {
+        return Err("--sound cannot be combined with --stats, --trace or --indices".to_string());
+    }
+
+    let recorded = recorded || options.sound.is_some();
+    if options.timings && (options.indices || recorded) {
+        return Err(
+            "--timings cannot be combined with --stats, --trace, --sound or --indices"
+                .to_string(),
+        );
+    }
+
+    Ok(options)
+}
+
 /// Main entry point for the bubble sort program.
 ///
 /// Orchestrates reading from stdin, sorting, and writing to stdout.
 /// Exits with status code 1 if any errors occur during execution.
 fn main() {
-    // Read integers from standard input
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Print the original line numbers in sorted order instead of the values
+    if options.indices {
+        let (numbers, lines) = match read_input_with_lines() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = print_line_numbers(&argsort(&numbers), &lines) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
     let mut numbers = match read_input() {
         Ok(nums) => nums,
         Err(e) => {
@@ -142,15 +305,44 @@ fn main() {
             process::exit(1);
         }
     };
+    timer.lap("read_input");
 
-    // Sort the numbers using bubble sort
-    bubble_sort(&mut numbers);
+    // Sort the numbers using bubble sort, tracing, playing or counting operations if asked
+    if let Some(target) = options.trace {
+        let written = target
+            .open()
+            .and_then(|writer| op_trace::record(&mut numbers, "bubble sort", writer, bubble_sort));
+        if let Err(e) = written {
+            eprintln!("Error writing trace: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(path) = options.sound {
+        let written = File::create(&path)
+            .and_then(|file| op_sound::record(&mut numbers, BufWriter::new(file), bubble_sort));
+        if let Err(e) = written {
+            eprintln!("Error writing sound: {}", e);
+            process::exit(1);
+        }
+    } else if options.stats {
+        let counts = count(&mut numbers, bubble_sort);
+        eprint!("{}", counts.summary("bubble sort", numbers.len()));
+    } else {
+        bubble_sort(&mut numbers);
+    }
+
+    timer.lap("sort");
 
     // Print the sorted numbers to standard output
     if let Err(e) = print_output(&numbers) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("bubble sort", numbers.len()));
+    }
 }
 
 #[cfg(test)]
@@ -212,4 +404,136 @@ mod tests {
         bubble_sort(&mut arr);
         assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
     }
+
+    #[test]
+    fn test_argsort_is_stable() {
+        let numbers = vec![3, 1, 3, 2, 1, 3];
+        assert_eq!(argsort(&numbers), vec![1, 4, 3, 0, 2, 5]);
+        assert_eq!(argsort(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_stats_match_documented_cases() {
+        let n = 100;
+
+        // Best case: one pass of n - 1 comparisons and no swaps
+        let mut sorted: Vec<i32> = (0..n).collect();
+        let counts = count(&mut sorted, bubble_sort);
+        assert_eq!((counts.comparisons, counts.swaps), (n as u64 - 1, 0));
+
+        // Worst case: every one of the n(n-1)/2 pairs is compared and swapped
+        let mut reverse: Vec<i32> = (0..n).rev().collect();
+        let counts = count(&mut reverse, bubble_sort);
+        let pairs = (n * (n - 1) / 2) as u64;
+        assert_eq!((counts.comparisons, counts.swaps), (pairs, pairs));
+        assert_eq!(reverse, (0..n).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_trace_matches_stats() {
+        use op_trace::Event;
+        use std::fs;
+
+        let path = env::temp_dir().join(format!("bubble_trace_{}.jsonl", process::id()));
+        let target = Target::File(path.to_string_lossy().into_owned());
+        let mut numbers = vec![4, 1, 3, 2];
+        let writer = target.open().unwrap();
+        op_trace::record(&mut numbers, "bubble sort", writer, bubble_sort).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4]);
+
+        // One event per counted comparison and swap, always between neighbors
+        let events: Vec<Event> = text.lines().map(|line| Event::parse(line).unwrap()).collect();
+        let counts = count(&mut [4, 1, 3, 2], bubble_sort);
+        let compares = events.iter().filter(|e| matches!(e, Event::Compare { .. })).count();
+        assert_eq!((compares as u64, counts.comparisons), (6, 6));
+        let mut array = Vec::new();
+        let mut swaps = 0;
+        for event in &events {
+            if let Event::Swap { i: Some(i), j: Some(j), .. } = *event {
+                assert_eq!(j, i + 1);
+                swaps += 1;
+            }
+            event.apply(&mut array);
+        }
+        assert_eq!((swaps, counts.swaps), (4, 4));
+        assert_eq!(array, numbers);
+    }
+
+    #[test]
+    fn test_sound_has_a_tone_per_access() {
+        let mut numbers = vec![5, 1, 4, 2, 3];
+        let mut wav = Vec::new();
+        op_sound::record(&mut numbers, &mut wav, bubble_sort).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+
+        // A comparison accesses two values and so does a swap
+        let counts = count(&mut [5, 1, 4, 2, 3], bubble_sort);
+        let accesses = (2 * counts.comparisons + 2 * counts.swaps) as usize;
+        let samples = accesses * op_sound::tone_length(accesses);
+        assert_eq!(wav.len(), 44 + 2 * samples);
+        assert_eq!(&wav[40..44], &(2 * samples as u32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                indices: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--trace=out.jsonl"])).map(|o| o.trace),
+            Ok(Some(Target::File("out.jsonl".to_string())))
+        );
+        assert_eq!(
+            parse_args(&args(&["--sound=out.wav"])).map(|o| o.sound),
+            Ok(Some("out.wav".to_string()))
+        );
+        assert_eq!(
+            parse_args(&args(&["--indices"])).map(|o| (o.indices, o.stats)),
+            Ok((true, false))
+        );
+        assert_eq!(
+            parse_args(&args(&["--timings"])).map(|o| o.timings),
+            Ok(true)
+        );
+
+        // --indices replaces the output, and the others each record or time
+        // a plain sort
+        for flags in [
+            ["--indices", "--stats"],
+            ["--indices", "--trace"],
+            ["--indices", "--sound=out.wav"],
+            ["--indices", "--timings"],
+            ["--stats", "--trace"],
+            ["--trace", "--sound=out.wav"],
+            ["--sound=out.wav", "--stats"],
+            ["--timings", "--stats"],
+            ["--trace=out.jsonl", "--timings"],
+            ["--timings", "--sound=out.wav"],
+        ]
+        .iter()
+        {
+            let error = parse_args(&args(flags)).unwrap_err();
+            assert!(error.contains("cannot be combined"), "{:?}: {}", flags, error);
+        }
+
+        // Misspelled flags are rejected rather than ignored
+        assert_eq!(
+            parse_args(&args(&["--stat"])),
+            Err("Unknown argument: --stat".to_string())
+        );
+        assert!(parse_args(&args(&["input.txt"])).is_err());
+    }
 }
@@ -0,0 +1,189 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: countingMerge.rs
+
+   Description:
+      This module holds the merge step of merge sort, which also counts the
+      inversions between the two halves it merges: whenever it takes a
+      value from the right half, that value is smaller than every value
+      left in the left half, and each of those is one inversion. Merge sort
+      uses the merge to sort, and a merge sort pass that adds up the counts
+      of every merge counts the inversions of a whole input in O(n log n)
+      time, for mergeSort.rs's --count-inversions and for presortedness.rs.
+
+      The merge reports its copies and writes through the `Tally` hooks of
+      opCounter.rs, which the program must include next to this module.
+
+   Usage:
+      mod counting_merge;
+      mod op_counter;
+      use counting_merge::{count_inversions, merge};
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::op_counter::Tally;
+
+/// Merges two sorted subarrays into a single sorted subarray.
+///
+/// The first subarray spans from index `left` to `mid`, and the second
+/// subarray spans from index `mid+1` to `right`. Uses a temporary buffer
+/// to hold the merged result before copying back to the original array.
+///
+/// Also counts the inversions between the two subarrays: a value taken
+/// from the right subarray is smaller than every value still waiting in
+/// the left one, and forms an inversion with each of them.
+///
+/// # Arguments
+/// * `arr` - The array containing the two subarrays to merge
+/// * `buffer` - Temporary buffer for storing merged elements
+/// * `left` - Starting index of the first subarray
+/// * `mid` - Ending index of the first subarray
+/// * `right` - Ending index of the second subarray
+///
+/// # Returns
+/// * `u64` - The number of pairs with the larger value in the first subarray
+pub fn merge<T: Tally>(
+    arr: &mut [T],
+    buffer: &mut [T],
+    left: usize,
+    mid: usize,
+    right: usize,
+) -> u64 {
+    T::merging(&arr[left..=mid], &arr[mid + 1..=right]);
+
+    // Copy the range we're working with into the buffer
+    buffer[left..=right].copy_from_slice(&arr[left..=right]);
+    T::copied(right - left + 1);
+
+    let mut i = left;      // Index for left subarray
+    let mut j = mid + 1;   // Index for right subarray
+    let mut k = left;      // Index for merged array
+    let mut inversions = 0;
+
+    // Merge elements from both subarrays in sorted order
+    while i <= mid && j <= right {
+        if buffer[i] <= buffer[j] {
+            arr[k] = buffer[i];
+            T::restored(&arr[k]);
+            i += 1;
+        } else {
+            // buffer[j] jumps ahead of the mid - i + 1 values left in the left half
+            arr[k] = buffer[j];
+            T::restored(&arr[k]);
+            j += 1;
+            inversions += (mid + 1 - i) as u64;
+        }
+        k += 1;
+    }
+
+    // Copy any remaining elements from the left subarray
+    while i <= mid {
+        arr[k] = buffer[i];
+        T::restored(&arr[k]);
+        i += 1;
+        k += 1;
+    }
+
+    // Copy any remaining elements from the right subarray
+    while j <= right {
+        arr[k] = buffer[j];
+        T::restored(&arr[k]);
+        j += 1;
+        k += 1;
+    }
+
+    // Every position of the range was copied back from the buffer
+    T::copied(right - left + 1);
+    inversions
+}
+
+/// Counts the pairs i < j with numbers[i] > numbers[j].
+///
+/// Merge sorts a copy all the way down to single elements and adds up the
+/// inversions found by every merge. A pair is counted by the one merge
+/// that brings its two values together, so each is counted exactly once.
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Space Complexity
+/// * O(n) - the copy and the merge buffer
+///
+/// # Arguments
+/// * `numbers` - The values to examine; they are not modified
+///
+/// # Returns
+/// * `u64` - The number of inversions, at most n(n-1)/2 (about 5 * 10^11
+///   for a million values, far beyond u32)
+pub fn count_inversions<T: Tally>(numbers: &[T]) -> u64 {
+    if numbers.len() <= 1 {
+        return 0;
+    }
+    let mut arr = numbers.to_vec();
+    let mut buffer = arr.clone();
+    let right = arr.len() - 1;
+    count_inversions_helper(&mut arr, &mut buffer, 0, right)
+}
+
+/// Helper for `count_inversions` that sorts `arr[left..=right]` and returns
+/// the inversions within it.
+///
+/// # Arguments
+/// * `arr` - The array slice to sort
+/// * `buffer` - Temporary buffer for merging (reused across recursive calls)
+/// * `left` - Starting index of the portion to sort (inclusive)
+/// * `right` - Ending index of the portion to sort (inclusive)
+///
+/// # Returns
+/// * `u64` - The number of inversions in the portion
+fn count_inversions_helper<T: Tally>(
+    arr: &mut [T],
+    buffer: &mut [T],
+    left: usize,
+    right: usize,
+) -> u64 {
+    if left >= right {
+        return 0;
+    }
+
+    let mid = left + (right - left) / 2;
+    let inversions = count_inversions_helper(arr, buffer, left, mid)
+        + count_inversions_helper(arr, buffer, mid + 1, right);
+    inversions + merge(arr, buffer, left, mid, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_counts_crossing_pairs() {
+        let mut arr = vec![9, 2, 5, 8, 1, 5, 6, 0];
+        let mut buffer = arr.clone();
+        // Merges [2, 5, 8] with [1, 5, 6]: 1 passes three values, 5 one
+        // (the equal 5 stays behind) and 6 one
+        assert_eq!(merge(&mut arr, &mut buffer, 1, 3, 6), 3 + 1 + 1);
+        assert_eq!(arr, vec![9, 1, 2, 5, 5, 6, 8, 0]);
+
+        let mut arr = vec![1, 2, 3, 4];
+        let mut buffer = arr.clone();
+        assert_eq!(merge(&mut arr, &mut buffer, 0, 1, 3), 0);
+        assert_eq!(arr, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_count_inversions() {
+        assert_eq!(count_inversions::<i32>(&[]), 0);
+        assert_eq!(count_inversions(&[7]), 0);
+        assert_eq!(count_inversions(&[3, 1, 2]), 2);
+        assert_eq!(count_inversions(&[2, 2, 2]), 0);
+
+        let numbers: Vec<i32> = (0..100).rev().collect();
+        assert_eq!(count_inversions(&numbers), 100 * 99 / 2);
+        assert_eq!(numbers[0], 99);
+    }
+}
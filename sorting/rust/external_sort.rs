@@ -0,0 +1,388 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: externalSort.rs
+
+   Description:
+      This module sorts inputs that are too large to hold in memory. The
+      input is read in chunks that fit in a memory budget, each chunk is
+      sorted in memory with the including program's own algorithm, and the
+      sorted chunks (runs) are written to temporary files. The runs are then
+      merged with the k-way merge from kwayMerge.rs and streamed to the
+      output.
+
+      The budget also limits the merge: each open run gets a read buffer
+      carved out of it, so at most budget / 4 KiB runs (and never more than
+      64) are merged at once. With more runs than that, groups of runs are
+      merged into longer runs first, in as many passes as needed.
+
+      Temporary files live in a fresh directory that is removed when the
+      sort finishes, whether it succeeds or fails.
+
+      The module has no main function. It merges with kwayMerge.rs, which
+      the program must include next to it (along with the loser tree in
+      loserTree.rs that the merge uses).
+
+   Usage:
+      mod external_sort;
+      mod kway_merge;
+      mod loser_tree;
+      use external_sort::{external_sort, parse_size};
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::kway_merge::merge_readers;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The memory budget used when none is given: 64 MiB.
+pub const DEFAULT_MEMORY: usize = 64 << 20;
+
+/// The smallest read buffer worth giving a run during a merge.
+const MIN_READ_BUFFER: usize = 4 << 10;
+
+/// The most runs merged at once, which bounds the number of open files.
+const MAX_FAN_IN: usize = 64;
+
+/// Distinguishes the temporary directories of sorts in the same process.
+static NEXT_DIRECTORY: AtomicUsize = AtomicUsize::new(0);
+
+/// What an external sort did.
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    /// The number of values sorted
+    pub values: usize,
+    /// The number of sorted runs written to temporary files
+    pub runs: usize,
+    /// The number of merge passes over the data, including the final one
+    pub merge_passes: usize,
+}
+
+/// A temporary directory that is removed, with its contents, when dropped.
+struct TempDir {
+    /// The directory's path
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a new, empty directory inside `parent`.
+    ///
+    /// # Arguments
+    /// * `parent` - Where to create the directory
+    ///
+    /// # Returns
+    /// * `Result<TempDir, String>` - The directory, or an error if it could
+    ///   not be created
+    fn new(parent: &Path) -> Result<TempDir, String> {
+        let id = NEXT_DIRECTORY.fetch_add(1, Ordering::Relaxed);
+        let path = parent.join(format!("merge-sort-{}-{}", process::id(), id));
+        fs::create_dir(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(TempDir { path })
+    }
+
+    /// Returns the path of the run file with the given number.
+    fn run(&self, number: usize) -> PathBuf {
+        self.path.join(format!("run-{}.txt", number))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // Nothing useful can be done if cleanup fails
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Parses a byte count with an optional K, M or G suffix (powers of 1024).
+///
+/// # Arguments
+/// * `text` - The size, such as "4096", "512K" or "64M"
+///
+/// # Returns
+/// * `Result<usize, String>` - The number of bytes, or an error message
+pub fn parse_size(text: &str) -> Result<usize, String> {
+    let upper = text.to_ascii_uppercase();
+    let (digits, shift) = match upper.chars().last() {
+        Some('K') => (&upper[..upper.len() - 1], 10),
+        Some('M') => (&upper[..upper.len() - 1], 20),
+        Some('G') => (&upper[..upper.len() - 1], 30),
+        _ => (&upper[..], 0),
+    };
+
+    digits
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(1 << shift))
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("Invalid size: {}", text))
+}
+
+/// Sorts integers from `input`, one per line, and writes them to `output`
+/// while holding only about `memory` bytes of values at a time.
+///
+/// Each chunk holds `memory / 8` values, leaving room for merge sort's
+/// buffer of the same size. If the whole input fits in one chunk, it is
+/// sorted and written directly without any temporary files.
+///
+/// # Time Complexity
+/// * O(n log n) comparisons, plus O(n) reads and writes per merge pass
+///
+/// # Space Complexity
+/// * O(memory) in RAM and O(n) on disk
+///
+/// # Arguments
+/// * `input` - Integers to sort, one per line; blank lines are skipped
+/// * `output` - Where the sorted integers are written
+/// * `memory` - The memory budget in bytes
+/// * `temp_parent` - Where to create the directory for the run files
+/// * `sort` - Sorts one chunk in memory
+///
+/// # Returns
+/// * `Result<Summary, String>` - What the sort did, or the first error
+pub fn external_sort<R, W, F>(
+    input: R,
+    mut output: W,
+    memory: usize,
+    temp_parent: &Path,
+    mut sort: F,
+) -> Result<Summary, String>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&mut [i32]),
+{
+    let chunk_len = (memory / (2 * mem::size_of::<i32>())).max(1);
+    let fan_in = (memory / MIN_READ_BUFFER).clamp(2, MAX_FAN_IN);
+    let read_buffer = (memory / fan_in).max(1);
+
+    let mut dir = None;
+    let mut runs = Vec::new();
+    let mut chunk = Vec::with_capacity(chunk_len);
+    let mut values = 0;
+
+    // Phase 1: cut the input into sorted runs
+    for line in input.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let value = trimmed
+            .parse::<i32>()
+            .map_err(|_| format!("Failed to parse integer: {}", trimmed))?;
+        chunk.push(value);
+        values += 1;
+
+        if chunk.len() == chunk_len {
+            if dir.is_none() {
+                dir = Some(TempDir::new(temp_parent)?);
+            }
+            let path = dir.as_ref().unwrap().run(runs.len());
+            sort(&mut chunk);
+            write_run(&path, &chunk)?;
+            runs.push(path);
+            chunk.clear();
+        }
+    }
+
+    // Everything fit in memory, so no merging is needed
+    let dir = match dir {
+        Some(dir) => dir,
+        None => {
+            sort(&mut chunk);
+            write_values(&mut output, &chunk).map_err(|e| e.to_string())?;
+            return Ok(Summary { values, runs: 0, merge_passes: 0 });
+        }
+    };
+
+    if !chunk.is_empty() {
+        let path = dir.run(runs.len());
+        sort(&mut chunk);
+        write_run(&path, &chunk)?;
+        runs.push(path);
+    }
+    drop(chunk);
+
+    let total_runs = runs.len();
+    let mut next_run = total_runs;
+    let mut merge_passes = 0;
+
+    // Phase 2: merge groups of runs until one merge can finish the job
+    while runs.len() > fan_in {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(fan_in));
+        for group in runs.chunks(fan_in) {
+            let path = dir.run(next_run);
+            next_run += 1;
+
+            let file = File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            merge_runs(group, BufWriter::new(file), read_buffer)?;
+
+            // Free the disk space of the merged runs right away
+            for run in group {
+                fs::remove_file(run).map_err(|e| format!("{}: {}", run.display(), e))?;
+            }
+            merged.push(path);
+        }
+        runs = merged;
+        merge_passes += 1;
+    }
+
+    // Phase 3: merge the remaining runs into the output
+    merge_runs(&runs, output, read_buffer)?;
+    merge_passes += 1;
+
+    Ok(Summary { values, runs: total_runs, merge_passes })
+}
+
+/// Writes a sorted chunk to a new run file.
+///
+/// # Arguments
+/// * `path` - The file to create
+/// * `chunk` - The sorted values
+///
+/// # Returns
+/// * `Result<(), String>` - Ok, or an error naming the file
+fn write_run(path: &Path, chunk: &[i32]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    write_values(BufWriter::new(file), chunk).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Writes values one per line and flushes the writer.
+///
+/// # Arguments
+/// * `writer` - Where to write
+/// * `values` - The values to write
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+fn write_values<W: Write>(mut writer: W, values: &[i32]) -> io::Result<()> {
+    for &value in values {
+        writeln!(writer, "{}", value)?;
+    }
+    writer.flush()
+}
+
+/// Merges run files into a writer.
+///
+/// # Arguments
+/// * `runs` - The run files to merge
+/// * `writer` - Where the merged values are written
+/// * `read_buffer` - The read buffer size for each run, in bytes
+///
+/// # Returns
+/// * `Result<(), String>` - Ok, or the first error
+fn merge_runs<W: Write>(runs: &[PathBuf], writer: W, read_buffer: usize) -> Result<(), String> {
+    let mut inputs = Vec::with_capacity(runs.len());
+    for run in runs {
+        let file = File::open(run).map_err(|e| format!("{}: {}", run.display(), e))?;
+        inputs.push((run.display().to_string(), BufReader::with_capacity(read_buffer, file)));
+    }
+    merge_readers(inputs, writer).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::xorshift::XorShift64;
+    use super::*;
+    use std::env;
+
+    /// Creates an empty directory for one test and returns its path.
+    fn test_parent(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("external-sort-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir(&path).unwrap();
+        path
+    }
+
+    /// Generates pseudo-random values as input text.
+    fn random_input(len: usize) -> (Vec<i32>, String) {
+        let mut rng = XorShift64::new(4096);
+        let values: Vec<i32> = rng.values(len, -(1 << 30)..1 << 30);
+        let text = values.iter().map(|v| format!("{}\n", v)).collect();
+        (values, text)
+    }
+
+    /// Runs an external sort and checks the output and the cleanup.
+    fn check(name: &str, len: usize, memory: usize) -> Summary {
+        let parent = test_parent(name);
+        let (mut values, text) = random_input(len);
+        let mut output = Vec::new();
+
+        let summary =
+            external_sort(text.as_bytes(), &mut output, memory, &parent, |c| c.sort()).unwrap();
+
+        let sorted: Vec<i32> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        values.sort();
+        assert_eq!(sorted, values);
+        assert_eq!(summary.values, len);
+
+        // The temporary directory is gone
+        assert_eq!(fs::read_dir(&parent).unwrap().count(), 0);
+        fs::remove_dir(&parent).unwrap();
+        summary
+    }
+
+    #[test]
+    fn test_fits_in_memory() {
+        let summary = check("fits", 1000, DEFAULT_MEMORY);
+        assert_eq!(summary, Summary { values: 1000, runs: 0, merge_passes: 0 });
+    }
+
+    #[test]
+    fn test_one_million_values_single_merge() {
+        // 256 KiB holds 32768 values, a fraction of the 4 MB of values,
+        // and 64 runs can be merged at once
+        let summary = check("single", 1_000_000, 256 << 10);
+        assert_eq!(summary.runs, 31);
+        assert_eq!(summary.merge_passes, 1);
+    }
+
+    #[test]
+    fn test_several_merge_passes() {
+        // 16 KiB holds 2048 values per run and merges only 4 runs at once,
+        // so 49 runs become 13, then 4, then the output
+        let summary = check("passes", 100_000, 16 << 10);
+        assert_eq!(summary.runs, 49);
+        assert_eq!(summary.merge_passes, 3);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let summary = check("empty", 0, 1024);
+        assert_eq!(summary, Summary { values: 0, runs: 0, merge_passes: 0 });
+    }
+
+    #[test]
+    fn test_bad_input_cleans_up() {
+        let parent = test_parent("bad");
+        let input = "3\n1\n2\nnope\n";
+        let result = external_sort(input.as_bytes(), Vec::new(), 8, &parent, |c| c.sort());
+        assert_eq!(result, Err("Failed to parse integer: nope".to_string()));
+        assert_eq!(fs::read_dir(&parent).unwrap().count(), 0);
+        fs::remove_dir(&parent).unwrap();
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("512k"), Ok(512 << 10));
+        assert_eq!(parse_size("64M"), Ok(64 << 20));
+        assert_eq!(parse_size("1G"), Ok(1 << 30));
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("M").is_err());
+        assert!(parse_size("12Q").is_err());
+        assert!(parse_size("-5K").is_err());
+    }
+}
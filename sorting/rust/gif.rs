@@ -0,0 +1,355 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: gif.rs
+
+   Description:
+      This module writes animated GIF89a images without any external crate.
+      Every frame covers the whole image and is given as one palette index
+      per pixel, row by row, against a single global palette of 256 colors.
+      Frames are compressed with the variable-width LZW coding that GIF
+      requires as soon as they are added, so an animation is streamed to
+      its writer and never held in memory as a whole.
+
+      The encoder starts with 9-bit codes (8-bit pixels plus the clear and
+      end codes), widens them each time the dictionary outgrows the current
+      width, and sends a clear code to start over once all 4096 codes of
+      12 bits are used. The animation loops forever.
+
+   Usage:
+      mod gif;
+      use gif::GifWriter;
+*/
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// The number of colors in the palette; pixels are palette indices.
+pub const PALETTE_SIZE: usize = 256;
+
+/// The number of bits per pixel, which is the LZW minimum code size.
+const PIXEL_BITS: u8 = 8;
+
+/// The number of codes a GIF decoder keeps at most (12-bit codes).
+const MAX_CODES: u16 = 4096;
+
+/// A GIF animation being written.
+pub struct GifWriter<W: Write> {
+    /// Where the image is written
+    writer: W,
+    /// The width of every frame in pixels
+    width: u16,
+    /// The height of every frame in pixels
+    height: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// Writes the header, the palette and the looping extension.
+    ///
+    /// # Arguments
+    /// * `writer` - Where the image is written
+    /// * `width` - The width of every frame in pixels
+    /// * `height` - The height of every frame in pixels
+    /// * `palette` - The RGB colors of the 256 pixel values
+    ///
+    /// # Returns
+    /// * `io::Result<GifWriter<W>>` - A writer ready for the first frame
+    ///
+    /// # Errors
+    /// * Returns an error if writing fails
+    pub fn new(
+        mut writer: W,
+        width: u16,
+        height: u16,
+        palette: &[[u8; 3]; PALETTE_SIZE],
+    ) -> io::Result<GifWriter<W>> {
+        writer.write_all(b"GIF89a")?;
+
+        // Logical screen: a global table of 2^(7+1) colors of 8 bits each
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        writer.write_all(&[0xF7, 0, 0])?;
+        for color in palette.iter() {
+            writer.write_all(color)?;
+        }
+
+        // The NETSCAPE2.0 extension with a loop count of 0 repeats forever
+        writer.write_all(&[0x21, 0xFF, 0x0B])?;
+        writer.write_all(b"NETSCAPE2.0")?;
+        writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(GifWriter {
+            writer,
+            width,
+            height,
+        })
+    }
+
+    /// Adds a frame to the animation.
+    ///
+    /// # Arguments
+    /// * `pixels` - One palette index per pixel, row by row
+    /// * `delay` - How long the frame is shown, in hundredths of a second
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - Ok if the frame was written
+    ///
+    /// # Errors
+    /// * Returns an error if `pixels` does not match the image size or if
+    ///   writing fails
+    pub fn add_frame(&mut self, pixels: &[u8], delay: u16) -> io::Result<()> {
+        if pixels.len() != usize::from(self.width) * usize::from(self.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a {}x{} frame needs {} pixels, not {}",
+                    self.width,
+                    self.height,
+                    usize::from(self.width) * usize::from(self.height),
+                    pixels.len()
+                ),
+            ));
+        }
+
+        // Graphic control extension: no transparency, keep the frame shown
+        self.writer.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+        self.writer.write_all(&delay.to_le_bytes())?;
+        self.writer.write_all(&[0x00, 0x00])?;
+
+        // Image descriptor for the whole image, without a local palette
+        self.writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.writer.write_all(&self.width.to_le_bytes())?;
+        self.writer.write_all(&self.height.to_le_bytes())?;
+        self.writer.write_all(&[0x00, PIXEL_BITS])?;
+
+        // The compressed data goes out in sub-blocks of at most 255 bytes
+        for block in lzw_encode(pixels).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0x00])
+    }
+
+    /// Writes the trailer and returns the underlying writer, flushed.
+    ///
+    /// # Returns
+    /// * `io::Result<W>` - The writer the image went to
+    ///
+    /// # Errors
+    /// * Returns an error if writing fails
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(&[0x3B])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Packs codes of varying width into bytes, least significant bit first.
+struct BitPacker {
+    /// The packed bytes
+    bytes: Vec<u8>,
+    /// Bits not yet making up a whole byte
+    pending: u32,
+    /// The number of bits in `pending`
+    count: u8,
+}
+
+impl BitPacker {
+    /// Appends the low `width` bits of `code`.
+    fn push(&mut self, code: u16, width: u8) {
+        self.pending |= u32::from(code) << self.count;
+        self.count += width;
+        while self.count >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Returns the bytes, padding the last one with zero bits.
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.pending as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses 8-bit pixels with GIF's variable-width LZW coding.
+///
+/// The dictionary maps a known string (its code) followed by one more
+/// pixel to the code of the longer string. The longest known string at
+/// the current position is emitted as one code, and that string plus the
+/// next pixel becomes a new code.
+///
+/// # Arguments
+/// * `pixels` - The palette indices of one frame
+///
+/// # Returns
+/// * `Vec<u8>` - The code stream, from the first clear code to the end code
+///
+/// # Time Complexity
+/// * O(n) expected, with one hash lookup per pixel
+fn lzw_encode(pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << PIXEL_BITS;
+    let end = clear + 1;
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = PIXEL_BITS + 1;
+    let mut packer = BitPacker {
+        bytes: Vec::new(),
+        pending: 0,
+        count: 0,
+    };
+    packer.push(clear, width);
+
+    let mut iter = pixels.iter();
+    let mut prefix = match iter.next() {
+        Some(&pixel) => u16::from(pixel),
+        None => {
+            packer.push(end, width);
+            return packer.finish();
+        }
+    };
+
+    for &pixel in iter {
+        if let Some(&code) = dictionary.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        packer.push(prefix, width);
+        dictionary.insert((prefix, pixel), next);
+
+        // The decoder adds each code one step later, and widens its codes
+        // when it adds the last code that fits; so must the encoder
+        if next == 1 << width && width < 12 {
+            width += 1;
+        }
+        next += 1;
+
+        // Start over rather than outgrow 12-bit codes
+        if next == MAX_CODES {
+            packer.push(clear, width);
+            dictionary.clear();
+            next = end + 1;
+            width = PIXEL_BITS + 1;
+        }
+        prefix = u16::from(pixel);
+    }
+
+    packer.push(prefix, width);
+    packer.push(end, width);
+    packer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::xorshift::XorShift64;
+    use super::*;
+
+    /// Decodes a GIF LZW code stream, as an image viewer would.
+    fn lzw_decode(data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << PIXEL_BITS;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut width = PIXEL_BITS + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+        let (mut bits, mut count, mut position) = (0u32, 0u8, 0usize);
+
+        loop {
+            while count < width {
+                bits |= u32::from(data[position]) << count;
+                position += 1;
+                count += 8;
+            }
+            let code = (bits & ((1 << width) - 1)) as usize;
+            bits >>= width;
+            count -= width;
+
+            if code == clear {
+                table = (0..clear).map(|pixel| vec![pixel as u8]).collect();
+                table.push(Vec::new());
+                table.push(Vec::new());
+                width = PIXEL_BITS + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match previous {
+                None => table[code].clone(),
+                Some(ref previous) => {
+                    let entry = if code < table.len() {
+                        table[code].clone()
+                    } else {
+                        let mut entry = previous.clone();
+                        entry.push(previous[0]);
+                        entry
+                    };
+                    if table.len() < usize::from(MAX_CODES) {
+                        let mut added = previous.clone();
+                        added.push(entry[0]);
+                        table.push(added);
+                        if table.len() == 1 << width && width < 12 {
+                            width += 1;
+                        }
+                    }
+                    entry
+                }
+            };
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let noise: Vec<u8> = XorShift64::new(5)
+            .values(20000, 0..256)
+            .iter()
+            .map(|&value| value as u8)
+            .collect();
+        let runs: Vec<u8> = (0..50000).map(|i| (i / 700) as u8).collect();
+
+        // Noise fills the dictionary and forces clear codes; runs compress
+        for pixels in &[vec![], vec![7], vec![1, 1, 1, 1], noise, runs] {
+            assert_eq!(&lzw_decode(&lzw_encode(pixels)), pixels);
+        }
+        assert!(lzw_encode(&vec![3; 10000]).len() < 200);
+    }
+
+    #[test]
+    fn test_single_frame_layout() {
+        let mut palette = [[0u8; 3]; PALETTE_SIZE];
+        palette[1] = [255, 0, 0];
+        let mut gif = GifWriter::new(Vec::new(), 2, 2, &palette).unwrap();
+        gif.add_frame(&[0, 1, 1, 0], 10).unwrap();
+        let bytes = gif.finish().unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF89a");
+        assert_eq!(&bytes[6..13], &[2, 0, 2, 0, 0xF7, 0, 0]);
+        assert_eq!(&bytes[16..19], &[255, 0, 0]);
+        assert_eq!(&bytes[13 + 768..13 + 768 + 3], &[0x21, 0xFF, 0x0B]);
+
+        // After the 19-byte loop extension: control extension, descriptor
+        let frame = &bytes[13 + 768 + 19..];
+        assert_eq!(&frame[..8], &[0x21, 0xF9, 0x04, 0x00, 10, 0, 0, 0]);
+        assert_eq!(&frame[8..18], &[0x2C, 0, 0, 0, 0, 2, 0, 2, 0, 0]);
+        assert_eq!(frame[18], PIXEL_BITS);
+        let length = usize::from(frame[19]);
+        assert_eq!(lzw_decode(&frame[20..20 + length]), vec![0, 1, 1, 0]);
+        assert_eq!(&frame[20 + length..], &[0x00, 0x3B]);
+    }
+
+    #[test]
+    fn test_frame_size_is_checked() {
+        let palette = [[0u8; 3]; PALETTE_SIZE];
+        let mut gif = GifWriter::new(Vec::new(), 3, 2, &palette).unwrap();
+        assert!(gif.add_frame(&[0; 5], 1).is_err());
+        assert!(gif.add_frame(&[0; 6], 1).is_ok());
+    }
+}
@@ -0,0 +1,191 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: heapCounter.rs
+
+   Description:
+      This module measures how much heap memory a piece of code uses, so
+      the auxiliary space quoted for a sort (such as the O(n) buffer of
+      merge sort) can be checked against what it really allocates.
+
+      Including the module installs `CountingAllocator` as the program's
+      global allocator. It hands every request to the system allocator and,
+      while a measurement is running, keeps for each thread the number of
+      bytes currently allocated and the most allocated at once. Outside a
+      measurement it only checks a shared flag, so plain runs and --timings
+      benchmarks don't pay for the bookkeeping. Keeping the totals per
+      thread means that other threads (such as tests running in parallel)
+      don't disturb a measurement; memory freed by a different thread than
+      the one that allocated it, or freed during a measurement but
+      allocated before any began, is not tracked exactly, which doesn't
+      happen in the sorts measured here.
+
+      The peak is counted in bytes requested, not in bytes the system
+      allocator actually reserves, and a reallocation counts the old and
+      the new block together, since both exist while the values move.
+
+   Usage:
+      mod heap_counter;
+      use heap_counter::peak_heap;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+/// A global allocator that counts the bytes each thread has allocated.
+pub struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The number of measurements running on any thread. The allocator only
+/// keeps its totals while this is above zero.
+static MEASUREMENTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns whether a measurement is running, so allocations must be counted.
+#[inline(always)]
+fn measuring() -> bool {
+    MEASUREMENTS.load(Relaxed) > 0
+}
+
+thread_local! {
+    /// The bytes allocated by this thread and not yet freed
+    static LIVE: Cell<usize> = const { Cell::new(0) };
+    /// The most bytes this thread has had allocated at once since the
+    /// current measurement began
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Adds `size` bytes to the calling thread's total.
+fn grew(size: usize) {
+    // The totals may already be gone while the thread is shutting down
+    let _ = LIVE.try_with(|live| {
+        let now = live.get() + size;
+        live.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+}
+
+/// Removes `size` bytes from the calling thread's total.
+fn shrank(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get().saturating_sub(size)));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && measuring() {
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && measuring() {
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if measuring() {
+            shrank(layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && measuring() {
+            grew(new_size);
+            shrank(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` and measures the most heap memory it held at once.
+///
+/// Only memory allocated by the calling thread while `f` runs is counted,
+/// on top of whatever the thread already held when `f` started. The
+/// allocator keeps its totals until the last running measurement ends.
+///
+/// # Arguments
+/// * `f` - The code to measure
+///
+/// # Returns
+/// * `(R, usize)` - What `f` returned, and its peak heap use in bytes
+pub fn peak_heap<F: FnOnce() -> R, R>(f: F) -> (R, usize) {
+    MEASUREMENTS.fetch_add(1, Relaxed);
+    let start = LIVE.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start));
+    let result = f();
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    MEASUREMENTS.fetch_sub(1, Relaxed);
+    (result, peak - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peak_of_one_vector() {
+        let (sum, bytes) = peak_heap(|| vec![1u64; 1000].iter().sum::<u64>());
+        assert_eq!(sum, 1000);
+        assert_eq!(bytes, 8000);
+    }
+
+    #[test]
+    fn test_peak_counts_memory_held_at_once() {
+        // Two buffers one after the other peak at the larger one
+        let ((), bytes) = peak_heap(|| {
+            drop(vec![0u8; 300]);
+            drop(vec![0u8; 500]);
+        });
+        assert_eq!(bytes, 500);
+
+        // Growing a vector holds the old and the new block for a moment
+        let (values, bytes) = peak_heap(|| {
+            let mut values: Vec<u32> = Vec::with_capacity(4);
+            values.extend(0..5);
+            values
+        });
+        assert_eq!(values.capacity(), 8);
+        assert_eq!(bytes, 4 * 4 + 8 * 4);
+
+        // Memory held before the measurement is not counted
+        let held = vec![0u8; 4096];
+        let ((), bytes) = peak_heap(|| drop(Box::new(7u16)));
+        assert_eq!(bytes, 2);
+        drop(held);
+    }
+
+    #[test]
+    fn test_nested_measurements() {
+        // The inner peak only counts its own memory but still adds to the
+        // outer one
+        let (((), inner), outer) = peak_heap(|| {
+            let kept = vec![0u8; 100];
+            let inner = peak_heap(|| drop(vec![0u8; 50]));
+            drop(kept);
+            inner
+        });
+        assert_eq!(inner, 50);
+        assert_eq!(outer, 150);
+    }
+
+    #[test]
+    fn test_plain_code_allocates_nothing() {
+        let (total, bytes) = peak_heap(|| (1..=100u32).sum::<u32>());
+        assert_eq!(total, 5050);
+        assert_eq!(bytes, 0);
+    }
+}
@@ -0,0 +1,77 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: insertion.rs
+
+   Description:
+      This module provides the insertion sort that other programs build on:
+      merge sort and quick sort use it as the base case below --cutoff,
+      quick sort's median of medians sorts its groups of five with it, and
+      the adaptive sort picks it for nearly sorted input. It is generic
+      over the element type and reports each write through the `Tally`
+      hooks of opCounter.rs, which the program must include next to it.
+
+   Usage:
+      mod insertion;
+      mod op_counter;
+      use insertion::insertion_sort;
+*/
+
+use super::op_counter::Tally;
+
+/// Sorts a slice in ascending order using insertion sort.
+///
+/// Each value is shifted left past the larger values before it, so the
+/// sort is stable and costs one comparison and one write per inversion.
+///
+/// # Time Complexity
+/// * O(n + inversions) - O(n) on sorted input, O(n²) on reversed input
+///
+/// # Space Complexity
+/// * O(1) - sorts in place
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+pub fn insertion_sort<T: Tally>(numbers: &mut [T]) {
+    for i in 1..numbers.len() {
+        let key = numbers[i];
+        let mut j = i;
+
+        // Shift larger elements one position to the right
+        while j > 0 && numbers[j - 1] > key {
+            numbers[j] = numbers[j - 1];
+            T::wrote(&numbers[j]);
+            j -= 1;
+        }
+
+        numbers[j] = key;
+        T::wrote(&numbers[j]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::op_counter;
+    use super::*;
+
+    #[test]
+    fn test_insertion_sort() {
+        let mut arr = vec![5, -1, 3, 3, 0, 9, -7];
+        insertion_sort(&mut arr);
+        assert_eq!(arr, vec![-7, -1, 0, 3, 3, 5, 9]);
+
+        let mut empty: Vec<i32> = Vec::new();
+        insertion_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_counts_follow_inversions() {
+        let mut arr = vec![3, 1, 2];
+        let counts = op_counter::count(&mut arr, insertion_sort);
+        assert_eq!(arr, vec![1, 2, 3]);
+        // 1 and 2 each shift the 3 once, and every key is written back
+        assert_eq!((counts.comparisons, counts.writes), (3, 2 + 2));
+    }
+}
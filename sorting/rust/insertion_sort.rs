@@ -11,14 +11,70 @@
       result to standard output (one integer per line). Designed for use with
       input/output redirection and benchmarking.
 
+      Passing --indices prints the original line number of each value in
+      sorted order instead of the values, for joining the result back to
+      other columns of the input. It replaces the output, so it cannot be
+      combined with --stats, --trace, --sound or --timings.
+
+      Passing --stats reports the operations the sort performed to stderr:
+      comparisons, and element writes (the shifts plus the insertion of
+      each key). Both grow with the number of inversions, from about n on
+      sorted input to about n²/2 on reverse sorted input.
+
+      Passing --trace writes each comparison and write to stderr as JSON
+      Lines (see opTrace.rs), or to FILE with --trace=FILE. The key being
+      inserted is held outside the array, so its side of a comparison has
+      a null index.
+
+      Passing --sound=FILE writes the sound of the sort to FILE as a WAV
+      file (see opSound.rs), one tone per value compared or written, so
+      the shifts of each insertion are heard as a run of tones.
+
+      Passing --timings prints the time spent in read_input, in the sort
+      and in print_output to stderr (see phaseTimer.rs). Counting, tracing
+      or playing the sort would be timed along with it, so --timings
+      cannot be combined with --stats, --trace or --sound.
+
    Usage:
       ./insertionSort < input.txt > output.txt
+      ./insertionSort --indices < input.txt > order.txt
+      ./insertionSort --stats < input.txt > output.txt
+      ./insertionSort --trace=trace.jsonl < input.txt > output.txt
+      ./insertionSort --sound=insertion.wav < input.txt > output.txt
+      ./insertionSort --timings < input.txt > output.txt
       cat input.txt | ./insertionSort
 */
 
+mod argsort;
+mod op_counter;
+mod op_sound;
+mod op_trace;
+mod phase_timer;
+
+use argsort::{argsort_with, print_line_numbers, read_input_with_lines, Tagged};
+use op_counter::{count, Tally};
+use op_trace::Target;
+use phase_timer::PhaseTimer;
+use std::env;
+use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::process;
 
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Whether to print original line numbers instead of sorted values
+    indices: bool,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Where to write a trace of the sort's operations, if anywhere
+    trace: Option<Target>,
+    /// Where to write the sound of the sort's array accesses, if anywhere
+    sound: Option<String>,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
+
 /// Reads integers from standard input, one per line.
 ///
 /// Uses a buffered reader for efficient I/O when handling large input files.
@@ -86,8 +142,8 @@ fn read_input() -> io::Result<Vec<i32>> {
 /// * Online: can sort a list as it receives it
 ///
 /// # Arguments
-/// * `numbers` - A mutable slice of i32 integers to sort
-fn insertion_sort(numbers: &mut [i32]) {
+/// * `numbers` - A mutable slice of values to sort
+fn insertion_sort<T: Tally>(numbers: &mut [T]) {
     let n = numbers.len();
     
     // Start from the second element (index 1) since a single element is already sorted
@@ -99,14 +155,33 @@ fn insertion_sort(numbers: &mut [i32]) {
         // one position to the right
         while j > 0 && numbers[j - 1] > key {
             numbers[j] = numbers[j - 1];
+            T::wrote(&numbers[j]);
             j -= 1;
         }
         
         // Insert the key into its correct position
         numbers[j] = key;
+        T::wrote(&numbers[j]);
     }
 }
 
+// Tagged values are sorted for --indices, where nothing is counted
+impl Tally for Tagged {}
+
+/// Returns the permutation that sorts `numbers`, computed with insertion sort.
+///
+/// Insertion sort is stable, so equal values keep their original relative
+/// order and the permutation is unique.
+///
+/// # Arguments
+/// * `numbers` - The values to sort; they are not modified
+///
+/// # Returns
+/// * `Vec<usize>` - Entry i is the original index of the i-th smallest value
+fn argsort(numbers: &[i32]) -> Vec<usize> {
+    argsort_with(numbers, insertion_sort)
+}
+
 /// Prints each integer in the vector to standard output, one per line.
 ///
 /// Uses a buffered writer for efficient I/O when handling large output.
@@ -131,12 +206,103 @@ fn print_output(numbers: &[i32]) -> io::Result<()> {
     Ok(())
 }
 
+/// Parses the command-line arguments.
+///
+/// Recognizes `--indices`, `--stats`, `--trace[=FILE]`, `--sound=FILE`
+/// and `--timings`; anything else is an error. `--indices` replaces the
+/// output, so it cannot be combined with the others. `--trace` and
+/// `--sound` record a plain sort, so neither can be combined with `--stats`
+/// or with each other. `--timings` times a plain sort too, so it cannot be
+/// combined with `--stats`, `--trace` or `--sound`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        indices: false,
+        stats: false,
+        trace: None,
+        sound: None,
+        timings: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--indices" => options.indices = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            other => {
+                if let Some(target) = Target::from_arg(other) {
+                    options.trace = Some(target);
+                } else if let Some(path) = op_sound::from_arg(other) {
+                    options.sound = Some(path);
+                } else {
+                    return Err(format!("Unknown argument: {}", other));
+                }
+            }
+        }
+    }
+
+    if options.stats && options.indices {
+        return Err("--stats cannot be combined with --indices".to_string());
+    }
+
+    if options.trace.is_some() && (options.indices || options.stats) {
+        return Err("--trace cannot be combined with --stats or --indices".to_string());
+    }
+
+    let recorded = options.stats || options.trace.is_some();
+    if options.sound.is_some() && (options.indices || recorded) {
+        return Err("--sound cannot be combined with --stats, --trace or --indices".to_string());
+    }
+
+    let recorded = recorded || options.sound.is_some();
+    if options.timings && (options.indices || recorded) {
+        return Err(
+            "--timings cannot be combined with --stats, --trace, --sound or --indices"
+                .to_string(),
+        );
+    }
+
+    Ok(options)
+}
+
 /// Main entry point for the insertion sort program.
 ///
 /// Orchestrates reading from stdin, sorting, and writing to stdout.
 /// Exits with status code 1 if any errors occur during execution.
 fn main() {
-    // Read integers from standard input
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Print the original line numbers in sorted order instead of the values
+    if options.indices {
+        let (numbers, lines) = match read_input_with_lines() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = print_line_numbers(&argsort(&numbers), &lines) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
     let mut numbers = match read_input() {
         Ok(nums) => nums,
         Err(e) => {
@@ -144,15 +310,44 @@ fn main() {
             process::exit(1);
         }
     };
+    timer.lap("read_input");
 
-    // Sort the numbers using insertion sort
-    insertion_sort(&mut numbers);
+    // Sort the numbers using insertion sort, tracing, playing or counting operations if asked
+    if let Some(target) = options.trace {
+        let written = target.open().and_then(|writer| {
+            op_trace::record(&mut numbers, "insertion sort", writer, insertion_sort)
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing trace: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(path) = options.sound {
+        let written = File::create(&path)
+            .and_then(|file| op_sound::record(&mut numbers, BufWriter::new(file), insertion_sort));
+        if let Err(e) = written {
+            eprintln!("Error writing sound: {}", e);
+            process::exit(1);
+        }
+    } else if options.stats {
+        let counts = count(&mut numbers, insertion_sort);
+        eprint!("{}", counts.summary("insertion sort", numbers.len()));
+    } else {
+        insertion_sort(&mut numbers);
+    }
+
+    timer.lap("sort");
 
     // Print the sorted numbers to standard output
     if let Err(e) = print_output(&numbers) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("insertion sort", numbers.len()));
+    }
 }
 
 #[cfg(test)]
@@ -229,4 +424,143 @@ mod tests {
         insertion_sort(&mut arr);
         assert_eq!(arr, vec![1, 2, 3, 3, 3]);
     }
+
+    #[test]
+    fn test_argsort_is_stable() {
+        let numbers = vec![3, 1, 3, 2, 1, 3];
+        assert_eq!(argsort(&numbers), vec![1, 4, 3, 0, 2, 5]);
+        assert_eq!(argsort(&[7]), vec![0]);
+    }
+
+    #[test]
+    fn test_stats_follow_inversions() {
+        // Sorted: n - 1 comparisons, and each key is written back in place
+        let mut sorted: Vec<i32> = (0..50).collect();
+        let counts = count(&mut sorted, insertion_sort);
+        assert_eq!((counts.comparisons, counts.writes, counts.swaps), (49, 49, 0));
+
+        // [2, 3, 1] has 2 inversions: key 3 needs 1 comparison, key 1 needs
+        // 2, and each shift and each insertion is one write
+        let mut arr = vec![2, 3, 1];
+        let counts = count(&mut arr, insertion_sort);
+        assert_eq!(arr, vec![1, 2, 3]);
+        assert_eq!((counts.comparisons, counts.writes), (3, 4));
+
+        // Reverse: every pair is compared and shifted
+        let mut reverse: Vec<i32> = (0..50).rev().collect();
+        let counts = count(&mut reverse, insertion_sort);
+        assert_eq!(counts.comparisons, 50 * 49 / 2);
+        assert_eq!(counts.writes, 50 * 49 / 2 + 49);
+    }
+
+    #[test]
+    fn test_trace_shifts_and_inserts() {
+        use op_trace::Event;
+        use std::fs;
+
+        let path = env::temp_dir().join(format!("insertion_trace_{}.jsonl", process::id()));
+        let target = Target::File(path.to_string_lossy().into_owned());
+        let mut numbers = vec![3, 1, 2];
+        let writer = target.open().unwrap();
+        op_trace::record(&mut numbers, "insertion sort", writer, insertion_sort).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+
+        // Key 1 shifts 3 right and lands at 0; key 2 shifts 3 again and
+        // stops after comparing with 1
+        let events: Vec<Event> = text.lines().map(|line| Event::parse(line).unwrap()).collect();
+        let expected = [
+            Event::Compare { i: Some(0), j: None, a: 3, b: 1 },
+            Event::Write { i: Some(1), value: 3 },
+            Event::Write { i: Some(0), value: 1 },
+            Event::Compare { i: Some(1), j: None, a: 3, b: 2 },
+            Event::Write { i: Some(2), value: 3 },
+            Event::Compare { i: Some(0), j: None, a: 1, b: 2 },
+            Event::Write { i: Some(1), value: 2 },
+        ];
+        assert_eq!(&events[1..events.len() - 1], &expected);
+
+        let mut array = Vec::new();
+        for event in &events {
+            event.apply(&mut array);
+        }
+        assert_eq!(array, numbers);
+    }
+
+    #[test]
+    fn test_sound_has_a_tone_per_access() {
+        let mut numbers = vec![5, 1, 4, 2, 3];
+        let mut wav = Vec::new();
+        op_sound::record(&mut numbers, &mut wav, insertion_sort).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+
+        // A comparison accesses two values, a shift or insertion one
+        let counts = count(&mut [5, 1, 4, 2, 3], insertion_sort);
+        let accesses = (2 * counts.comparisons + counts.writes) as usize;
+        let samples = accesses * op_sound::tone_length(accesses);
+        assert_eq!(wav.len(), 44 + 2 * samples);
+        assert_eq!(&wav[40..44], &(2 * samples as u32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                indices: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--trace=out.jsonl"])).map(|o| o.trace),
+            Ok(Some(Target::File("out.jsonl".to_string())))
+        );
+        assert_eq!(
+            parse_args(&args(&["--sound=out.wav"])).map(|o| o.sound),
+            Ok(Some("out.wav".to_string()))
+        );
+        assert_eq!(
+            parse_args(&args(&["--indices"])).map(|o| (o.indices, o.stats)),
+            Ok((true, false))
+        );
+        assert_eq!(
+            parse_args(&args(&["--timings"])).map(|o| o.timings),
+            Ok(true)
+        );
+
+        // --indices replaces the output, and the others each record or time
+        // a plain sort
+        for flags in [
+            ["--indices", "--stats"],
+            ["--indices", "--trace"],
+            ["--indices", "--sound=out.wav"],
+            ["--indices", "--timings"],
+            ["--stats", "--trace"],
+            ["--trace", "--sound=out.wav"],
+            ["--sound=out.wav", "--stats"],
+            ["--timings", "--stats"],
+            ["--trace=out.jsonl", "--timings"],
+            ["--timings", "--sound=out.wav"],
+        ]
+        .iter()
+        {
+            let error = parse_args(&args(flags)).unwrap_err();
+            assert!(error.contains("cannot be combined"), "{:?}: {}", flags, error);
+        }
+
+        // Misspelled flags are rejected rather than ignored
+        assert_eq!(
+            parse_args(&args(&["--stat"])),
+            Err("Unknown argument: --stat".to_string())
+        );
+        assert!(parse_args(&args(&["input.txt"])).is_err());
+    }
 }
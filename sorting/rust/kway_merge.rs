@@ -0,0 +1,245 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: kwayMerge.rs
+
+   Description:
+      This module merges any number of already-sorted inputs into one
+      sorted stream, like `sort -m`. Each input is read lazily one line at a
+      time, so only a read buffer and the current value are held per input
+      no matter how long the inputs are. A loser tree picks the smallest
+      current value in O(log k) comparisons per output value.
+
+      Every input is checked while it is read: a value smaller than the one
+      before it, or a line that is not an integer, stops the merge with an
+      error naming the input and line. Output written before the error is
+      left in place.
+
+      The module has no main function. It picks values with the loser tree
+      in loserTree.rs, which the program must include next to it.
+
+   Usage:
+      mod kway_merge;
+      mod loser_tree;
+      use kway_merge::merge_files;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::loser_tree::LoserTree;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+/// An iterator over the integers of one sorted input, one per line.
+///
+/// Blank lines are skipped. The iterator ends early, recording an error,
+/// if a line is not an integer or a value is smaller than the one before.
+pub struct SortedLines<R: BufRead> {
+    /// The name of the input, used in error messages
+    name: String,
+    /// The remaining lines of the input
+    lines: io::Lines<R>,
+    /// The number of lines read so far
+    line: usize,
+    /// The last value returned, for the sortedness check
+    previous: Option<i32>,
+    /// Why the input ended early, if it did
+    error: Option<String>,
+}
+
+impl<R: BufRead> SortedLines<R> {
+    /// Wraps a reader whose lines should hold integers in ascending order.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the input, used in error messages
+    /// * `reader` - The input to read
+    pub fn new(name: &str, reader: R) -> Self {
+        SortedLines {
+            name: name.to_string(),
+            lines: reader.lines(),
+            line: 0,
+            previous: None,
+            error: None,
+        }
+    }
+
+    /// Returns why the input ended early, or None if it is fine so far.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Records an error and ends the iteration.
+    fn fail(&mut self, message: String) -> Option<i32> {
+        self.error = Some(format!("{}: line {}: {}", self.name, self.line, message));
+        None
+    }
+}
+
+impl<R: BufRead> Iterator for SortedLines<R> {
+    type Item = i32;
+
+    /// Returns the next integer, or None at the end of the input or on error.
+    fn next(&mut self) -> Option<i32> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return self.fail(e.to_string()),
+            };
+            self.line += 1;
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let value = match trimmed.parse::<i32>() {
+                Ok(value) => value,
+                Err(_) => return self.fail(format!("not an integer: {}", trimmed)),
+            };
+
+            if let Some(previous) = self.previous {
+                if value < previous {
+                    let message = format!("not sorted: {} comes after {}", value, previous);
+                    return self.fail(message);
+                }
+            }
+
+            self.previous = Some(value);
+            return Some(value);
+        }
+    }
+}
+
+/// Merges sorted inputs and writes the result, one integer per line.
+///
+/// # Time Complexity
+/// * O(n log k) for n values in k inputs
+///
+/// # Space Complexity
+/// * O(k) - one reader and one value per input
+///
+/// # Arguments
+/// * `inputs` - The inputs to merge, each named for error messages
+/// * `writer` - Where the merged values are written
+///
+/// # Returns
+/// * `Result<usize, String>` - The number of values written, or the first
+///   input or output error
+pub fn merge_readers<R, W>(inputs: Vec<(String, R)>, mut writer: W) -> Result<usize, String>
+where
+    R: BufRead,
+    W: Write,
+{
+    let sources = inputs
+        .into_iter()
+        .map(|(name, reader)| SortedLines::new(&name, reader))
+        .collect();
+    let mut tree = LoserTree::new(sources);
+
+    // Building the tree read the first value of every input
+    if let Some(error) = tree.sources().iter().find_map(|source| source.error()) {
+        return Err(error.to_string());
+    }
+
+    let mut written = 0;
+    while let Some((source, value)) = tree.pop() {
+        // Popping read the next value of the winning input
+        if let Some(error) = tree.sources()[source].error() {
+            return Err(error.to_string());
+        }
+        writeln!(writer, "{}", value).map_err(|e| e.to_string())?;
+        written += 1;
+    }
+
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(written)
+}
+
+/// Merges sorted files and writes the result to standard output.
+///
+/// # Arguments
+/// * `paths` - The files to merge
+///
+/// # Returns
+/// * `Result<usize, String>` - The number of values written, or the first
+///   error
+pub fn merge_files(paths: &[String]) -> Result<usize, String> {
+    let mut inputs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        inputs.push((path.clone(), BufReader::new(file)));
+    }
+
+    let stdout = io::stdout();
+    let writer = io::BufWriter::new(stdout.lock());
+    merge_readers(inputs, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Merges in-memory inputs and returns the output lines.
+    fn merge(inputs: &[&str]) -> Result<Vec<i32>, String> {
+        let readers = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, text)| (format!("input{}", i), text.as_bytes()))
+            .collect();
+        let mut output = Vec::new();
+        let written = merge_readers(readers, &mut output)?;
+        let values: Vec<i32> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(written, values.len());
+        Ok(values)
+    }
+
+    #[test]
+    fn test_merge_several_inputs() {
+        let merged = merge(&["1\n4\n9\n", "2\n\n3\n10\n", "", "-5\n4\n"]).unwrap();
+        assert_eq!(merged, vec![-5, 1, 2, 3, 4, 4, 9, 10]);
+    }
+
+    #[test]
+    fn test_merge_no_inputs() {
+        assert_eq!(merge(&[]).unwrap(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_merge_many_inputs() {
+        let texts: Vec<String> = (0..37)
+            .map(|s| (s..500).step_by(37).map(|x| format!("{}\n", x)).collect())
+            .collect();
+        let inputs: Vec<&str> = texts.iter().map(|t| t.as_str()).collect();
+        assert_eq!(merge(&inputs).unwrap(), (0..500).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_unsorted_input_is_reported() {
+        let error = merge(&["1\n2\n3\n", "1\n5\n4\n"]).unwrap_err();
+        assert_eq!(error, "input1: line 3: not sorted: 4 comes after 5");
+    }
+
+    #[test]
+    fn test_bad_first_line_is_reported() {
+        let error = merge(&["1\n", "x\n"]).unwrap_err();
+        assert_eq!(error, "input1: line 1: not an integer: x");
+    }
+
+    #[test]
+    fn test_sorted_lines_counts_blank_lines() {
+        let mut source = SortedLines::new("in", "3\n\n2\n".as_bytes());
+        assert_eq!(source.next(), Some(3));
+        assert_eq!(source.next(), None);
+        assert_eq!(source.error(), Some("in: line 3: not sorted: 2 comes after 3"));
+    }
+}
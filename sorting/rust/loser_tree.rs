@@ -0,0 +1,256 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: loserTree.rs
+
+   Description:
+      This module provides a loser tree (tournament tree) for k-way merging.
+      Each leaf of the tree is fed by an iterator that yields values in
+      ascending order. Every internal node remembers the loser of the match
+      played there, and the overall winner is the smallest current value.
+      Taking the winner and advancing its iterator replays only the matches
+      on one leaf-to-root path, so each step costs O(log k) comparisons.
+
+      The module has no main function. Programs include it as a module and
+      use `LoserTree` to merge sorted sequences or to run a tournament sort.
+
+   Usage:
+      mod loser_tree;
+      use loser_tree::LoserTree;
+*/
+
+// Programs that include this module don't necessarily use every method
+#![allow(dead_code)]
+
+/// A loser tree that merges `k` ascending sources into one ascending sequence.
+///
+/// Sources are any iterators over an ordered item type. An exhausted source
+/// behaves like an infinitely large value, so it loses every match. When two
+/// sources hold equal values, the one with the lower index wins, which makes
+/// the merge stable with respect to source order.
+///
+/// # Time Complexity
+/// * Building: O(k)
+/// * Each `pop`: O(log k) comparisons
+///
+/// # Space Complexity
+/// * O(k) - one buffered value and one internal node per source
+pub struct LoserTree<I: Iterator> {
+    /// The input iterators, one per leaf
+    sources: Vec<I>,
+    /// The current front value of each source, or None once it is exhausted
+    heads: Vec<Option<I::Item>>,
+    /// Internal nodes 1..k, each holding the source index that lost there
+    losers: Vec<usize>,
+    /// The source index of the overall winner
+    winner: usize,
+}
+
+impl<I> LoserTree<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    /// Builds a loser tree over the given sources.
+    ///
+    /// Pulls the first value from every source and plays the initial
+    /// tournament bottom-up.
+    ///
+    /// # Arguments
+    /// * `sources` - Iterators that each yield values in ascending order
+    ///
+    /// # Returns
+    /// * `LoserTree<I>` - A tree whose winner is the smallest front value
+    pub fn new(mut sources: Vec<I>) -> Self {
+        let heads = sources.iter_mut().map(|source| source.next()).collect();
+        let k = sources.len();
+        let mut tree = LoserTree {
+            sources,
+            heads,
+            losers: vec![0; k],
+            winner: 0,
+        };
+
+        if k > 0 {
+            tree.winner = tree.build(1);
+        }
+
+        tree
+    }
+
+    /// Returns the number of sources the tree merges.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns true if the tree has no sources at all.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Returns the sources, for example to inspect their state after a `pop`.
+    pub fn sources(&self) -> &[I] {
+        &self.sources
+    }
+
+    /// Returns the smallest current value without removing it.
+    ///
+    /// # Returns
+    /// * `Option<&I::Item>` - The winning value, or None if every source is exhausted
+    pub fn peek(&self) -> Option<&I::Item> {
+        self.heads.get(self.winner).and_then(|head| head.as_ref())
+    }
+
+    /// Removes and returns the smallest current value along with its source.
+    ///
+    /// The winning source is advanced and the matches on its path to the
+    /// root are replayed.
+    ///
+    /// # Returns
+    /// * `Option<(usize, I::Item)>` - The source index and value, or None if
+    ///   every source is exhausted
+    pub fn pop(&mut self) -> Option<(usize, I::Item)> {
+        let source = self.winner;
+        let value = self.heads.get_mut(source)?.take()?;
+
+        self.heads[source] = self.sources[source].next();
+        self.replay(source);
+
+        Some((source, value))
+    }
+
+    /// Plays the initial matches in the subtree rooted at `node`.
+    ///
+    /// Leaves are stored at positions k..2k, so node `k + i` is source `i`.
+    ///
+    /// # Arguments
+    /// * `node` - The tree position to evaluate
+    ///
+    /// # Returns
+    /// * `usize` - The source index that wins the subtree
+    fn build(&mut self, node: usize) -> usize {
+        let k = self.sources.len();
+        if node >= k {
+            return node - k;
+        }
+
+        let left = self.build(2 * node);
+        let right = self.build(2 * node + 1);
+
+        if self.beats(left, right) {
+            self.losers[node] = right;
+            left
+        } else {
+            self.losers[node] = left;
+            right
+        }
+    }
+
+    /// Replays the matches from the leaf of `source` up to the root.
+    ///
+    /// At each node the stored loser plays the current candidate; the
+    /// winner moves up and the loser stays behind.
+    ///
+    /// # Arguments
+    /// * `source` - The source whose front value just changed
+    fn replay(&mut self, source: usize) {
+        let mut candidate = source;
+        let mut node = (source + self.sources.len()) / 2;
+
+        while node >= 1 {
+            if self.beats(self.losers[node], candidate) {
+                std::mem::swap(&mut self.losers[node], &mut candidate);
+            }
+            node /= 2;
+        }
+
+        self.winner = candidate;
+    }
+
+    /// Decides whether source `a` wins a match against source `b`.
+    ///
+    /// Exhausted sources always lose, and ties go to the lower index.
+    fn beats(&self, a: usize, b: usize) -> bool {
+        match (&self.heads[a], &self.heads[b]) {
+            (Some(x), Some(y)) => x < y || (x == y && a < b),
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => a < b,
+        }
+    }
+}
+
+impl<I> Iterator for LoserTree<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    /// Yields the merged values in ascending order.
+    fn next(&mut self) -> Option<I::Item> {
+        self.pop().map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_sorted_sources() {
+        let sources = vec![
+            vec![1, 4, 9].into_iter(),
+            vec![2, 3, 10, 11].into_iter(),
+            vec![].into_iter(),
+            vec![0, 5].into_iter(),
+        ];
+        let merged: Vec<i32> = LoserTree::new(sources).collect();
+        assert_eq!(merged, vec![0, 1, 2, 3, 4, 5, 9, 10, 11]);
+    }
+
+    #[test]
+    fn test_no_sources() {
+        let mut tree = LoserTree::new(Vec::<std::vec::IntoIter<i32>>::new());
+        assert!(tree.is_empty());
+        assert_eq!(tree.peek(), None);
+        assert_eq!(tree.pop(), None);
+    }
+
+    #[test]
+    fn test_single_source() {
+        let tree = LoserTree::new(vec![vec![-2, 7, 7].into_iter()]);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.collect::<Vec<i32>>(), vec![-2, 7, 7]);
+    }
+
+    #[test]
+    fn test_ties_prefer_lower_source() {
+        let sources = vec![vec![1, 2].into_iter(), vec![1, 2].into_iter(), vec![1].into_iter()];
+        let mut tree = LoserTree::new(sources);
+        let order: Vec<(usize, i32)> = std::iter::from_fn(|| tree.pop()).collect();
+        assert_eq!(order, vec![(0, 1), (1, 1), (2, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_peek_matches_pop() {
+        let mut tree = LoserTree::new(vec![vec![3, 6].into_iter(), vec![4].into_iter()]);
+        while let Some(&expected) = tree.peek() {
+            assert_eq!(tree.pop().map(|(_, value)| value), Some(expected));
+        }
+        assert_eq!(tree.pop(), None);
+    }
+
+    #[test]
+    fn test_many_sources() {
+        // Every non-power-of-two source count up to 40
+        for k in 1..=40 {
+            let sources: Vec<_> = (0..k)
+                .map(|s| ((s as i32)..200).step_by(k).collect::<Vec<i32>>().into_iter())
+                .collect();
+            let merged: Vec<i32> = LoserTree::new(sources).collect();
+            assert_eq!(merged, (0..200).collect::<Vec<i32>>(), "k = {}", k);
+        }
+    }
+}
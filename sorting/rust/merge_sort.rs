@@ -11,13 +11,172 @@
       result to standard output (one integer per line). Designed for use with
       input/output redirection and benchmarking.
 
+      Passing --network stops the recursion at subarrays of up to 16
+      elements and sorts them with an optimal sorting network instead.
+      Passing --cutoff N hands subarrays shorter than N elements to
+      insertion sort. Passing --tune times a sweep of cutoffs on the input
+      and reports the fastest one instead of printing the sorted numbers.
+      The sweep picks its own base cases, so --tune cannot be combined with
+      --network or --cutoff, nor with --indices, whose output it replaces.
+
+      Passing --indices prints the original line number of each value in
+      sorted order instead of the values, for joining the result back to
+      other columns of the input. Merge sort is stable, so lines with equal
+      values stay in their original order; the unstable --network base case
+      is therefore not allowed with --indices.
+
+      Passing --merge FILE... merges files that are each already sorted
+      into one sorted stream on standard output, like `sort -m`, without
+      reading standard input. The files are read one line at a time, so any
+      number of files of any size can be merged, and each one is checked to
+      really be sorted.
+
+      Passing --external sorts input that may not fit in memory. At most
+      --memory SIZE bytes of values (64M by default; K, M and G suffixes are
+      accepted) are read at a time; each such chunk is merge sorted and
+      written to a temporary file under $TMPDIR, and the files are then
+      merged into standard output and deleted.
+
+      Passing --count-inversions prints the number of pairs of values that
+      are out of order instead of the sorted numbers. The merge counts them
+      as it sorts: whenever it takes a value from the right half, that value
+      is smaller than everything left in the left half. The merge lives in
+      countingMerge.rs, which presortedness.rs counts inversions with too.
+
+      Passing --stats prints the comparisons and the element copies through
+      the merge buffer to stderr (plus the writes of an insertion sort or
+      the swaps of a network base case, if one is chosen). Without a base
+      case, sorting n elements makes at most n log2 n comparisons and about
+      2n log2 n buffer copies whatever the input order. It also reports the
+      deepest recursion, ceil(log2 n) + 1 calls, and the most heap memory
+      the sort held at once (see heapCounter.rs), which is the n-element
+      buffer.
+
+      Passing --trace writes the sort's operations to stderr as JSON Lines
+      (see opTrace.rs), or to FILE with --trace=FILE: a merge event with
+      the two runs before each merge, the comparisons, and a write for each
+      value copied back from the buffer. Comparisons read the buffer, which
+      mirrors the array, so they carry the array positions of the values.
+
+      Passing --sound=FILE writes the sound of the sort to FILE as a WAV
+      file (see opSound.rs): a tone for each value compared and each value
+      copied back from the buffer. Merges are heard as rising sweeps that
+      grow longer and wider as the runs do.
+
+      Passing --timings reports to stderr how long reading the input, the
+      sort and printing the output took (see phaseTimer.rs). It times a
+      plain sort, so it cannot be combined with the other modes or with
+      --stats, --trace and --sound, whose recording would be timed along
+      with the sort.
+
    Usage:
       ./mergeSort < input.txt > output.txt
+      ./mergeSort --network < input.txt > output.txt
+      ./mergeSort --cutoff 24 < input.txt > output.txt
+      ./mergeSort --tune < input.txt
+      ./mergeSort --indices < input.txt > order.txt
+      ./mergeSort --merge sorted1.txt sorted2.txt sorted3.txt > output.txt
+      ./mergeSort --external --memory 256M < huge.txt > output.txt
+      ./mergeSort --count-inversions < input.txt
+      ./mergeSort --cutoff 24 --stats < input.txt > output.txt
+      ./mergeSort --trace=trace.jsonl < input.txt > output.txt
+      ./mergeSort --sound=merge.wav < input.txt > output.txt
+      ./mergeSort --network --timings < input.txt > output.txt
       cat input.txt | ./mergeSort
 */
 
+mod argsort;
+mod counting_merge;
+mod external_sort;
+mod heap_counter;
+mod insertion;
+mod kway_merge;
+mod loser_tree;
+mod op_counter;
+mod op_sound;
+mod op_trace;
+mod phase_timer;
+mod sorting_network;
+#[cfg(test)]
+mod xorshift;
+
+use argsort::{argsort_with, print_line_numbers, read_input_with_lines, Tagged};
+use counting_merge::{count_inversions, merge};
+use external_sort::{external_sort, parse_size, DEFAULT_MEMORY};
+use heap_counter::peak_heap;
+use insertion::insertion_sort;
+use kway_merge::merge_files;
+use op_counter::{count, Tally};
+use op_trace::Target;
+use phase_timer::PhaseTimer;
+use sorting_network::{network_sort, MAX_NETWORK_SIZE};
+use std::env;
+use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::process;
+use std::time::{Duration, Instant};
+
+/// Strategy for sorting small subarrays at the bottom of the recursion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BaseCase {
+    /// Recurse all the way down to single elements
+    Single,
+    /// Sort subarrays of up to `MAX_NETWORK_SIZE` elements with a sorting network
+    Network,
+    /// Sort subarrays shorter than the given cutoff with insertion sort
+    Insertion(usize),
+}
+
+impl BaseCase {
+    /// Sorts a subarray directly if it is small enough for this base case.
+    ///
+    /// # Arguments
+    /// * `slice` - The subarray about to be divided further
+    ///
+    /// # Returns
+    /// * `bool` - true if the subarray was sorted, false if the caller
+    ///   should keep recursing
+    fn sort_small<T: Tally>(self, slice: &mut [T]) -> bool {
+        match self {
+            BaseCase::Network if slice.len() <= MAX_NETWORK_SIZE => network_sort(slice),
+            BaseCase::Insertion(cutoff) if slice.len() < cutoff => insertion_sort(slice),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Cutoffs timed by `--tune`. A cutoff of 1 never calls insertion sort, so
+/// it measures the plain recursive sort as a baseline.
+const TUNE_CUTOFFS: [usize; 11] = [1, 4, 8, 12, 16, 20, 24, 32, 48, 64, 96];
+
+/// The number of timed runs per cutoff; the median is reported.
+const TUNE_REPETITIONS: usize = 5;
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// How subarrays at the bottom of the recursion are sorted
+    base_case: BaseCase,
+    /// Whether to sweep insertion sort cutoffs instead of sorting
+    tune: bool,
+    /// Whether to print original line numbers instead of sorted values
+    indices: bool,
+    /// Sorted files to merge instead of sorting standard input
+    merge: Option<Vec<String>>,
+    /// The memory budget in bytes, if sorting externally
+    external: Option<usize>,
+    /// Whether to print the number of inversions instead of sorting
+    count_inversions: bool,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Where to write a trace of the sort's operations, if anywhere
+    trace: Option<Target>,
+    /// Where to write the sound of the sort's array accesses, if anywhere
+    sound: Option<String>,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
 
 /// Reads integers from standard input, one per line.
 ///
@@ -88,14 +247,26 @@ fn read_input() -> io::Result<Vec<i32>> {
 /// # Arguments
 /// * `numbers` - A mutable slice of i32 integers to sort
 pub fn merge_sort(numbers: &mut [i32]) {
+    merge_sort_with(numbers, BaseCase::Single);
+}
+
+/// Sorts a slice with merge sort using the given base case.
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+fn merge_sort_with<T: Tally>(numbers: &mut [T], base_case: BaseCase) {
     let len = numbers.len();
     if len <= 1 {
         return;
     }
     
-    // Allocate temporary buffer once for all merge operations
-    let mut buffer = vec![0; len];
-    merge_sort_helper(numbers, &mut buffer, 0, len - 1);
+    // Allocate temporary buffer once for all merge operations; its initial
+    // contents are overwritten before they are read
+    let mut buffer = numbers.to_vec();
+    T::copied(len);
+    T::buffered(&buffer);
+    merge_sort_helper(numbers, &mut buffer, 0, len - 1, base_case, 1);
 }
 
 /// Helper function for merge sort that performs the recursive divide-and-conquer.
@@ -105,68 +276,106 @@ pub fn merge_sort(numbers: &mut [i32]) {
 /// * `buffer` - Temporary buffer for merging (reused across recursive calls)
 /// * `left` - Starting index of the portion to sort (inclusive)
 /// * `right` - Ending index of the portion to sort (inclusive)
-fn merge_sort_helper(arr: &mut [i32], buffer: &mut [i32], left: usize, right: usize) {
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+/// * `depth` - The depth of this call, 1 for the outermost one
+fn merge_sort_helper<T: Tally>(
+    arr: &mut [T],
+    buffer: &mut [T],
+    left: usize,
+    right: usize,
+    base_case: BaseCase,
+    depth: usize,
+) {
+    T::descended(depth);
+
+    // Small subarrays go straight to the base case sort
+    if base_case.sort_small(&mut arr[left..=right]) {
+        return;
+    }
+
     if left < right {
         // Find the middle point to divide the array into two halves
         let mid = left + (right - left) / 2;
         
         // Recursively sort the left and right halves
-        merge_sort_helper(arr, buffer, left, mid);
-        merge_sort_helper(arr, buffer, mid + 1, right);
+        merge_sort_helper(arr, buffer, left, mid, base_case, depth + 1);
+        merge_sort_helper(arr, buffer, mid + 1, right, base_case, depth + 1);
         
         // Merge the two sorted halves
         merge(arr, buffer, left, mid, right);
     }
 }
 
-/// Merges two sorted subarrays into a single sorted subarray.
+// Tagged values are sorted for --indices, where nothing is counted
+impl Tally for Tagged {}
+
+/// Returns the permutation that sorts `numbers`, computed with merge sort.
 ///
-/// The first subarray spans from index `left` to `mid`, and the second
-/// subarray spans from index `mid+1` to `right`. Uses a temporary buffer
-/// to hold the merged result before copying back to the original array.
+/// Merge sort is stable, so equal values keep their original relative
+/// order. The same holds with an insertion sort cutoff, but not with the
+/// network base case.
 ///
 /// # Arguments
-/// * `arr` - The array containing the two subarrays to merge
-/// * `buffer` - Temporary buffer for storing merged elements
-/// * `left` - Starting index of the first subarray
-/// * `mid` - Ending index of the first subarray
-/// * `right` - Ending index of the second subarray
-fn merge(arr: &mut [i32], buffer: &mut [i32], left: usize, mid: usize, right: usize) {
-    // Copy the range we're working with into the buffer
-    for i in left..=right {
-        buffer[i] = arr[i];
-    }
-    
-    let mut i = left;      // Index for left subarray
-    let mut j = mid + 1;   // Index for right subarray
-    let mut k = left;      // Index for merged array
-    
-    // Merge elements from both subarrays in sorted order
-    while i <= mid && j <= right {
-        if buffer[i] <= buffer[j] {
-            arr[k] = buffer[i];
-            i += 1;
-        } else {
-            arr[k] = buffer[j];
-            j += 1;
+/// * `numbers` - The values to sort; they are not modified
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+///
+/// # Returns
+/// * `Vec<usize>` - Entry i is the original index of the i-th smallest value
+fn argsort(numbers: &[i32], base_case: BaseCase) -> Vec<usize> {
+    argsort_with(numbers, |tagged| merge_sort_with(tagged, base_case))
+}
+
+/// Times merge sort with each insertion sort cutoff in `TUNE_CUTOFFS`.
+///
+/// Every run sorts a fresh copy of `numbers`. Each cutoff is timed
+/// `TUNE_REPETITIONS` times and the median is kept, which filters out
+/// one-off interruptions on the local machine.
+///
+/// # Arguments
+/// * `numbers` - The unsorted input to time the sweep on
+///
+/// # Returns
+/// * `Vec<(usize, Duration)>` - The median sort time for each cutoff
+fn tune_cutoff(numbers: &[i32]) -> Vec<(usize, Duration)> {
+    let mut results = Vec::with_capacity(TUNE_CUTOFFS.len());
+
+    for &cutoff in TUNE_CUTOFFS.iter() {
+        let mut times = Vec::with_capacity(TUNE_REPETITIONS);
+        for _ in 0..TUNE_REPETITIONS {
+            let mut copy = numbers.to_vec();
+            let start = Instant::now();
+            merge_sort_with(&mut copy, BaseCase::Insertion(cutoff));
+            times.push(start.elapsed());
         }
-        k += 1;
+        times.sort();
+        results.push((cutoff, times[TUNE_REPETITIONS / 2]));
     }
-    
-    // Copy any remaining elements from the left subarray
-    while i <= mid {
-        arr[k] = buffer[i];
-        i += 1;
-        k += 1;
+
+    results
+}
+
+/// Prints the results of a cutoff sweep and the fastest cutoff.
+///
+/// # Arguments
+/// * `results` - The median sort time for each cutoff
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+fn print_tuning(results: &[(usize, Duration)]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    writeln!(writer, "{:>8}  {:>12}", "cutoff", "median (ms)")?;
+    for &(cutoff, time) in results {
+        writeln!(writer, "{:>8}  {:>12.3}", cutoff, time.as_secs_f64() * 1000.0)?;
     }
-    
-    // Copy any remaining elements from the right subarray
-    // Note: if right subarray is exhausted first, elements are already in place
-    while j <= right {
-        arr[k] = buffer[j];
-        j += 1;
-        k += 1;
+
+    if let Some(&(best, _)) = results.iter().min_by_key(|&&(_, time)| time) {
+        writeln!(writer, "best cutoff: {}", best)?;
     }
+
+    writer.flush()?;
+    Ok(())
 }
 
 /// Prints each integer in the vector to standard output, one per line.
@@ -193,12 +402,201 @@ fn print_output(numbers: &[i32]) -> io::Result<()> {
     Ok(())
 }
 
+/// Parses the command-line arguments.
+///
+/// Recognizes `--network`, `--cutoff N`, `--tune`, `--indices`,
+/// `--merge FILE...`, `--external`, `--memory SIZE`, `--count-inversions`,
+/// `--stats`, `--trace[=FILE]`, `--sound=FILE` and `--timings`.
+/// Only one base case may be chosen, and `--indices` requires a stable one.
+/// `--tune` sweeps its own base cases and replaces the output, so it cannot
+/// be combined with a base case or with `--indices`.
+/// Arguments that are not flags are the files to merge and are only allowed
+/// with `--merge`. `--memory` only applies to `--external`, which sorts
+/// standard input and so cannot be combined with `--tune`, `--indices` or
+/// `--merge`. `--count-inversions` replaces the output, so it cannot be
+/// combined with any other mode. `--stats` counts a plain sort and cannot
+/// be combined with any mode either. `--trace` and `--sound` record a plain
+/// sort, so neither can be combined with any mode, with `--stats` or with
+/// each other. `--timings` times a plain sort too, so it cannot be
+/// combined with any mode, with `--stats`, with `--trace` or with `--sound`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        base_case: BaseCase::Single,
+        tune: false,
+        indices: false,
+        merge: None,
+        external: None,
+        count_inversions: false,
+        stats: false,
+        trace: None,
+        sound: None,
+        timings: false,
+    };
+    let mut memory = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--network" | "--cutoff" if options.base_case != BaseCase::Single => {
+                return Err("--network and --cutoff cannot be combined".to_string());
+            }
+            "--network" => options.base_case = BaseCase::Network,
+            "--cutoff" => {
+                let value = iter.next().ok_or("--cutoff requires a value")?;
+                let cutoff = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid cutoff: {}", value))?;
+                options.base_case = BaseCase::Insertion(cutoff);
+            }
+            "--tune" => options.tune = true,
+            "--indices" => options.indices = true,
+            "--merge" => options.merge = Some(options.merge.unwrap_or_default()),
+            "--external" => options.external = Some(DEFAULT_MEMORY),
+            "--count-inversions" => options.count_inversions = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            "--memory" => {
+                let value = iter.next().ok_or("--memory requires a value")?;
+                memory = Some(parse_size(value)?);
+            }
+            other if other.starts_with("--") => {
+                if let Some(target) = Target::from_arg(other) {
+                    options.trace = Some(target);
+                } else if let Some(path) = op_sound::from_arg(other) {
+                    options.sound = Some(path);
+                } else {
+                    return Err(format!("Unknown argument: {}", other));
+                }
+            }
+            path => match options.merge {
+                Some(ref mut files) => files.push(path.to_string()),
+                None => return Err(format!("Unexpected argument: {} (use --merge)", path)),
+            },
+        }
+    }
+
+    if options.merge == Some(Vec::new()) {
+        return Err("--merge requires at least one file".to_string());
+    }
+
+    if options.indices && options.base_case == BaseCase::Network {
+        return Err("--indices needs a stable sort and cannot use --network".to_string());
+    }
+
+    if options.tune && (options.base_case != BaseCase::Single || options.indices) {
+        return Err("--tune cannot be combined with --network, --cutoff or --indices".to_string());
+    }
+
+    if let Some(memory) = memory {
+        match options.external {
+            Some(ref mut budget) => *budget = memory,
+            None => return Err("--memory requires --external".to_string()),
+        }
+    }
+
+    if options.external.is_some() && (options.tune || options.indices || options.merge.is_some()) {
+        return Err("--external cannot be combined with --tune, --indices or --merge".to_string());
+    }
+
+    let other_mode = options.tune
+        || options.indices
+        || options.merge.is_some()
+        || options.external.is_some();
+    if options.count_inversions && other_mode {
+        return Err("--count-inversions cannot be combined with another mode".to_string());
+    }
+
+    if options.stats && (other_mode || options.count_inversions) {
+        return Err("--stats cannot be combined with another mode".to_string());
+    }
+
+    if options.trace.is_some() && (other_mode || options.count_inversions || options.stats) {
+        return Err("--trace cannot be combined with --stats or another mode".to_string());
+    }
+
+    let recorded = options.stats || options.trace.is_some();
+    if options.sound.is_some() && (other_mode || options.count_inversions || recorded) {
+        return Err("--sound cannot be combined with --stats, --trace or another mode".to_string());
+    }
+
+    let recorded = recorded || options.sound.is_some();
+    if options.timings && (other_mode || options.count_inversions || recorded) {
+        return Err(
+            "--timings cannot be combined with --stats, --trace, --sound or another mode"
+                .to_string(),
+        );
+    }
+
+    Ok(options)
+}
+
 /// Main entry point for the merge sort program.
 ///
 /// Orchestrates reading from stdin, sorting, and writing to stdout.
 /// Exits with status code 1 if any errors occur during execution.
 fn main() {
-    // Read integers from standard input
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Merge already-sorted files instead of reading standard input
+    if let Some(files) = options.merge {
+        if let Err(e) = merge_files(&files) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Sort standard input in chunks that fit the memory budget
+    if let Some(memory) = options.external {
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        let base_case = options.base_case;
+        let result = external_sort(
+            stdin.lock(),
+            BufWriter::new(stdout.lock()),
+            memory,
+            &env::temp_dir(),
+            |chunk| merge_sort_with(chunk, base_case),
+        );
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Print the original line numbers in sorted order instead of the values
+    if options.indices {
+        let (numbers, lines) = match read_input_with_lines() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = print_line_numbers(&argsort(&numbers, options.base_case), &lines) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
     let mut numbers = match read_input() {
         Ok(nums) => nums,
         Err(e) => {
@@ -206,20 +604,77 @@ fn main() {
             process::exit(1);
         }
     };
+    timer.lap("read_input");
 
-    // Sort the numbers using merge sort
-    merge_sort(&mut numbers);
+    // Count the inversions instead of printing the sorted numbers
+    if options.count_inversions {
+        println!("{}", count_inversions(&numbers));
+        return;
+    }
+
+    // Sweep insertion sort cutoffs instead of sorting
+    if options.tune {
+        if let Err(e) = print_tuning(&tune_cutoff(&numbers)) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Sort the numbers using merge sort, tracing, playing or counting operations if asked
+    if let Some(target) = options.trace {
+        let base_case = options.base_case;
+        let written = target.open().and_then(|writer| {
+            op_trace::record(&mut numbers, "merge sort", writer, |slice| {
+                merge_sort_with(slice, base_case)
+            })
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing trace: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(path) = options.sound {
+        let base_case = options.base_case;
+        let written = File::create(&path).and_then(|file| {
+            op_sound::record(&mut numbers, BufWriter::new(file), |slice| {
+                merge_sort_with(slice, base_case)
+            })
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing sound: {}", e);
+            process::exit(1);
+        }
+    } else if options.stats {
+        let base_case = options.base_case;
+        let mut heap = 0;
+        let mut counts = count(&mut numbers, |slice| {
+            heap = peak_heap(|| merge_sort_with(slice, base_case)).1;
+        });
+        counts.peak_heap_bytes = Some(heap as u64);
+        eprint!("{}", counts.summary("merge sort", numbers.len()));
+    } else {
+        merge_sort_with(&mut numbers, options.base_case);
+    }
+
+    timer.lap("sort");
 
     // Print the sorted numbers to standard output
     if let Err(e) = print_output(&numbers) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("merge sort", numbers.len()));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use xorshift::XorShift64;
 
     #[test]
     fn test_empty_array() {
@@ -306,4 +761,376 @@ mod tests {
         merge_sort(&mut arr);
         assert_eq!(arr, vec![1, 2, 3, 3, 3]);
     }
+
+    #[test]
+    fn test_network_base_case() {
+        // Cover lengths below, at and well above the network size
+        let mut rng = XorShift64::new(2026);
+        for len in 0..200 {
+            let arr: Vec<i32> = rng.values(len, -50..50);
+            let mut expected = arr.clone();
+            merge_sort(&mut expected);
+            let mut actual = arr;
+            merge_sort_with(&mut actual, BaseCase::Network);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_insertion_cutoff() {
+        let mut rng = XorShift64::new(416);
+        for &cutoff in &[0, 1, 2, 5, 16, 32, 100] {
+            for len in 0..150 {
+                let arr: Vec<i32> = rng.values(len, -50..50);
+                let mut expected = arr.clone();
+                merge_sort(&mut expected);
+                let mut actual = arr;
+                merge_sort_with(&mut actual, BaseCase::Insertion(cutoff));
+                assert_eq!(actual, expected, "cutoff {}, length {}", cutoff, len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                base_case: BaseCase::Single,
+                tune: false,
+                indices: false,
+                merge: None,
+                external: None,
+                count_inversions: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--cutoff", "24"])),
+            Ok(Options {
+                base_case: BaseCase::Insertion(24),
+                tune: false,
+                indices: false,
+                merge: None,
+                external: None,
+                count_inversions: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--tune"])),
+            Ok(Options {
+                base_case: BaseCase::Single,
+                tune: true,
+                indices: false,
+                merge: None,
+                external: None,
+                count_inversions: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--indices", "--cutoff", "8"])),
+            Ok(Options {
+                base_case: BaseCase::Insertion(8),
+                tune: false,
+                indices: true,
+                merge: None,
+                external: None,
+                count_inversions: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert!(parse_args(&args(&["--network", "--indices"])).is_err());
+        // The sweep ignores any base case and prints no line numbers
+        assert!(parse_args(&args(&["--network", "--tune"])).is_err());
+        assert!(parse_args(&args(&["--tune", "--cutoff", "8"])).is_err());
+        assert!(parse_args(&args(&["--tune", "--indices"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["a.txt", "--merge", "b.txt"])).err(),
+            Some("Unexpected argument: a.txt (use --merge)".to_string())
+        );
+        assert_eq!(
+            parse_args(&args(&["--merge", "a.txt", "b.txt"])).map(|o| o.merge),
+            Ok(Some(vec!["a.txt".to_string(), "b.txt".to_string()]))
+        );
+        assert!(parse_args(&args(&["--merge"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--external"])).map(|o| o.external),
+            Ok(Some(DEFAULT_MEMORY))
+        );
+        assert_eq!(
+            parse_args(&args(&["--memory", "512K", "--external", "--network"])).map(|o| o.external),
+            Ok(Some(512 << 10))
+        );
+        assert!(parse_args(&args(&["--memory", "512K"])).is_err());
+        assert!(parse_args(&args(&["--external", "--memory", "lots"])).is_err());
+        assert!(parse_args(&args(&["--external", "--indices"])).is_err());
+        assert!(parse_args(&args(&["--external", "--merge", "a.txt"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--count-inversions"])).map(|o| o.count_inversions),
+            Ok(true)
+        );
+        assert!(parse_args(&args(&["--count-inversions", "--tune"])).is_err());
+        assert!(parse_args(&args(&["--indices", "--count-inversions"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--stats", "--network"])).map(|o| (o.stats, o.base_case)),
+            Ok((true, BaseCase::Network))
+        );
+        assert!(parse_args(&args(&["--stats", "--tune"])).is_err());
+        assert!(parse_args(&args(&["--indices", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--stats", "--count-inversions"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--cutoff", "8", "--trace=out.jsonl"])).map(|o| o.trace),
+            Ok(Some(Target::File("out.jsonl".to_string())))
+        );
+        assert_eq!(parse_args(&args(&["--trace"])).map(|o| o.trace), Ok(Some(Target::Stderr)));
+        assert!(parse_args(&args(&["--trace", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--indices", "--trace"])).is_err());
+        assert!(parse_args(&args(&["--trace=x", "--count-inversions"])).is_err());
+        assert!(parse_args(&args(&["--trace=x", "--external"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--network", "--sound=out.wav"])).map(|o| o.sound),
+            Ok(Some("out.wav".to_string()))
+        );
+        assert!(parse_args(&args(&["--sound=x", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--trace", "--sound=x"])).is_err());
+        assert!(parse_args(&args(&["--sound=x", "--tune"])).is_err());
+        assert!(parse_args(&args(&["--sound"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--network", "--timings"])).map(|o| o.timings),
+            Ok(true)
+        );
+        assert!(parse_args(&args(&["--timings", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--trace", "--timings"])).is_err());
+        assert!(parse_args(&args(&["--timings", "--sound=x"])).is_err());
+        assert!(parse_args(&args(&["--timings", "--count-inversions"])).is_err());
+        assert!(parse_args(&args(&["--external", "--timings"])).is_err());
+        assert!(parse_args(&args(&["--cutoff"])).is_err());
+        assert!(parse_args(&args(&["--cutoff", "abc"])).is_err());
+        assert!(parse_args(&args(&["--network", "--cutoff", "8"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_tune_cutoff_covers_every_cutoff() {
+        let numbers: Vec<i32> = (0..500).rev().collect();
+        let results = tune_cutoff(&numbers);
+        let cutoffs: Vec<usize> = results.iter().map(|&(cutoff, _)| cutoff).collect();
+        assert_eq!(cutoffs, TUNE_CUTOFFS.to_vec());
+    }
+
+    #[test]
+    fn test_argsort_is_stable() {
+        let mut rng = XorShift64::new(2718);
+        let numbers: Vec<i32> = rng.values(3000, 0..50);
+
+        // A stable sort of the indices by value gives the unique answer
+        let mut expected: Vec<usize> = (0..numbers.len()).collect();
+        expected.sort_by_key(|&i| numbers[i]);
+
+        for &base_case in &[BaseCase::Single, BaseCase::Insertion(16)] {
+            assert_eq!(argsort(&numbers, base_case), expected, "{:?}", base_case);
+        }
+    }
+
+    // Lets the tests sort strings
+    impl Tally for &str {}
+
+    #[test]
+    fn test_generic_merge_sort() {
+        let mut words = vec!["pear", "apple", "fig", "apple", "kiwi"];
+        merge_sort_with(&mut words, BaseCase::Network);
+        assert_eq!(words, vec!["apple", "apple", "fig", "kiwi", "pear"]);
+    }
+
+    /// Counts inversions by checking every pair.
+    fn brute_force_inversions(numbers: &[i32]) -> u64 {
+        let mut count = 0;
+        for i in 0..numbers.len() {
+            for j in i + 1..numbers.len() {
+                if numbers[i] > numbers[j] {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_count_inversions_matches_brute_force() {
+        let mut rng = XorShift64::new(2024);
+        for len in 0..80 {
+            for &bound in [3, 50, 1 << 20].iter() {
+                let numbers: Vec<i32> = rng.values(len, -(bound / 2)..bound - bound / 2);
+                let copy = numbers.clone();
+                assert_eq!(count_inversions(&numbers), brute_force_inversions(&numbers));
+                assert_eq!(numbers, copy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_inversions_small_cases() {
+        assert_eq!(count_inversions::<i32>(&[]), 0);
+        assert_eq!(count_inversions(&[5]), 0);
+        assert_eq!(count_inversions(&[2, 1]), 1);
+        assert_eq!(count_inversions(&[1, 1, 1]), 0);
+        assert_eq!(count_inversions(&[3, 1, 2]), 2);
+        assert_eq!(count_inversions(&[i32::MAX, i32::MIN]), 1);
+    }
+
+    #[test]
+    fn test_count_inversions_exceeds_u32() {
+        // 100000 reversed values have n(n-1)/2, about 5 * 10^9, inversions
+        let reverse: Vec<i32> = (0..100_000).rev().collect();
+        assert_eq!(count_inversions(&reverse), 4_999_950_000);
+    }
+
+    #[test]
+    fn test_stats_match_documented_bounds() {
+        // 64 = 2^6 elements: six levels of merges over all 64 elements
+        let sorted: Vec<i32> = (0..64).collect();
+        let reverse: Vec<i32> = (0..64).rev().collect();
+        for input in [sorted, reverse].iter() {
+            let mut arr = input.clone();
+            let counts = count(&mut arr, |slice| merge_sort_with(slice, BaseCase::Single));
+            assert_eq!(arr, (0..64).collect::<Vec<i32>>());
+            // One half runs out after len / 2 comparisons in every merge
+            assert_eq!(counts.comparisons, 32 * 6);
+            // The initial buffer, then each level copies in and back out
+            assert_eq!(counts.buffer_copies, 64 + 2 * 64 * 6);
+            assert_eq!((counts.swaps, counts.writes), (0, 0));
+        }
+
+        // Random input needs about n log2 n - 1.25 n comparisons
+        let mut rng = XorShift64::new(5);
+        let mut random: Vec<i32> = rng.values(1024, 0..1 << 24);
+        let counts = count(&mut random, |slice| merge_sort_with(slice, BaseCase::Single));
+        assert!(counts.comparisons <= 1024 * 10, "{:?}", counts);
+        assert!(counts.comparisons >= 1024 * 10 - 2 * 1024, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_stats_count_base_case_writes() {
+        let mut arr: Vec<i32> = (0..16).rev().collect();
+        let counts = count(&mut arr, |slice| merge_sort_with(slice, BaseCase::Network));
+        assert_eq!(arr, (0..16).collect::<Vec<i32>>());
+        // One network of 60 comparators, and the buffer copy of the input
+        assert_eq!((counts.comparisons, counts.writes, counts.buffer_copies), (60, 0, 16));
+        assert!(counts.swaps > 0 && counts.swaps <= 60, "{:?}", counts);
+
+        let mut arr: Vec<i32> = (0..8).rev().collect();
+        let counts = count(&mut arr, |slice| merge_sort_with(slice, BaseCase::Insertion(16)));
+        assert_eq!(counts.comparisons, 28);
+        assert_eq!(counts.writes, 28 + 7);
+    }
+
+    #[test]
+    fn test_depth_and_heap_match_documented_space() {
+        // The recursion halves the range down to single elements, and the
+        // only allocation is the buffer, whatever the input order
+        for &n in &[2usize, 3, 100, 1000, 1024, 1025] {
+            let mut orders: Vec<Vec<i32>> = vec![(0..n as i32).collect()];
+            orders.push((0..n as i32).rev().collect());
+            orders.push(XorShift64::new(n as u64).values(n, 0..1 << 16));
+            let log = (n as f64).log2().ceil() as u64;
+            for mut arr in orders {
+                let mut heap = 0;
+                let counts = count(&mut arr, |slice| {
+                    heap = peak_heap(|| merge_sort_with(slice, BaseCase::Single)).1;
+                });
+                assert_eq!(counts.max_depth, log + 1, "n = {}", n);
+                assert_eq!(heap, n * std::mem::size_of::<i32>(), "n = {}", n);
+            }
+        }
+
+        // A base case stops the recursion early
+        let counts = count(&mut (0..1000).collect::<Vec<i32>>(), |slice| {
+            merge_sort_with(slice, BaseCase::Insertion(16))
+        });
+        assert_eq!(counts.max_depth, 8);
+        assert_eq!(count(&mut [1], |slice| merge_sort_with(slice, BaseCase::Single)).max_depth, 0);
+    }
+
+    #[test]
+    fn test_trace_shows_merges_and_copies() {
+        use op_trace::Event;
+        use std::fs;
+
+        let path = env::temp_dir().join(format!("merge_trace_{}.jsonl", process::id()));
+        let target = Target::File(path.to_string_lossy().into_owned());
+        let mut numbers = vec![4, 3, 2, 1];
+        let writer = target.open().unwrap();
+        op_trace::record(&mut numbers, "merge sort", writer, |slice| {
+            merge_sort_with(slice, BaseCase::Single)
+        })
+        .unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4]);
+        let events: Vec<Event> = text.lines().map(|line| Event::parse(line).unwrap()).collect();
+
+        // The runs of each merge, bottom up
+        let merges: Vec<(usize, usize, usize)> = events
+            .iter()
+            .filter_map(|event| match *event {
+                Event::Merge { left: Some(l), mid: Some(m), right: Some(r) } => Some((l, m, r)),
+                Event::Merge { .. } => panic!("merge ranges must be known"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(merges, vec![(0, 0, 1), (2, 2, 3), (0, 1, 3)]);
+
+        // Comparisons of buffer values report the positions they mirror,
+        // and every position of a merged range is written back
+        let mut writes = 0;
+        let mut array = Vec::new();
+        for event in &events {
+            match *event {
+                Event::Compare { i, j, .. } => assert!(i.is_some() && j.is_some()),
+                Event::Write { i, .. } => {
+                    assert!(i.is_some());
+                    writes += 1;
+                }
+                _ => {}
+            }
+            event.apply(&mut array);
+        }
+        assert_eq!(writes, 2 + 2 + 4);
+        assert_eq!(array, numbers);
+    }
+
+    #[test]
+    fn test_sound_has_a_tone_per_access() {
+        let mut numbers = vec![4, 3, 2, 1];
+        let mut wav = Vec::new();
+        op_sound::record(&mut numbers, &mut wav, |slice| {
+            merge_sort_with(slice, BaseCase::Single)
+        })
+        .unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4]);
+
+        // Both values of each comparison, then each value copied back
+        let counts = count(&mut [4, 3, 2, 1], |slice| merge_sort_with(slice, BaseCase::Single));
+        let accesses = 2 * counts.comparisons as usize + (2 + 2 + 4);
+        let samples = accesses * op_sound::tone_length(accesses);
+        assert_eq!(wav.len(), 44 + 2 * samples);
+    }
 }
@@ -0,0 +1,481 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: oddEvenMergeSort.rs
+
+   Description:
+      This program implements Odd-Even Merge Sort to sort a list of integers. It reads
+      integers from standard input (one per line), sorts them in ascending
+      order, and prints the sorted result to standard output (one integer
+      per line). Designed for use with input/output redirection and
+      benchmarking.
+
+      Odd-Even Merge Sort is a data-oblivious sorting network: the sequence of
+      compare-exchange operations depends only on the input length, never
+      on the values. Every comparator picks the smaller and the larger
+      value without branching on them, so the running time is the same for
+      all six test distributions (an optimized test build, rustc -O --test,
+      checks this) and the network can be used where comparisons must run
+      in constant time. Inputs whose length is not a power of two are
+      padded with copies of the largest value. Each stage of the network is
+      spread over all available cores; --threads N overrides the number of
+      worker threads.
+
+      With --stats the program reports to standard error how many
+      comparisons and swaps the network performed. Every comparator is one
+      comparison, so that count is the number of comparators whatever the
+      input; the swaps are the comparators that found their pair out of
+      order.
+
+      With --timings the program reports to standard error how long it
+      took to read the input, to sort and to print the output (see
+      phaseTimer.rs), so the network can be timed apart from the I/O.
+      Counting would slow the network down, so --timings cannot be
+      combined with --stats.
+
+   Usage:
+      ./oddEvenMergeSort < input.txt > output.txt
+      ./oddEvenMergeSort --threads 4 < input.txt > output.txt
+      ./oddEvenMergeSort --stats < input.txt > output.txt
+      ./oddEvenMergeSort --threads 4 --timings < input.txt > output.txt
+      cat input.txt | ./oddEvenMergeSort
+*/
+
+mod op_counter;
+mod phase_timer;
+mod stage_executor;
+#[cfg(test)]
+mod xorshift;
+
+use op_counter::{count, Tally};
+use phase_timer::PhaseTimer;
+use stage_executor::{available_threads, run_stage, sort_padded};
+#[cfg(test)]
+use stage_executor::timing_spread;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// The maximum number of worker threads per stage
+    threads: usize,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Sorts a slice of integers in ascending order using Batcher's Odd-Even Merge Sort.
+///
+/// Odd-Even Merge Sort merges sorted runs of doubling length p. Merging two
+/// runs of length p into a block of 2p elements takes log(2p) stages:
+/// 1. For k = p, compare element i with element i+p in each block
+/// 2. For k = p/2, p/4, ..., 1, compare elements k apart, skipping the
+///    pairs that earlier stages have already put in order
+///
+/// Every comparator places the smaller value at the lower index. Each step
+/// is one stage of the network and runs on `threads` worker threads.
+///
+/// # Time Complexity
+/// * Best case: O(n log² n) - the network is the same for every input
+/// * Average case: O(n log² n)
+/// * Worst case: O(n log² n)
+///
+/// The network has (n/4)(log²n - log n) + n - 1 comparators, fewer than the
+/// (n/4)(log²n + log n) of Bitonic Sort.
+///
+/// # Space Complexity
+/// * O(n) - padding buffer when the length is not a power of two
+/// * O(1) - otherwise, the network sorts in place
+///
+/// # Advantages
+/// * Data-oblivious: the comparison sequence never depends on the values
+/// * Fewer comparators than Bitonic Sort
+///
+/// # Disadvantages
+/// * More comparisons than merge sort or quick sort (O(n log² n))
+/// * Not stable (relative order of equal elements may change)
+/// * Not adaptive (doesn't benefit from partially sorted data)
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of i32 integers to sort
+pub fn odd_even_merge_sort(numbers: &mut [i32]) {
+    odd_even_merge_sort_with_threads(numbers, available_threads());
+}
+
+/// Sorts a slice of integers with Odd-Even Merge Sort using the given number of threads.
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+/// * `threads` - The maximum number of worker threads per stage
+fn odd_even_merge_sort_with_threads<T: Tally + Send>(numbers: &mut [T], threads: usize) {
+    sort_padded(numbers, threads, odd_even_merge_network);
+}
+
+/// Runs Batcher's odd-even merge network on a slice whose length is a power of two.
+///
+/// Within a block of 2p elements, the stage for distance k compares pairs
+/// (i, i+k) whose indices start at k mod p and come in runs of k: the
+/// segments [k mod p + 2mk, k mod p + 2mk + k) and the k elements after them.
+///
+/// # Arguments
+/// * `numbers` - The slice to sort; its length must be a power of two
+/// * `threads` - The maximum number of worker threads per stage
+fn odd_even_merge_network<T: Tally + Send>(numbers: &mut [T], threads: usize) {
+    let n = numbers.len();
+    debug_assert!(n.is_power_of_two());
+
+    let mut p = 1;
+    while p < n {
+        let mut k = p;
+        while k >= 1 {
+            let offset = k % p;
+            let mut pairs = Vec::new();
+            for block in numbers.chunks_mut(2 * p) {
+                for run in block[offset..].chunks_exact_mut(2 * k) {
+                    pairs.push(run.split_at_mut(k));
+                }
+            }
+            run_stage(pairs, false, threads);
+            k /= 2;
+        }
+        p *= 2;
+    }
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Parses the command-line arguments.
+///
+/// Recognizes `--threads N`, which sets the number of worker threads used
+/// for each stage and defaults to the available parallelism, `--stats` and
+/// `--timings`, which times a plain sort and so excludes `--stats`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        threads: available_threads(),
+        stats: false,
+        timings: false,
+    };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--threads" => {
+                let value = iter.next().ok_or("--threads requires a value")?;
+                options.threads = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid thread count: {}", value)),
+                };
+            }
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    if options.timings && options.stats {
+        return Err("--timings cannot be combined with --stats".to_string());
+    }
+
+    Ok(options)
+}
+
+/// Main entry point for the odd-even merge sort program.
+///
+/// Orchestrates reading from stdin, sorting, and writing to stdout.
+/// Exits with status code 1 if any errors occur during execution.
+fn main() {
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // Sort the numbers using odd-even merge sort, counting operations if asked
+    if options.stats {
+        let threads = options.threads;
+        let counts = count(&mut numbers, |slice| odd_even_merge_sort_with_threads(slice, threads));
+        eprint!("{}", counts.summary("odd-even merge sort", numbers.len()));
+    } else {
+        odd_even_merge_sort_with_threads(&mut numbers, options.threads);
+    }
+
+    timer.lap("sort");
+
+    // Print the sorted numbers to standard output
+    if let Err(e) = print_output(&numbers) {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("odd-even merge sort", numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xorshift::XorShift64;
+
+    #[test]
+    fn test_empty_array() {
+        let mut arr: Vec<i32> = vec![];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+    }
+
+    #[test]
+    fn test_single_element() {
+        let mut arr = vec![42];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_already_sorted() {
+        let mut arr = vec![1, 2, 3, 4, 5];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        let mut arr = vec![5, 4, 3, 2, 1];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_order() {
+        let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr = vec![5, 2, 8, 2, 9, 1, 5, 5];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 2, 5, 5, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let mut arr = vec![-3, 5, -1, 0, -9, 2];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![-9, -3, -1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let mut arr = vec![1000000, -1000000, 0, 999999, -999999];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
+    }
+
+    #[test]
+    fn test_two_elements_sorted() {
+        let mut arr = vec![1, 2];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_two_elements_unsorted() {
+        let mut arr = vec![2, 1];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        odd_even_merge_sort(&mut arr);
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_non_power_of_two_lengths() {
+        let mut rng = XorShift64::new(41600);
+        for len in 0..=100 {
+            let arr: Vec<i32> = rng.values(len, -25..25);
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut actual = arr;
+            odd_even_merge_sort(&mut actual);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_padding_value_in_input() {
+        let mut arr = vec![i32::MAX, 3, i32::MIN, i32::MAX, 0];
+        odd_even_merge_sort(&mut arr);
+        assert_eq!(arr, vec![i32::MIN, 0, 3, i32::MAX, i32::MAX]);
+    }
+
+    #[test]
+    fn test_threads_match_single_thread() {
+        // Large enough for the stages to be split across worker threads
+        let arr: Vec<i32> = (0..100_000).map(|x: i32| x.wrapping_mul(7919) % 10_007).collect();
+        let mut single = arr.clone();
+        odd_even_merge_sort_with_threads(&mut single, 1);
+        let mut threaded = arr.clone();
+        odd_even_merge_sort_with_threads(&mut threaded, 4);
+        let mut expected = arr;
+        expected.sort();
+        assert_eq!(single, expected);
+        assert_eq!(threaded, expected);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&["--threads".to_string(), "3".to_string()]),
+            Ok(Options {
+                threads: 3,
+                stats: false,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&["--stats".to_string(), "--threads".to_string(), "2".to_string()]),
+            Ok(Options {
+                threads: 2,
+                stats: true,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&["--timings".to_string()]).map(|o| (o.stats, o.timings)),
+            Ok((false, true))
+        );
+        assert!(parse_args(&["--timings".to_string(), "--stats".to_string()]).is_err());
+        assert!(parse_args(&["--threads".to_string(), "0".to_string()]).is_err());
+        assert!(parse_args(&["--threads".to_string()]).is_err());
+        assert!(parse_args(&["--bogus".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_stats_count_comparators() {
+        // Batcher's network for 16 inputs has 63 comparators
+        let mut arr: Vec<i32> = (0..16).rev().collect();
+        let counts = count(&mut arr, |slice| odd_even_merge_sort_with_threads(slice, 1));
+        assert_eq!(arr, (0..16).collect::<Vec<i32>>());
+        assert_eq!((counts.comparisons, counts.writes), (63, 0));
+        assert!(counts.swaps > 0 && counts.swaps <= 63, "{:?}", counts);
+
+        // The same network runs on sorted input, which it never swaps
+        let mut arr: Vec<i32> = (0..16).collect();
+        let counts = count(&mut arr, |slice| odd_even_merge_sort_with_threads(slice, 1));
+        assert_eq!((counts.comparisons, counts.writes), (63, 0));
+        assert_eq!((counts.swaps, counts.buffer_copies), (0, 0));
+    }
+
+    // Unoptimized code spends so long around each comparison that a branch
+    // on the values hardly shows, so this only runs in optimized test builds
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn test_running_time_is_data_independent() {
+        // A branch on the values makes random input two to three times
+        // slower than sorted input; without one the six distributions only
+        // differ by the noise of the test machine
+        let spread = timing_spread(1 << 14, |slice| odd_even_merge_sort_with_threads(slice, 1));
+        assert!(spread < 1.5, "slowest distribution took {:.2}x the fastest", spread);
+    }
+}
@@ -0,0 +1,416 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: opCounter.rs
+
+   Description:
+      This module counts the basic operations a sorting algorithm performs,
+      so the measured counts can be checked against the best, average and
+      worst cases quoted in the programs' doc comments. Four kinds of
+      operation are counted:
+
+        * comparisons - every comparison of two elements,
+        * swaps - every exchange of two elements of the array being sorted,
+        * writes - every single element stored into the array being sorted
+          outside a swap, such as the shifts of insertion sort,
+        * buffer copies - every element copied into or out of auxiliary
+          storage, such as a merge buffer, a heap or a tree.
+
+      Recursive sorts also report how deep their recursion goes, so the
+      stack space quoted for them can be checked as well. Programs that
+      include heapCounter.rs can add the peak heap use of the sort to the
+      counts before printing them.
+
+      Comparisons are counted by sorting `Counted` values, whose comparison
+      operators bump a counter. The other operations cannot be seen from
+      the element type, so the sorts report them through the hooks of the
+      `Tally` trait. For plain i32 the hooks are empty and inlined away, so
+      the normal sorting path pays nothing for them.
+
+      The counters are shared by all threads, so operations performed by
+      worker threads are counted too.
+
+      The swap and write hooks receive the elements involved, and a few
+      more hooks mark merges and partitions. Counting ignores those, but
+      the tracer in opTrace.rs turns them into events for --trace, and
+      opSound.rs listens to the same hooks and turns each access into a
+      tone for --sound. Programs that offer those options include both
+      modules next to this one.
+
+   Usage:
+      mod op_counter;
+      use op_counter::{count, Tally};
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Mutex;
+
+static COMPARISONS: AtomicU64 = AtomicU64::new(0);
+static SWAPS: AtomicU64 = AtomicU64::new(0);
+static WRITES: AtomicU64 = AtomicU64::new(0);
+static BUFFER_COPIES: AtomicU64 = AtomicU64::new(0);
+static MAX_DEPTH: AtomicU64 = AtomicU64::new(0);
+
+/// Held while a sort is being counted, so that concurrent measurements
+/// (such as tests running in parallel) don't mix their counts.
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// Hooks through which a sort reports the operations it performs.
+///
+/// Every method does nothing by default, except that `exchanged` passes a
+/// swap on to `swapped`. `Counted` overrides the first four,
+/// `op_sound::Sounded` the ones that access values, and `op_trace::Traced`
+/// all of them but `exchanged`. Hooks that take elements expect references
+/// into the slice being sorted (or into a buffer passed to `buffered`),
+/// since a tracer finds an element's position from its address.
+pub trait Tally: Ord + Copy {
+    /// Records that two elements of the array were exchanged.
+    ///
+    /// # Arguments
+    /// * `a` - One of the elements, at its new position
+    /// * `b` - The other element, at its new position
+    #[inline(always)]
+    fn swapped(_a: &Self, _b: &Self) {}
+
+    /// Records a compare-exchange of a sorting network, which swapped its
+    /// two elements if `swapped` is set. The networks call this on every
+    /// comparator, so that overriding it lets a tally count swaps without
+    /// branching on the data; by default it calls `swapped` when needed.
+    ///
+    /// # Arguments
+    /// * `a` - The element that received the smaller value
+    /// * `b` - The element that received the larger value
+    /// * `swapped` - Whether the two values were exchanged
+    #[inline(always)]
+    fn exchanged(a: &Self, b: &Self, swapped: bool) {
+        if swapped {
+            Self::swapped(a, b);
+        }
+    }
+
+    /// Records a single element stored into the array outside a swap.
+    ///
+    /// # Arguments
+    /// * `slot` - The element just stored
+    #[inline(always)]
+    fn wrote(_slot: &Self) {}
+
+    /// Records `count` elements copied into or out of auxiliary storage.
+    #[inline(always)]
+    fn copied(_count: usize) {}
+
+    /// Records that a value was copied back from auxiliary storage into
+    /// `slot`. The copy is already counted by `copied`, so only a trace
+    /// uses this.
+    #[inline(always)]
+    fn restored(_slot: &Self) {}
+
+    /// Announces auxiliary storage whose element i stands for position i
+    /// of the array, such as the merge buffer of merge sort.
+    #[inline(always)]
+    fn buffered(_buffer: &[Self]) {}
+
+    /// Records that two adjacent sorted runs are about to be merged.
+    #[inline(always)]
+    fn merging(_left: &[Self], _right: &[Self]) {}
+
+    /// Records that `range` was partitioned around the element `pivot`,
+    /// which is now in its final position.
+    #[inline(always)]
+    fn partitioned(_range: &[Self], _pivot: &Self) {}
+
+    /// Records that a recursive sort entered a call `depth` levels deep,
+    /// where the outermost call is at depth 1.
+    #[inline(always)]
+    fn descended(_depth: usize) {}
+}
+
+impl Tally for i32 {}
+
+/// An i32 whose comparisons are counted.
+#[derive(Clone, Copy, Debug)]
+pub struct Counted(pub i32);
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Counted) -> bool {
+        COMPARISONS.fetch_add(1, Relaxed);
+        self.0 == other.0
+    }
+}
+
+impl Eq for Counted {}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Counted) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted {
+    fn cmp(&self, other: &Counted) -> Ordering {
+        COMPARISONS.fetch_add(1, Relaxed);
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Tally for Counted {
+    fn swapped(_a: &Counted, _b: &Counted) {
+        SWAPS.fetch_add(1, Relaxed);
+    }
+
+    fn exchanged(_a: &Counted, _b: &Counted, swapped: bool) {
+        SWAPS.fetch_add(swapped as u64, Relaxed);
+    }
+
+    fn wrote(_slot: &Counted) {
+        WRITES.fetch_add(1, Relaxed);
+    }
+
+    fn copied(count: usize) {
+        BUFFER_COPIES.fetch_add(count as u64, Relaxed);
+    }
+
+    fn descended(depth: usize) {
+        MAX_DEPTH.fetch_max(depth as u64, Relaxed);
+    }
+}
+
+/// The operations counted during one sort.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counts {
+    /// Comparisons of two elements
+    pub comparisons: u64,
+    /// Exchanges of two elements of the array
+    pub swaps: u64,
+    /// Single elements stored into the array outside a swap
+    pub writes: u64,
+    /// Elements copied into or out of auxiliary storage
+    pub buffer_copies: u64,
+    /// The deepest level of recursion reached, or 0 for a sort that
+    /// doesn't recurse
+    pub max_depth: u64,
+    /// The most heap bytes the sort held at once, if it was measured
+    pub peak_heap_bytes: Option<u64>,
+}
+
+impl Counts {
+    /// Reads the counters and resets them to zero.
+    fn take() -> Counts {
+        Counts {
+            comparisons: COMPARISONS.swap(0, Relaxed),
+            swaps: SWAPS.swap(0, Relaxed),
+            writes: WRITES.swap(0, Relaxed),
+            buffer_copies: BUFFER_COPIES.swap(0, Relaxed),
+            max_depth: MAX_DEPTH.swap(0, Relaxed),
+            peak_heap_bytes: None,
+        }
+    }
+
+    /// Formats the counts for printing, along with the usual reference
+    /// points n log₂ n and n(n-1)/2 for comparing them with the documented
+    /// complexities. The recursion depth is only shown for sorts that
+    /// recurse, and the peak heap use only if it was measured.
+    ///
+    /// # Arguments
+    /// * `algorithm` - The name of the algorithm that was counted
+    /// * `len` - The number of values sorted
+    ///
+    /// # Returns
+    /// * `String` - A multi-line summary ending in a newline
+    pub fn summary(&self, algorithm: &str, len: usize) -> String {
+        let n = len as f64;
+        let n_log_n = if len > 1 { n * n.log2() } else { 0.0 };
+        let pairs = len as u64 * len.saturating_sub(1) as u64 / 2;
+
+        let mut summary = format!(
+            "stats: {} on {} values\n\
+             {:<15} {:>15}\n\
+             {:<15} {:>15}\n\
+             {:<15} {:>15}\n\
+             {:<15} {:>15}\n",
+            algorithm,
+            len,
+            "comparisons",
+            self.comparisons,
+            "swaps",
+            self.swaps,
+            "writes",
+            self.writes,
+            "buffer copies",
+            self.buffer_copies
+        );
+        if self.max_depth > 0 {
+            summary += &format!("{:<15} {:>15}\n", "recursion depth", self.max_depth);
+        }
+        if let Some(bytes) = self.peak_heap_bytes {
+            summary += &format!("{:<15} {:>15}\n", "peak heap bytes", bytes);
+        }
+        summary + &format!("for reference: n log2 n = {:.0}, n(n-1)/2 = {}\n", n_log_n, pairs)
+    }
+}
+
+/// Sorts `numbers` with the given sort and counts the operations it performs.
+///
+/// The values are wrapped in `Counted`, sorted, and unwrapped again, so
+/// `numbers` ends up sorted exactly as the sort would leave plain integers.
+///
+/// # Arguments
+/// * `numbers` - The values to sort
+/// * `sort` - Sorts a slice of counted values in place
+///
+/// # Returns
+/// * `Counts` - The operations performed by `sort`
+pub fn count<F: FnOnce(&mut [Counted])>(numbers: &mut [i32], sort: F) -> Counts {
+    let mut counted: Vec<Counted> = numbers.iter().map(|&value| Counted(value)).collect();
+
+    // A panic in another measurement leaves nothing that needs repair
+    let _guard = MEASURING.lock().unwrap_or_else(|e| e.into_inner());
+    Counts::take();
+    sort(&mut counted);
+    let counts = Counts::take();
+
+    for (number, value) in numbers.iter_mut().zip(counted) {
+        *number = value.0;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bubble sort that reports its swaps.
+    fn bubble<T: Tally>(numbers: &mut [T]) {
+        for i in 0..numbers.len() {
+            for j in 0..numbers.len() - i - 1 {
+                if numbers[j] > numbers[j + 1] {
+                    numbers.swap(j, j + 1);
+                    T::swapped(&numbers[j], &numbers[j + 1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_bubble_sort() {
+        let mut numbers = vec![3, 1, 2];
+        let counts = count(&mut numbers, bubble);
+        assert_eq!(numbers, vec![1, 2, 3]);
+        // Passes of 2, 1 and 0 comparisons; 3 > 1 and 3 > 2 are swapped
+        assert_eq!(
+            counts,
+            Counts {
+                comparisons: 3,
+                swaps: 2,
+                writes: 0,
+                buffer_copies: 0,
+                max_depth: 0,
+                peak_heap_bytes: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_hooks_are_counted() {
+        let counts = count(&mut [5, 4], |slice| {
+            Counted::wrote(&slice[0]);
+            Counted::wrote(&slice[1]);
+            Counted::wrote(&slice[1]);
+            Counted::copied(7);
+            Counted::swapped(&slice[0], &slice[1]);
+            Counted::exchanged(&slice[0], &slice[1], true);
+            Counted::exchanged(&slice[0], &slice[1], false);
+            // The trace-only hooks count nothing
+            Counted::restored(&slice[0]);
+            Counted::merging(&slice[..1], &slice[1..]);
+            Counted::partitioned(slice, &slice[0]);
+            assert!(slice[0] > slice[1]);
+            assert!(slice[0] != slice[1]);
+            assert_eq!(slice[0].max(slice[1]).0, 5);
+        });
+        assert_eq!(
+            counts,
+            Counts {
+                comparisons: 3,
+                swaps: 2,
+                writes: 3,
+                buffer_copies: 7,
+                max_depth: 0,
+                peak_heap_bytes: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_counts_start_from_zero() {
+        let first = count(&mut [2, 1], |slice| slice.sort());
+        let second = count(&mut [2, 1], |slice| slice.sort());
+        assert_eq!(first, second);
+        assert_eq!(first.comparisons, 1);
+    }
+
+    #[test]
+    fn test_plain_integers_are_not_counted() {
+        let _guard = MEASURING.lock().unwrap_or_else(|e| e.into_inner());
+        Counts::take();
+        let mut numbers = vec![4, 2, 9, 1];
+        bubble(&mut numbers);
+        i32::wrote(&numbers[0]);
+        assert_eq!(numbers, vec![1, 2, 4, 9]);
+        assert_eq!(Counts::take(), Counts::default());
+    }
+
+    #[test]
+    fn test_summary() {
+        let counts = Counts {
+            comparisons: 12,
+            swaps: 3,
+            writes: 0,
+            buffer_copies: 40,
+            max_depth: 0,
+            peak_heap_bytes: None,
+        };
+        let summary = counts.summary("merge sort", 8);
+        assert!(summary.starts_with("stats: merge sort on 8 values\ncomparisons"));
+        assert!(summary.contains("\nswaps                         3\n"));
+        assert!(summary.ends_with("for reference: n log2 n = 24, n(n-1)/2 = 28\n"));
+        assert!(!summary.contains("recursion depth") && !summary.contains("heap"));
+
+        let counts = Counts {
+            max_depth: 4,
+            peak_heap_bytes: Some(0),
+            ..counts
+        };
+        let summary = counts.summary("merge sort", 8);
+        assert!(summary.contains(
+            "\nbuffer copies                40\n\
+             recursion depth               4\n\
+             peak heap bytes               0\n\
+             for reference"
+        ));
+    }
+
+    #[test]
+    fn test_deepest_call_is_counted() {
+        /// Recurses down to `depth` levels, then back up through shallower
+        /// calls that must not lower the maximum.
+        fn descend<T: Tally>(depth: usize, limit: usize) {
+            T::descended(depth);
+            if depth < limit {
+                descend::<T>(depth + 1, limit);
+                T::descended(depth);
+            }
+        }
+
+        let counts = count(&mut [1], |_| descend::<Counted>(1, 6));
+        assert_eq!(counts.max_depth, 6);
+        assert_eq!(counts.peak_heap_bytes, None);
+        assert_eq!(count(&mut [1], |_| {}).max_depth, 0);
+    }
+}
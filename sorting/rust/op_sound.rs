@@ -0,0 +1,386 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: opSound.rs
+
+   Description:
+      This module plays a sort as sound ("the sound of sorting"): every
+      array access becomes a short tone whose pitch rises with the value
+      accessed, from 120 Hz for the smallest input value to 1320 Hz for the
+      largest, and the tones are written as a mono 16-bit PCM WAV file at
+      44.1 kHz. The WAV format is written by hand: a RIFF header, a "fmt "
+      chunk describing the samples and a "data" chunk holding them.
+
+      The sort runs on `Sounded` values. Each comparison accesses both
+      values, and the swap, write and restore hooks of the `Tally` trait in
+      opCounter.rs, which the program must include next to this module,
+      report the values stored. Each access sounds for 20 ms, or less for
+      long sorts so that the whole sort lasts about a minute, but never
+      less than 1 ms. The phase of the wave carries over from tone to tone,
+      so pitch changes don't click.
+
+      The accesses are collected in memory before the file is written, so
+      inputs of a few hundred values suit this best.
+
+   Usage:
+      mod op_counter;
+      mod op_sound;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::op_counter::Tally;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::f64::consts::PI;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Samples per second.
+pub const SAMPLE_RATE: u32 = 44_100;
+
+/// The pitch of the smallest value, in Hz.
+const LOWEST_PITCH: f64 = 120.0;
+
+/// The pitch of the largest value, in Hz.
+const HIGHEST_PITCH: f64 = 1320.0;
+
+/// The length of a tone when the sort is short, in samples (20 ms).
+const LONGEST_TONE: usize = SAMPLE_RATE as usize / 50;
+
+/// The length of a tone however long the sort, in samples (1 ms).
+const SHORTEST_TONE: usize = SAMPLE_RATE as usize / 1000;
+
+/// The length the whole sort is squeezed into when possible, in seconds.
+const TARGET_SECONDS: usize = 60;
+
+/// The peak amplitude of a tone, leaving headroom below full scale.
+const AMPLITUDE: f64 = 0.3 * i16::MAX as f64;
+
+/// The length of the fade at the start and end of the sound, in samples.
+const FADE: usize = SAMPLE_RATE as usize / 200;
+
+/// The values accessed so far by the sort being played, or None when no
+/// sort is being played.
+static ACCESSES: Mutex<Option<Vec<i32>>> = Mutex::new(None);
+
+/// Held for the whole of a recording, so that concurrent recordings (such
+/// as tests running in parallel) don't hear each other's accesses.
+static RECORDING: Mutex<()> = Mutex::new(());
+
+/// Recognizes the `--sound=FILE` argument.
+///
+/// # Arguments
+/// * `arg` - One command-line argument
+///
+/// # Returns
+/// * `Option<String>` - The WAV file to write, or None if `arg` is not
+///   the sound flag
+pub fn from_arg(arg: &str) -> Option<String> {
+    arg.strip_prefix("--sound=").map(|path| path.to_string())
+}
+
+/// An i32 whose accesses are heard.
+#[derive(Clone, Copy, Debug)]
+pub struct Sounded(pub i32);
+
+impl PartialEq for Sounded {
+    fn eq(&self, other: &Sounded) -> bool {
+        accessed(self.0);
+        accessed(other.0);
+        self.0 == other.0
+    }
+}
+
+impl Eq for Sounded {}
+
+impl PartialOrd for Sounded {
+    fn partial_cmp(&self, other: &Sounded) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Sounded {
+    fn cmp(&self, other: &Sounded) -> Ordering {
+        accessed(self.0);
+        accessed(other.0);
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Tally for Sounded {
+    fn swapped(a: &Sounded, b: &Sounded) {
+        accessed(a.0);
+        accessed(b.0);
+    }
+
+    fn wrote(slot: &Sounded) {
+        accessed(slot.0);
+    }
+
+    fn restored(slot: &Sounded) {
+        accessed(slot.0);
+    }
+}
+
+/// Adds an access to the sort being played, if there is one.
+fn accessed(value: i32) {
+    let mut accesses = ACCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(accesses) = accesses.as_mut() {
+        accesses.push(value);
+    }
+}
+
+/// Sorts `numbers` with the given sort and writes the sound of its accesses.
+///
+/// The values are wrapped in `Sounded`, sorted, and unwrapped again, so
+/// `numbers` ends up sorted exactly as the sort would leave plain integers.
+///
+/// # Arguments
+/// * `numbers` - The values to sort
+/// * `writer` - Where the WAV file is written
+/// * `sort` - Sorts a slice of sounded values in place
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if the whole file was written
+///
+/// # Errors
+/// * Returns an error if writing fails or the sound is too long for a WAV
+///   file; the numbers are sorted even then
+pub fn record<W, F>(numbers: &mut [i32], writer: W, sort: F) -> io::Result<()>
+where
+    W: Write,
+    F: FnOnce(&mut [Sounded]),
+{
+    let mut sounded: Vec<Sounded> = numbers.iter().map(|&value| Sounded(value)).collect();
+
+    // A panic in another recording leaves nothing that needs repair
+    let accesses = {
+        let _guard = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+        *ACCESSES.lock().unwrap_or_else(|e| e.into_inner()) = Some(Vec::new());
+        sort(&mut sounded);
+        let accesses = ACCESSES.lock().unwrap_or_else(|e| e.into_inner()).take();
+        accesses.expect("the accesses are only removed here")
+    };
+
+    for (number, value) in numbers.iter_mut().zip(sounded) {
+        *number = value.0;
+    }
+
+    let min = numbers.iter().cloned().min().unwrap_or(0);
+    let max = numbers.iter().cloned().max().unwrap_or(0);
+    write_sound(writer, &accesses, min, max)
+}
+
+/// Returns the length of each tone for a sort with `accesses` accesses.
+///
+/// # Arguments
+/// * `accesses` - The number of array accesses
+///
+/// # Returns
+/// * `usize` - Samples per tone, from 1 ms to 20 ms
+pub fn tone_length(accesses: usize) -> usize {
+    let budget = TARGET_SECONDS * SAMPLE_RATE as usize / accesses.max(1);
+    budget.clamp(SHORTEST_TONE, LONGEST_TONE)
+}
+
+/// Returns the pitch of a value, rising linearly from the smallest input
+/// value to the largest.
+///
+/// # Arguments
+/// * `value` - The value accessed
+/// * `min` - The smallest input value
+/// * `max` - The largest input value
+///
+/// # Returns
+/// * `f64` - The frequency in Hz
+fn pitch(value: i32, min: i32, max: i32) -> f64 {
+    if max == min {
+        return LOWEST_PITCH;
+    }
+    let position = (f64::from(value) - f64::from(min)) / (f64::from(max) - f64::from(min));
+    LOWEST_PITCH + position.clamp(0.0, 1.0) * (HIGHEST_PITCH - LOWEST_PITCH)
+}
+
+/// Writes the header of a mono 16-bit PCM WAV file.
+///
+/// # Arguments
+/// * `writer` - Where the file is written
+/// * `samples` - The number of samples that will follow
+///
+/// # Errors
+/// * Returns an error if writing fails, or if the samples would not fit
+///   the 4 GiB a RIFF file can describe
+fn write_wav_header<W: Write>(writer: &mut W, samples: usize) -> io::Result<()> {
+    const CHANNELS: u16 = 1;
+    const BITS_PER_SAMPLE: u16 = 16;
+    const BLOCK_ALIGN: u16 = CHANNELS * BITS_PER_SAMPLE / 8;
+
+    let data_size = samples
+        .checked_mul(usize::from(BLOCK_ALIGN))
+        .and_then(|size| u32::try_from(size).ok())
+        .filter(|size| size.checked_add(36).is_some())
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "the sound is too long for a WAV file")
+        })?;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_size).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    // The format chunk: 16 bytes describing uncompressed PCM (format 1)
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?;
+    writer.write_all(&CHANNELS.to_le_bytes())?;
+    writer.write_all(&SAMPLE_RATE.to_le_bytes())?;
+    writer.write_all(&(SAMPLE_RATE * u32::from(BLOCK_ALIGN)).to_le_bytes())?;
+    writer.write_all(&BLOCK_ALIGN.to_le_bytes())?;
+    writer.write_all(&BITS_PER_SAMPLE.to_le_bytes())?;
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_size.to_le_bytes())
+}
+
+/// Writes a WAV file with one tone per access.
+///
+/// The samples are generated as they are written, so only the accesses
+/// are held in memory.
+///
+/// # Arguments
+/// * `writer` - Where the file is written
+/// * `accesses` - The values accessed, in order
+/// * `min` - The smallest input value, which sounds lowest
+/// * `max` - The largest input value, which sounds highest
+///
+/// # Errors
+/// * Returns an error if writing fails or the sound is too long
+fn write_sound<W: Write>(mut writer: W, accesses: &[i32], min: i32, max: i32) -> io::Result<()> {
+    let tone = tone_length(accesses.len());
+    let samples = accesses.len() * tone;
+    write_wav_header(&mut writer, samples)?;
+
+    let mut phase = 0.0f64;
+    let mut sample = 0;
+    let mut bytes = Vec::with_capacity(2 * tone);
+    for &value in accesses {
+        let step = 2.0 * PI * pitch(value, min, max) / f64::from(SAMPLE_RATE);
+        bytes.clear();
+        for _ in 0..tone {
+            // Fade in at the very start and out at the very end
+            let edge = sample.min(samples - 1 - sample);
+            let envelope = if edge < FADE { edge as f64 / FADE as f64 } else { 1.0 };
+            let level = (AMPLITUDE * envelope * phase.sin()).round() as i16;
+            bytes.extend_from_slice(&level.to_le_bytes());
+            phase = (phase + step) % (2.0 * PI);
+            sample += 1;
+        }
+        writer.write_all(&bytes)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bubble sort that reports its swaps.
+    fn bubble<T: Tally>(numbers: &mut [T]) {
+        for i in 0..numbers.len() {
+            for j in 0..numbers.len() - i - 1 {
+                if numbers[j] > numbers[j + 1] {
+                    numbers.swap(j, j + 1);
+                    T::swapped(&numbers[j], &numbers[j + 1]);
+                }
+            }
+        }
+    }
+
+    /// Reads the samples back from a WAV file written by `record`.
+    fn samples(wav: &[u8]) -> Vec<i16> {
+        wav[44..]
+            .chunks(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(from_arg("--sound=bubble.wav"), Some("bubble.wav".to_string()));
+        assert_eq!(from_arg("--sound"), None);
+        assert_eq!(from_arg("--trace=x"), None);
+    }
+
+    #[test]
+    fn test_wav_header() {
+        let mut header = Vec::new();
+        write_wav_header(&mut header, 100).unwrap();
+        assert_eq!(header.len(), 44);
+        assert_eq!(&header[0..4], b"RIFF");
+        assert_eq!(&header[4..8], &(36u32 + 200).to_le_bytes());
+        assert_eq!(&header[8..16], b"WAVEfmt ");
+        assert_eq!(&header[20..24], &[1, 0, 1, 0]);
+        assert_eq!(&header[24..28], &SAMPLE_RATE.to_le_bytes());
+        assert_eq!(&header[32..36], &[2, 0, 16, 0]);
+        assert_eq!(&header[36..40], b"data");
+        assert_eq!(&header[40..44], &200u32.to_le_bytes());
+
+        assert!(write_wav_header(&mut Vec::new(), usize::MAX / 2).is_err());
+        assert!(write_wav_header(&mut Vec::new(), 1 << 31).is_err());
+    }
+
+    #[test]
+    fn test_tone_length_and_pitch() {
+        assert_eq!(tone_length(0), LONGEST_TONE);
+        assert_eq!(tone_length(10), LONGEST_TONE);
+        assert_eq!(tone_length(TARGET_SECONDS * 100), SAMPLE_RATE as usize / 100);
+        assert_eq!(tone_length(usize::MAX), SHORTEST_TONE);
+
+        assert_eq!(pitch(-5, -5, 5), LOWEST_PITCH);
+        assert_eq!(pitch(0, -5, 5), (LOWEST_PITCH + HIGHEST_PITCH) / 2.0);
+        assert_eq!(pitch(5, -5, 5), HIGHEST_PITCH);
+        assert_eq!(pitch(3, 3, 3), LOWEST_PITCH);
+    }
+
+    #[test]
+    fn test_every_access_is_a_tone() {
+        // [2, 1]: one comparison and one swap, each accessing two values
+        let mut numbers = vec![2, 1];
+        let mut wav = Vec::new();
+        record(&mut numbers, &mut wav, bubble).unwrap();
+        assert_eq!(numbers, vec![1, 2]);
+        let samples = samples(&wav);
+        assert_eq!(samples.len(), 4 * LONGEST_TONE);
+
+        // The first access is of the largest value, the second of the
+        // smallest; count the cycles of each tone by its upward crossings
+        let cycles = |tone: &[i16]| tone.windows(2).filter(|w| w[0] < 0 && w[1] >= 0).count();
+        let seconds = LONGEST_TONE as f64 / f64::from(SAMPLE_RATE);
+        let high = cycles(&samples[..LONGEST_TONE]) as f64;
+        let low = cycles(&samples[LONGEST_TONE..2 * LONGEST_TONE]) as f64;
+        assert!((high - HIGHEST_PITCH * seconds).abs() <= 1.0, "{}", high);
+        assert!((low - LOWEST_PITCH * seconds).abs() <= 1.0, "{}", low);
+
+        // Faded in and out, and never louder than the amplitude
+        assert_eq!((samples[0], samples[samples.len() - 1]), (0, 0));
+        assert!(samples.iter().all(|&s| f64::from(s).abs() <= AMPLITUDE.ceil()));
+    }
+
+    #[test]
+    fn test_plain_integers_are_silent() {
+        let mut numbers = vec![4, 2, 9, 1];
+        let mut wav = Vec::new();
+        record(&mut numbers, &mut wav, |slice| {
+            let mut plain: Vec<i32> = slice.iter().map(|value| value.0).collect();
+            bubble(&mut plain);
+            for (slot, value) in slice.iter_mut().zip(plain) {
+                *slot = Sounded(value);
+            }
+        })
+        .unwrap();
+        assert_eq!(numbers, vec![1, 2, 4, 9]);
+        assert_eq!(wav.len(), 44);
+    }
+}
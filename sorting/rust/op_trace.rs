@@ -0,0 +1,784 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: opTrace.rs
+
+   Description:
+      This module records every operation of a sort as a trace in JSON Lines
+      format, one event per line, for visualizers and replay tools. The
+      sort runs on `Traced` values, whose comparison operators report each
+      comparison. The other events arrive through the hooks of the `Tally`
+      trait in opCounter.rs, which the program must include next to this
+      module.
+
+      Every event has a "step" number and an "op" name:
+
+        * start - {"algorithm", "values"}: the name and the input array,
+        * compare - {"i", "j", "a", "b"}: values a and b were compared,
+        * swap - {"i", "j", "a", "b"}: positions i and j were exchanged
+          and now hold a and b,
+        * write - {"i", "value"}: value was stored at position i,
+        * merge - {"left", "mid", "right"}: the runs left..=mid and
+          mid+1..=right are about to be merged,
+        * partition - {"low", "high", "pivot"}: low..=high was partitioned
+          around the pivot, now at its final position,
+        * end - the sort has finished.
+
+      Positions are found from the address of the element involved. An
+      element of the array being sorted, or of a merge buffer that mirrors
+      it, has a position. A value the sort holds in a local variable, such
+      as the key of insertion sort or a pivot copy, does not, and its index
+      is written as null.
+
+      Event::parse reads a line of a trace back, and Event::apply replays
+      the swaps and writes on a copy of the array, for tools that animate
+      or render a recorded sort.
+
+   Usage:
+      mod op_counter;
+      mod op_trace;
+      use op_trace::Target;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::op_counter::Tally;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::mem;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// The recorder of the trace in progress, or None when nothing is traced.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Held for the whole of a recording, so that concurrent recordings (such
+/// as tests running in parallel) don't write into each other's traces.
+static RECORDING: Mutex<()> = Mutex::new(());
+
+/// Where a trace is written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    /// Standard error, leaving standard output for the sorted numbers
+    Stderr,
+    /// A file, which is created or truncated
+    File(String),
+}
+
+impl Target {
+    /// Recognizes the `--trace` and `--trace=FILE` arguments.
+    ///
+    /// # Arguments
+    /// * `arg` - One command-line argument
+    ///
+    /// # Returns
+    /// * `Option<Target>` - The target, or None if `arg` is not a trace flag
+    pub fn from_arg(arg: &str) -> Option<Target> {
+        if arg == "--trace" {
+            Some(Target::Stderr)
+        } else {
+            arg.strip_prefix("--trace=")
+                .map(|path| Target::File(path.to_string()))
+        }
+    }
+
+    /// Opens the target for writing.
+    ///
+    /// # Returns
+    /// * `io::Result<Box<dyn Write + Send>>` - A buffered writer
+    ///
+    /// # Errors
+    /// * Returns an error if the file cannot be created
+    pub fn open(&self) -> io::Result<Box<dyn Write + Send>> {
+        match self {
+            Target::Stderr => Ok(Box::new(BufWriter::new(io::stderr()))),
+            Target::File(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        }
+    }
+}
+
+/// One event of a trace.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The sort is starting on `values`
+    Start { algorithm: String, values: Vec<i32> },
+    /// Values `a` and `b`, at positions `i` and `j`, were compared
+    Compare { i: Option<usize>, j: Option<usize>, a: i32, b: i32 },
+    /// Positions `i` and `j` were exchanged and now hold `a` and `b`
+    Swap { i: Option<usize>, j: Option<usize>, a: i32, b: i32 },
+    /// `value` was stored at position `i`
+    Write { i: Option<usize>, value: i32 },
+    /// The runs left..=mid and mid+1..=right are about to be merged
+    Merge { left: Option<usize>, mid: Option<usize>, right: Option<usize> },
+    /// low..=high was partitioned around the pivot at position `pivot`
+    Partition { low: Option<usize>, high: Option<usize>, pivot: Option<usize> },
+    /// The sort has finished
+    End,
+}
+
+impl Event {
+    /// Parses one line of a trace written by `record`.
+    ///
+    /// # Arguments
+    /// * `line` - A JSON object with an "op" field; "step" is ignored
+    ///
+    /// # Returns
+    /// * `Result<Event, String>` - The event
+    ///
+    /// # Errors
+    /// * Returns a message if the line is not an event of the format above
+    pub fn parse(line: &str) -> Result<Event, String> {
+        let fields = Fields::parse(line)?;
+        match fields.text("op")? {
+            "start" => Ok(Event::Start {
+                algorithm: fields.text("algorithm")?.to_string(),
+                values: fields.values("values")?,
+            }),
+            "compare" => Ok(Event::Compare {
+                i: fields.index("i")?,
+                j: fields.index("j")?,
+                a: fields.number("a")?,
+                b: fields.number("b")?,
+            }),
+            "swap" => Ok(Event::Swap {
+                i: fields.index("i")?,
+                j: fields.index("j")?,
+                a: fields.number("a")?,
+                b: fields.number("b")?,
+            }),
+            "write" => Ok(Event::Write {
+                i: fields.index("i")?,
+                value: fields.number("value")?,
+            }),
+            "merge" => Ok(Event::Merge {
+                left: fields.index("left")?,
+                mid: fields.index("mid")?,
+                right: fields.index("right")?,
+            }),
+            "partition" => Ok(Event::Partition {
+                low: fields.index("low")?,
+                high: fields.index("high")?,
+                pivot: fields.index("pivot")?,
+            }),
+            "end" => Ok(Event::End),
+            other => Err(format!("unknown op \"{}\"", other)),
+        }
+    }
+
+    /// Applies the event to a replayed copy of the array.
+    ///
+    /// A start event replaces the array with its values; swaps and writes
+    /// store their values at their positions. Positions that are null or
+    /// out of range are skipped, and the other events change nothing.
+    ///
+    /// # Arguments
+    /// * `array` - The array as it stood before the event
+    pub fn apply(&self, array: &mut Vec<i32>) {
+        let mut store = |index: Option<usize>, value: i32| {
+            if let Some(slot) = index.and_then(|index| array.get_mut(index)) {
+                *slot = value;
+            }
+        };
+        match *self {
+            Event::Start { ref values, .. } => *array = values.clone(),
+            Event::Swap { i, j, a, b } => {
+                store(i, a);
+                store(j, b);
+            }
+            Event::Write { i, value } => store(i, value),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    /// Writes the fields of the event after "step", without the braces.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Start { ref algorithm, ref values } => write!(
+                f,
+                "\"op\":\"start\",\"algorithm\":\"{}\",\"values\":[{}]",
+                algorithm,
+                Values(values)
+            ),
+            Event::Compare { i, j, a, b } => write!(
+                f,
+                "\"op\":\"compare\",\"i\":{},\"j\":{},\"a\":{},\"b\":{}",
+                Index(i),
+                Index(j),
+                a,
+                b
+            ),
+            Event::Swap { i, j, a, b } => write!(
+                f,
+                "\"op\":\"swap\",\"i\":{},\"j\":{},\"a\":{},\"b\":{}",
+                Index(i),
+                Index(j),
+                a,
+                b
+            ),
+            Event::Write { i, value } => {
+                write!(f, "\"op\":\"write\",\"i\":{},\"value\":{}", Index(i), value)
+            }
+            Event::Merge { left, mid, right } => write!(
+                f,
+                "\"op\":\"merge\",\"left\":{},\"mid\":{},\"right\":{}",
+                Index(left),
+                Index(mid),
+                Index(right)
+            ),
+            Event::Partition { low, high, pivot } => write!(
+                f,
+                "\"op\":\"partition\",\"low\":{},\"high\":{},\"pivot\":{}",
+                Index(low),
+                Index(high),
+                Index(pivot)
+            ),
+            Event::End => write!(f, "\"op\":\"end\""),
+        }
+    }
+}
+
+/// The fields of one flat JSON object, with their values as raw text.
+///
+/// Only what `record` writes is understood: string keys, and values that
+/// are numbers, null, strings without escapes, or arrays of numbers.
+struct Fields(Vec<(String, String)>);
+
+impl Fields {
+    /// Splits a line holding one JSON object into its fields.
+    fn parse(line: &str) -> Result<Fields, String> {
+        let body = line
+            .trim()
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .ok_or("an event must be a JSON object")?;
+        let mut fields = Vec::new();
+        let mut rest = body.trim_start();
+
+        while !rest.is_empty() {
+            let quoted = rest.strip_prefix('"').ok_or("expected a quoted key")?;
+            let close = quoted.find('"').ok_or("unterminated key")?;
+            let key = &quoted[..close];
+            let value = quoted[close + 1..]
+                .trim_start()
+                .strip_prefix(':')
+                .ok_or_else(|| format!("expected ':' after \"{}\"", key))?
+                .trim_start();
+
+            // Strings and arrays may hold commas, so they end at their close
+            let len = match value.chars().next() {
+                Some('"') => value[1..].find('"').map(|end| end + 2),
+                Some('[') => value.find(']').map(|end| end + 1),
+                _ => Some(value.find(',').unwrap_or(value.len())),
+            }
+            .ok_or_else(|| format!("unterminated value of \"{}\"", key))?;
+            fields.push((key.to_string(), value[..len].trim().to_string()));
+
+            rest = value[len..].trim_start();
+            if let Some(next) = rest.strip_prefix(',') {
+                rest = next.trim_start();
+            } else if !rest.is_empty() {
+                return Err(format!("expected ',' after \"{}\"", key));
+            }
+        }
+        Ok(Fields(fields))
+    }
+
+    /// Returns the raw text of a field.
+    fn raw(&self, key: &str) -> Result<&str, String> {
+        self.0
+            .iter()
+            .find(|field| field.0 == key)
+            .map(|field| field.1.as_str())
+            .ok_or_else(|| format!("missing field \"{}\"", key))
+    }
+
+    /// Returns a string field without its quotes.
+    fn text(&self, key: &str) -> Result<&str, String> {
+        let raw = self.raw(key)?;
+        raw.strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .ok_or_else(|| format!("\"{}\" must be a string", key))
+    }
+
+    /// Returns a numeric field.
+    fn number<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        let raw = self.raw(key)?;
+        raw.parse()
+            .map_err(|_| format!("\"{}\" must be a number, not {}", key, raw))
+    }
+
+    /// Returns a position, which is null when it is unknown.
+    fn index(&self, key: &str) -> Result<Option<usize>, String> {
+        if self.raw(key)? == "null" {
+            Ok(None)
+        } else {
+            self.number(key).map(Some)
+        }
+    }
+
+    /// Returns an array of integers.
+    fn values(&self, key: &str) -> Result<Vec<i32>, String> {
+        let raw = self.raw(key)?;
+        let inner = raw
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(|| format!("\"{}\" must be an array", key))?;
+        inner
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("\"{}\" holds {}, not an integer", key, value))
+            })
+            .collect()
+    }
+}
+
+/// An i32 whose comparisons, swaps and writes are traced.
+#[derive(Clone, Copy, Debug)]
+pub struct Traced(pub i32);
+
+impl PartialEq for Traced {
+    fn eq(&self, other: &Traced) -> bool {
+        compared(self, other);
+        self.0 == other.0
+    }
+}
+
+impl Eq for Traced {}
+
+impl PartialOrd for Traced {
+    fn partial_cmp(&self, other: &Traced) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Traced {
+    fn cmp(&self, other: &Traced) -> Ordering {
+        compared(self, other);
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Tally for Traced {
+    fn swapped(a: &Traced, b: &Traced) {
+        with_recorder(|recorder| {
+            let (i, j) = (recorder.position(a), recorder.position(b));
+            recorder.emit(&Event::Swap { i, j, a: a.0, b: b.0 });
+        });
+    }
+
+    fn wrote(slot: &Traced) {
+        written(slot);
+    }
+
+    fn restored(slot: &Traced) {
+        written(slot);
+    }
+
+    fn buffered(buffer: &[Traced]) {
+        with_recorder(|recorder| recorder.buffer = Some(Region::of(buffer)));
+    }
+
+    fn merging(left: &[Traced], right: &[Traced]) {
+        with_recorder(|recorder| {
+            let (left, mid) = recorder.bounds(left);
+            let (_, right) = recorder.bounds(right);
+            recorder.emit(&Event::Merge { left, mid, right });
+        });
+    }
+
+    fn partitioned(range: &[Traced], pivot: &Traced) {
+        with_recorder(|recorder| {
+            let (low, high) = recorder.bounds(range);
+            let pivot = recorder.position(pivot);
+            recorder.emit(&Event::Partition { low, high, pivot });
+        });
+    }
+}
+
+/// Emits the compare event for two traced values.
+fn compared(a: &Traced, b: &Traced) {
+    with_recorder(|recorder| {
+        let (i, j) = (recorder.position(a), recorder.position(b));
+        recorder.emit(&Event::Compare { i, j, a: a.0, b: b.0 });
+    });
+}
+
+/// Emits the write event for a value stored into the array.
+fn written(slot: &Traced) {
+    with_recorder(|recorder| {
+        let i = recorder.position(slot);
+        recorder.emit(&Event::Write { i, value: slot.0 });
+    });
+}
+
+/// Runs `f` on the recorder of the trace in progress, if there is one.
+fn with_recorder<F: FnOnce(&mut Recorder)>(f: F) {
+    let mut recorder = RECORDER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(recorder) = recorder.as_mut() {
+        f(recorder);
+    }
+}
+
+/// The memory occupied by a slice of traced values.
+#[derive(Clone, Copy)]
+struct Region {
+    /// The address of the first element
+    start: usize,
+    /// The number of elements
+    len: usize,
+}
+
+impl Region {
+    /// Returns the region occupied by `slice`.
+    fn of(slice: &[Traced]) -> Region {
+        Region {
+            start: slice.as_ptr() as usize,
+            len: slice.len(),
+        }
+    }
+
+    /// Returns the index of the element at `address`, if it lies in the region.
+    fn index_of(self, address: usize) -> Option<usize> {
+        let offset = address.checked_sub(self.start)? / mem::size_of::<Traced>();
+        if offset < self.len {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+}
+
+/// The state of a trace in progress.
+struct Recorder {
+    /// Where the events are written
+    writer: Box<dyn Write + Send>,
+    /// The slice being sorted
+    array: Region,
+    /// A buffer announced with `buffered`, which mirrors the array
+    buffer: Option<Region>,
+    /// The number of the next event
+    step: u64,
+    /// The first write error, after which nothing more is written
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    /// Returns the array position of an element of the array or of the
+    /// announced buffer, or None for a value held anywhere else.
+    fn position(&self, element: &Traced) -> Option<usize> {
+        let address = element as *const Traced as usize;
+        self.array
+            .index_of(address)
+            .or_else(|| self.buffer.and_then(|buffer| buffer.index_of(address)))
+    }
+
+    /// Returns the positions of the first and last elements of a range.
+    fn bounds(&self, range: &[Traced]) -> (Option<usize>, Option<usize>) {
+        match (range.first(), range.last()) {
+            (Some(first), Some(last)) => (self.position(first), self.position(last)),
+            _ => (None, None),
+        }
+    }
+
+    /// Writes one event line, numbering it with the next step.
+    fn emit(&mut self, event: &Event) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = writeln!(self.writer, "{{\"step\":{},{}}}", self.step, event) {
+            self.error = Some(e);
+        }
+        self.step += 1;
+    }
+}
+
+/// Formats a position as a JSON number, or null if it is unknown.
+struct Index(Option<usize>);
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(index) => write!(f, "{}", index),
+            None => write!(f, "null"),
+        }
+    }
+}
+
+/// Formats integers as the elements of a JSON array.
+struct Values<'a>(&'a [i32]);
+
+impl<'a> fmt::Display for Values<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sorts `numbers` with the given sort and writes a trace of its operations.
+///
+/// The values are wrapped in `Traced`, sorted, and unwrapped again, so
+/// `numbers` ends up sorted exactly as the sort would leave plain integers.
+///
+/// # Arguments
+/// * `numbers` - The values to sort
+/// * `algorithm` - The name recorded in the start event
+/// * `writer` - Where the events are written
+/// * `sort` - Sorts a slice of traced values in place
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if the whole trace was written
+///
+/// # Errors
+/// * Returns the first error from writing the trace; the numbers are
+///   sorted even then
+pub fn record<F>(
+    numbers: &mut [i32],
+    algorithm: &str,
+    writer: Box<dyn Write + Send>,
+    sort: F,
+) -> io::Result<()>
+where
+    F: FnOnce(&mut [Traced]),
+{
+    let mut traced: Vec<Traced> = numbers.iter().map(|&value| Traced(value)).collect();
+
+    // A panic in another recording leaves nothing that needs repair
+    let _guard = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+    let mut recorder = Recorder {
+        writer,
+        array: Region::of(&traced),
+        buffer: None,
+        step: 0,
+        error: None,
+    };
+    recorder.emit(&Event::Start {
+        algorithm: algorithm.to_string(),
+        values: numbers.to_vec(),
+    });
+
+    *RECORDER.lock().unwrap_or_else(|e| e.into_inner()) = Some(recorder);
+    sort(&mut traced);
+    let recorder = RECORDER.lock().unwrap_or_else(|e| e.into_inner()).take();
+    let mut recorder = recorder.expect("the recorder is only removed here");
+    recorder.emit(&Event::End);
+
+    for (number, value) in numbers.iter_mut().zip(traced) {
+        *number = value.0;
+    }
+
+    match recorder.error {
+        Some(e) => Err(e),
+        None => recorder.writer.flush(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// A writer that keeps its output where the test can read it.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Records a sort and returns the event lines.
+    fn lines<F: FnOnce(&mut [Traced])>(numbers: &mut [i32], sort: F) -> Vec<String> {
+        let output = Shared::default();
+        record(numbers, "test", Box::new(output.clone()), sort).unwrap();
+        let text = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        text.lines().map(|line| line.to_string()).collect()
+    }
+
+    /// A bubble sort that reports its swaps.
+    fn bubble<T: Tally>(numbers: &mut [T]) {
+        for i in 0..numbers.len() {
+            for j in 0..numbers.len() - i - 1 {
+                if numbers[j] > numbers[j + 1] {
+                    numbers.swap(j, j + 1);
+                    T::swapped(&numbers[j], &numbers[j + 1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Target::from_arg("--trace"), Some(Target::Stderr));
+        assert_eq!(
+            Target::from_arg("--trace=run.jsonl"),
+            Some(Target::File("run.jsonl".to_string()))
+        );
+        assert_eq!(Target::from_arg("--tracer"), None);
+        assert_eq!(Target::from_arg("--stats"), None);
+    }
+
+    #[test]
+    fn test_bubble_sort_trace() {
+        let mut numbers = vec![3, 1, 2];
+        let events = lines(&mut numbers, bubble);
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(
+            events,
+            vec![
+                r#"{"step":0,"op":"start","algorithm":"test","values":[3,1,2]}"#,
+                r#"{"step":1,"op":"compare","i":0,"j":1,"a":3,"b":1}"#,
+                r#"{"step":2,"op":"swap","i":0,"j":1,"a":1,"b":3}"#,
+                r#"{"step":3,"op":"compare","i":1,"j":2,"a":3,"b":2}"#,
+                r#"{"step":4,"op":"swap","i":1,"j":2,"a":2,"b":3}"#,
+                r#"{"step":5,"op":"compare","i":0,"j":1,"a":1,"b":2}"#,
+                r#"{"step":6,"op":"end"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_positions_of_buffers_and_locals() {
+        let mut numbers = vec![2, 1];
+        let events = lines(&mut numbers, |slice| {
+            let mut buffer = slice.to_vec();
+            Traced::buffered(&buffer);
+            Traced::merging(&slice[..1], &slice[1..]);
+
+            // Buffer elements stand for the array positions they mirror
+            let key = buffer[1];
+            assert!(buffer[0] > key);
+            slice[0] = buffer[1];
+            Traced::restored(&slice[0]);
+            slice[1] = buffer[0];
+            Traced::wrote(&slice[1]);
+            buffer.clear();
+
+            Traced::partitioned(slice, &slice[1]);
+        });
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(
+            &events[1..6],
+            &[
+                r#"{"step":1,"op":"merge","left":0,"mid":0,"right":1}"#,
+                r#"{"step":2,"op":"compare","i":0,"j":null,"a":2,"b":1}"#,
+                r#"{"step":3,"op":"write","i":0,"value":1}"#,
+                r#"{"step":4,"op":"write","i":1,"value":2}"#,
+                r#"{"step":5,"op":"partition","low":0,"high":1,"pivot":1}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_integers_are_not_traced() {
+        let mut numbers = vec![4, 2, 9, 1];
+        let events = lines(&mut numbers, |slice| {
+            let mut plain: Vec<i32> = slice.iter().map(|value| value.0).collect();
+            bubble(&mut plain);
+            for (slot, value) in slice.iter_mut().zip(plain) {
+                *slot = Traced(value);
+            }
+        });
+        assert_eq!(numbers, vec![1, 2, 4, 9]);
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_events_parse_what_is_written() {
+        let events = [
+            Event::Start {
+                algorithm: "quick sort".to_string(),
+                values: vec![3, -1, 2],
+            },
+            Event::Compare { i: Some(0), j: None, a: 3, b: -1 },
+            Event::Swap { i: Some(0), j: Some(1), a: -1, b: 3 },
+            Event::Write { i: Some(2), value: 7 },
+            Event::Merge { left: Some(0), mid: Some(1), right: Some(2) },
+            Event::Partition { low: Some(0), high: Some(2), pivot: None },
+            Event::End,
+        ];
+        for (step, event) in events.iter().enumerate() {
+            let line = format!("{{\"step\":{},{}}}", step, event);
+            assert_eq!(Event::parse(&line).as_ref(), Ok(event), "{}", line);
+        }
+        let empty = Event::parse(r#"{"op":"start","algorithm":"x","values":[]}"#);
+        assert_eq!(empty.unwrap(), Event::Start { algorithm: "x".to_string(), values: vec![] });
+    }
+
+    #[test]
+    fn test_malformed_events_are_rejected() {
+        let bad = [
+            "",
+            r#""op":"end""#,
+            r#"{"op":"shuffle"}"#,
+            r#"{"op":"write","i":0}"#,
+            r#"{"op":"write","i":-1,"value":3}"#,
+            r#"{"op":"write","i":0,"value":three}"#,
+            r#"{"op":"start","algorithm":"x","values":[1,2}"#,
+            r#"{"op" "end"}"#,
+            r#"{"op":"end" "step":1}"#,
+        ];
+        for line in &bad {
+            assert!(Event::parse(line).is_err(), "{}", line);
+        }
+        assert!(Event::parse(r#"{"op":"write","i":-1,"value":3}"#)
+            .unwrap_err()
+            .contains("\"i\""));
+    }
+
+    #[test]
+    fn test_replay_reaches_the_sorted_array() {
+        let mut numbers = vec![5, 3, 8, 1, 9, 2];
+        let mut array = Vec::new();
+        for line in lines(&mut numbers, bubble) {
+            Event::parse(&line).unwrap().apply(&mut array);
+        }
+        assert_eq!(array, numbers);
+
+        // Unknown or out of range positions are skipped
+        Event::Write { i: None, value: 0 }.apply(&mut array);
+        Event::Swap { i: Some(0), j: Some(99), a: 4, b: 4 }.apply(&mut array);
+        assert_eq!(array, vec![4, 2, 3, 5, 8, 9]);
+    }
+
+    /// A writer that fails on every write.
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _data: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_errors_are_reported() {
+        let mut numbers = vec![5, 4, 3];
+        let result = record(&mut numbers, "test", Box::new(Broken), bubble);
+        assert_eq!(result.unwrap_err().to_string(), "disk full");
+        assert_eq!(numbers, vec![3, 4, 5]);
+    }
+}
@@ -0,0 +1,591 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: patienceSort.rs
+
+   Description:
+      This program implements Patience Sort to sort a list of integers. It
+      reads integers from standard input (one per line), sorts them in
+      ascending order using Patience Sort, and prints the sorted result to
+      standard output (one integer per line). Designed for use with
+      input/output redirection and benchmarking.
+
+      Passing --lis prints a longest strictly increasing subsequence of the
+      input instead: its length on the first line, followed by its values
+      one per line. Passing --piles reports the number of piles to stderr,
+      which equals the length of that subsequence and measures how sorted
+      the input already is (n piles for sorted input, 1 for reverse).
+
+      Passing --stats prints the comparisons and buffer copies of the sort
+      to stderr. Each value is copied three times: onto a pile, into the
+      heap, and back into the array. The comparisons show the adaptivity:
+      reverse sorted input deals a single pile and needs only n - 1.
+
+      Passing --timings reports the time spent reading the input, dealing
+      and merging the piles, and printing the output to stderr (see
+      phaseTimer.rs). With --piles, finding the longest increasing
+      subsequence counts toward the sort. It times a plain sort, so it
+      cannot be used with --lis or with --stats.
+
+   Usage:
+      ./patienceSort < input.txt > output.txt
+      ./patienceSort --lis < input.txt > lis.txt
+      ./patienceSort --piles < input.txt > output.txt
+      ./patienceSort --stats < input.txt > output.txt
+      ./patienceSort --timings < input.txt > output.txt
+      cat input.txt | ./patienceSort
+*/
+
+mod op_counter;
+mod phase_timer;
+#[cfg(test)]
+mod xorshift;
+
+use op_counter::{count, Tally};
+use phase_timer::PhaseTimer;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Deals the numbers onto piles following the rules of patience solitaire.
+///
+/// Each card goes onto the leftmost pile whose top card is greater than or
+/// equal to it, or onto a new pile to the right if there is none. Every pile
+/// is therefore non-increasing from bottom to top, and the top cards are in
+/// strictly increasing order from left to right, which allows the target
+/// pile to be found with a binary search.
+///
+/// # Arguments
+/// * `numbers` - The cards to deal, in input order
+///
+/// # Returns
+/// * `Vec<Vec<T>>` - The piles, each stored bottom to top
+fn deal_piles<T: Tally>(numbers: &[T]) -> Vec<Vec<T>> {
+    let mut piles: Vec<Vec<T>> = Vec::new();
+
+    for &card in numbers {
+        // Find the leftmost pile whose top is >= card
+        let pile = piles.partition_point(|pile| pile[pile.len() - 1] < card);
+
+        if pile == piles.len() {
+            piles.push(vec![card]);
+        } else {
+            piles[pile].push(card);
+        }
+    }
+    T::copied(numbers.len());
+
+    piles
+}
+
+/// Sorts a slice of integers in ascending order using Patience Sort.
+///
+/// Patience Sort works in two phases:
+/// 1. **Deal**: Place the elements onto piles with `deal_piles`. Reading a
+///    pile from top to bottom gives an ascending run.
+/// 2. **Merge**: Keep the top card of every pile in a min-heap. Repeatedly
+///    remove the smallest card, write it out, and push the new top of the
+///    pile it came from.
+///
+/// # Time Complexity
+/// * Best case: O(n) when the array is reverse sorted (a single pile)
+/// * Average case: O(n log n)
+/// * Worst case: O(n log n)
+///
+/// With p piles, dealing costs O(n log p) and merging O(n log p).
+///
+/// # Space Complexity
+/// * O(n) - the piles hold a copy of every element
+///
+/// # Advantages
+/// * Adaptive to the number of increasing runs in the input
+/// * The pile count is the length of the longest increasing subsequence
+///
+/// # Disadvantages
+/// * Requires O(n) extra space
+/// * Sorted input creates n piles of one card, the most expensive merge
+/// * Not stable (relative order of equal elements may change)
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+pub fn patience_sort<T: Tally>(numbers: &mut [T]) {
+    let mut piles = deal_piles(numbers);
+
+    // Seed the heap with the top card of every pile
+    let mut heap: BinaryHeap<Reverse<(T, usize)>> = piles
+        .iter()
+        .enumerate()
+        .map(|(index, pile)| Reverse((pile[pile.len() - 1], index)))
+        .collect();
+    T::copied(heap.len());
+
+    // Repeatedly take the smallest top card and expose the next one
+    for slot in numbers.iter_mut() {
+        let Reverse((card, index)) = heap.pop().expect("piles hold every element");
+        *slot = card;
+        T::copied(1);
+
+        let pile = &mut piles[index];
+        pile.pop();
+        if let Some(&next) = pile.last() {
+            heap.push(Reverse((next, index)));
+            T::copied(1);
+        }
+    }
+}
+
+/// Finds a longest strictly increasing subsequence of the numbers.
+///
+/// Runs the same dealing process as `deal_piles`, but only tracks the index
+/// of each pile's top card. When a card is placed on pile k, it remembers
+/// the current top of pile k-1 as its predecessor. The number of piles is
+/// the length of the longest increasing subsequence, and following the
+/// predecessors back from the top of the last pile yields one such
+/// subsequence.
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Space Complexity
+/// * O(n) - one predecessor link per element
+///
+/// # Arguments
+/// * `numbers` - The input sequence
+///
+/// # Returns
+/// * `Vec<i32>` - A longest strictly increasing subsequence, in order
+fn longest_increasing_subsequence(numbers: &[i32]) -> Vec<i32> {
+    let mut tops: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; numbers.len()];
+
+    for (index, &card) in numbers.iter().enumerate() {
+        let pile = tops.partition_point(|&top| numbers[top] < card);
+
+        if pile > 0 {
+            previous[index] = Some(tops[pile - 1]);
+        }
+
+        if pile == tops.len() {
+            tops.push(index);
+        } else {
+            tops[pile] = index;
+        }
+    }
+
+    // Walk the predecessor links back from the top of the last pile
+    let mut subsequence = Vec::with_capacity(tops.len());
+    let mut current = tops.last().copied();
+    while let Some(index) = current {
+        subsequence.push(numbers[index]);
+        current = previous[index];
+    }
+
+    subsequence.reverse();
+    subsequence
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Prints the length of a subsequence followed by its values, one per line.
+///
+/// # Arguments
+/// * `subsequence` - The subsequence to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_lis(subsequence: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    writeln!(writer, "{}", subsequence.len())?;
+    for &num in subsequence {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Print a longest increasing subsequence instead of sorting
+    lis: bool,
+    /// Report the number of piles to stderr
+    piles: bool,
+    /// Report operation counts for the sort to stderr
+    stats: bool,
+    /// Report the time of each phase to stderr
+    timings: bool,
+}
+
+/// Parses the command-line arguments.
+///
+/// Recognizes `--lis`, `--piles`, `--stats` and `--timings`. `--stats`
+/// counts the sort and `--timings` times it, so neither can be combined
+/// with `--lis`, which prints no sorted output, or with the other.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        lis: false,
+        piles: false,
+        stats: false,
+        timings: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--lis" => options.lis = true,
+            "--piles" => options.piles = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    if options.stats && options.lis {
+        return Err("--stats cannot be combined with --lis".to_string());
+    }
+
+    if options.timings && (options.lis || options.stats) {
+        return Err("--timings cannot be combined with --lis or --stats".to_string());
+    }
+
+    Ok(options)
+}
+
+/// Main entry point for the patience sort program.
+///
+/// Orchestrates reading from stdin, sorting, and writing to stdout.
+/// Exits with status code 1 if any errors occur during execution.
+fn main() {
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // The pile count is the length of the longest increasing subsequence
+    let lis = if options.lis || options.piles {
+        longest_increasing_subsequence(&numbers)
+    } else {
+        Vec::new()
+    };
+
+    if options.piles {
+        eprintln!("piles: {}", lis.len());
+    }
+
+    let result = if options.lis {
+        print_lis(&lis)
+    } else {
+        // Sort the numbers using patience sort, counting operations if asked
+        if options.stats {
+            let counts = count(&mut numbers, patience_sort);
+            eprint!("{}", counts.summary("patience sort", numbers.len()));
+        } else {
+            patience_sort(&mut numbers);
+        }
+        timer.lap("sort");
+        print_output(&numbers)
+    };
+
+    // Report any error while writing to standard output
+    if let Err(e) = result {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("patience sort", numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xorshift::XorShift64;
+
+    #[test]
+    fn test_empty_array() {
+        let mut arr: Vec<i32> = vec![];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+    }
+
+    #[test]
+    fn test_single_element() {
+        let mut arr = vec![42];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_already_sorted() {
+        let mut arr = vec![1, 2, 3, 4, 5];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        let mut arr = vec![5, 4, 3, 2, 1];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_order() {
+        let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr = vec![5, 2, 8, 2, 9, 1, 5, 5];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 2, 5, 5, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let mut arr = vec![-3, 5, -1, 0, -9, 2];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![-9, -3, -1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let mut arr = vec![1000000, -1000000, 0, 999999, -999999];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
+    }
+
+    #[test]
+    fn test_two_elements_sorted() {
+        let mut arr = vec![1, 2];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_two_elements_unsorted() {
+        let mut arr = vec![2, 1];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7];
+        patience_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        patience_sort(&mut arr);
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = XorShift64::new(1949);
+        for len in 0..=300 {
+            let arr: Vec<i32> = rng.values(len, -32..32);
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut actual = arr;
+            patience_sort(&mut actual);
+            assert_eq!(actual, expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_deal_piles() {
+        let piles = deal_piles(&[4, 2, 6, 1, 5, 3, 3]);
+        assert_eq!(piles, vec![vec![4, 2, 1], vec![6, 5, 3, 3]]);
+
+        // Sorted input deals one pile per card, reverse input a single pile
+        assert_eq!(deal_piles(&[1, 2, 3]).len(), 3);
+        assert_eq!(deal_piles(&[3, 2, 1]).len(), 1);
+    }
+
+    /// Computes the LIS length with the O(n²) dynamic program.
+    fn brute_force_lis_length(numbers: &[i32]) -> usize {
+        let mut best = vec![1; numbers.len()];
+        for i in 0..numbers.len() {
+            for j in 0..i {
+                if numbers[j] < numbers[i] {
+                    best[i] = best[i].max(best[j] + 1);
+                }
+            }
+        }
+        best.into_iter().max().unwrap_or(0)
+    }
+
+    /// Checks that `sub` appears in `numbers` in order.
+    fn is_subsequence(sub: &[i32], numbers: &[i32]) -> bool {
+        let mut remaining = numbers.iter();
+        sub.iter().all(|x| remaining.any(|y| y == x))
+    }
+
+    #[test]
+    fn test_lis_against_brute_force() {
+        let mut rng = XorShift64::new(1961);
+        for len in 0..=80 {
+            let arr: Vec<i32> = rng.values(len, 0..30);
+            let lis = longest_increasing_subsequence(&arr);
+            assert_eq!(lis.len(), brute_force_lis_length(&arr), "input {:?}", arr);
+            assert!(lis.windows(2).all(|w| w[0] < w[1]), "not increasing: {:?}", lis);
+            assert!(is_subsequence(&lis, &arr), "not a subsequence: {:?}", lis);
+            assert_eq!(lis.len(), deal_piles(&arr).len());
+        }
+    }
+
+    #[test]
+    fn test_lis_examples() {
+        assert_eq!(longest_increasing_subsequence(&[]), vec![]);
+        assert_eq!(longest_increasing_subsequence(&[7, 7, 7]), vec![7]);
+        assert_eq!(longest_increasing_subsequence(&[3, 1, 2, 5, 4, 6]), vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options { lis: false, piles: false, stats: false, timings: false })
+        );
+        assert_eq!(
+            parse_args(&args(&["--lis", "--piles"])),
+            Ok(Options { lis: true, piles: true, stats: false, timings: false })
+        );
+        assert_eq!(
+            parse_args(&args(&["--piles", "--stats"])),
+            Ok(Options { lis: false, piles: true, stats: true, timings: false })
+        );
+        assert_eq!(
+            parse_args(&args(&["--timings", "--piles"])),
+            Ok(Options { lis: false, piles: true, stats: false, timings: true })
+        );
+        assert!(parse_args(&args(&["--timings", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--stats", "--lis"])).is_err());
+        assert!(parse_args(&args(&["--lis", "--timings"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_stats_follow_pile_count() {
+        let n: usize = 2048;
+
+        // Reverse input: one pile, one comparison per dealt card, and a
+        // heap that never holds more than one card
+        let mut reverse: Vec<i32> = (0..n as i32).rev().collect();
+        let counts = count(&mut reverse, patience_sort);
+        assert_eq!(reverse, (0..n as i32).collect::<Vec<i32>>());
+        assert_eq!(counts.comparisons, n as u64 - 1);
+        assert_eq!(counts.buffer_copies, 3 * n as u64);
+
+        // Sorted input: n piles, so both phases pay about log2 n per card
+        let mut sorted: Vec<i32> = (0..n as i32).collect();
+        let counts = count(&mut sorted, patience_sort);
+        assert_eq!(sorted, (0..n as i32).collect::<Vec<i32>>());
+        assert!(counts.comparisons > (n * 11) as u64, "{:?}", counts);
+        assert_eq!(counts.buffer_copies, 3 * n as u64);
+        assert_eq!((counts.swaps, counts.writes), (0, 0));
+    }
+}
@@ -0,0 +1,128 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: phaseTimer.rs
+
+   Description:
+      This module times the phases of a sorting program (reading the input,
+      sorting it and printing it) for --timings. Timing the whole process
+      from outside mostly measures parsing and printing once the input
+      runs to a million lines, so the programs report each phase on its own
+      and the sort itself can be compared across languages.
+
+      Phases are timed with `Instant`, a monotonic clock, so a change of
+      the system time while the program runs cannot distort them. Each lap
+      ends the current phase and starts the next one.
+
+   Usage:
+      mod phase_timer;
+      use phase_timer::PhaseTimer;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+/// The durations of the phases completed so far.
+pub struct PhaseTimer {
+    /// When the current phase began
+    started: Instant,
+    /// The completed phases in order, with how long each took
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl PhaseTimer {
+    /// Starts timing the first phase.
+    ///
+    /// # Returns
+    /// * `PhaseTimer` - A timer with no completed phases
+    pub fn start() -> PhaseTimer {
+        PhaseTimer {
+            started: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    /// Ends the current phase under the given name and starts the next.
+    ///
+    /// # Arguments
+    /// * `phase` - The name of the phase that just ended
+    pub fn lap(&mut self, phase: &'static str) {
+        let now = Instant::now();
+        self.phases.push((phase, now - self.started));
+        self.started = now;
+    }
+
+    /// Returns the completed phases in order.
+    ///
+    /// # Returns
+    /// * `&[(&'static str, Duration)]` - Each phase's name and duration
+    pub fn phases(&self) -> &[(&'static str, Duration)] {
+        &self.phases
+    }
+
+    /// Formats the phases for printing, in seconds, followed by their total.
+    ///
+    /// # Arguments
+    /// * `algorithm` - The name of the algorithm that was timed
+    /// * `len` - The number of values sorted
+    ///
+    /// # Returns
+    /// * `String` - A multi-line summary ending in a newline
+    pub fn summary(&self, algorithm: &str, len: usize) -> String {
+        let mut summary = format!("timings: {} on {} values\n", algorithm, len);
+        for &(phase, duration) in &self.phases {
+            summary += &format!("{:<15} {:>13.6} s\n", phase, duration.as_secs_f64());
+        }
+        let total: Duration = self.phases.iter().map(|&(_, duration)| duration).sum();
+        summary + &format!("{:<15} {:>13.6} s\n", "total", total.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_laps_split_the_time() {
+        let begun = Instant::now();
+        let mut timer = PhaseTimer::start();
+        thread::sleep(Duration::from_millis(20));
+        timer.lap("read_input");
+        timer.lap("sort");
+        thread::sleep(Duration::from_millis(10));
+        timer.lap("print_output");
+
+        let names: Vec<&str> = timer.phases().iter().map(|&(name, _)| name).collect();
+        assert_eq!(names, vec!["read_input", "sort", "print_output"]);
+        let durations: Vec<Duration> = timer.phases().iter().map(|&(_, d)| d).collect();
+        assert!(durations[0] >= Duration::from_millis(20));
+        assert!(durations[2] >= Duration::from_millis(10));
+
+        // Consecutive laps don't overlap
+        assert!(durations.iter().sum::<Duration>() <= begun.elapsed());
+    }
+
+    #[test]
+    fn test_summary() {
+        let timer = PhaseTimer {
+            started: Instant::now(),
+            phases: vec![
+                ("read_input", Duration::from_micros(250_000)),
+                ("sort", Duration::from_micros(1_500)),
+                ("print_output", Duration::from_micros(100_000)),
+            ],
+        };
+        assert_eq!(
+            timer.summary("merge sort", 1000),
+            "timings: merge sort on 1000 values\n\
+             read_input           0.250000 s\n\
+             sort                 0.001500 s\n\
+             print_output         0.100000 s\n\
+             total                0.351500 s\n"
+        );
+    }
+}
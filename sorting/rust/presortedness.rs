@@ -0,0 +1,381 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: presortedness.rs
+
+   Description:
+      This module measures how far an input is from sorted with the standard
+      measures of disorder from the adaptive sorting literature:
+
+        * Inv - the number of pairs that are out of order,
+        * Runs - the number of maximal non-decreasing runs,
+        * Rem - the fewest values to remove to leave a sorted sequence,
+        * Osc - the total number of times the sequence jumps across each
+          value (Levcopoulos and Petersson's oscillation),
+        * Max - the farthest any value sits from its sorted position,
+        * and the number of distinct values.
+
+      Each is computed in O(n log n) time. Inversions are counted by the
+      merge sort pass of countingMerge.rs, the same one mergeSort.rs runs
+      for --count-inversions; the program must include that module and
+      opCounter.rs next to this one.
+
+   Usage:
+      mod counting_merge;
+      mod op_counter;
+      mod presortedness;
+      use presortedness::Metrics;
+*/
+
+// Programs that include this module don't necessarily use every function
+#![allow(dead_code)]
+
+use super::counting_merge::count_inversions;
+use std::io::{self, Write};
+
+/// The measures of disorder of one input.
+#[derive(Debug, PartialEq)]
+pub struct Metrics {
+    /// The number of values
+    pub len: usize,
+    /// The number of pairs i < j with a[i] > a[j]
+    pub inversions: u64,
+    /// The number of maximal non-decreasing runs (0 for empty input)
+    pub runs: usize,
+    /// The number of values outside a longest non-decreasing subsequence
+    pub rem: usize,
+    /// The number of (value, adjacent pair) combinations where the pair
+    /// straddles the value strictly
+    pub osc: u64,
+    /// The largest distance between a value's position and its position
+    /// after a stable sort
+    pub max_displacement: usize,
+    /// The number of distinct values
+    pub distinct: usize,
+}
+
+impl Metrics {
+    /// Computes every measure for an input.
+    ///
+    /// # Time Complexity
+    /// * O(n log n)
+    ///
+    /// # Space Complexity
+    /// * O(n)
+    ///
+    /// # Arguments
+    /// * `numbers` - The input to measure
+    ///
+    /// # Returns
+    /// * `Metrics` - The measures
+    pub fn measure(numbers: &[i32]) -> Metrics {
+        let mut sorted = numbers.to_vec();
+        sorted.sort_unstable();
+
+        Metrics {
+            len: numbers.len(),
+            inversions: inversions(numbers),
+            runs: runs(numbers),
+            rem: rem(numbers),
+            osc: osc(numbers, &sorted),
+            max_displacement: max_displacement(numbers),
+            distinct: distinct(&sorted),
+        }
+    }
+
+    /// Returns the inversions as a share of all pairs: 0 for sorted input,
+    /// about 0.5 for random input and 1 for strictly decreasing input.
+    pub fn inversion_ratio(&self) -> f64 {
+        let pairs = self.len as u64 * self.len.saturating_sub(1) as u64 / 2;
+        if pairs == 0 {
+            0.0
+        } else {
+            self.inversions as f64 / pairs as f64
+        }
+    }
+
+    /// Writes the measures as a two-column report.
+    ///
+    /// # Arguments
+    /// * `writer` - Where the report is written
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - Ok if successful, Err if writing fails
+    pub fn report<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{:<12} {}", "length", self.len)?;
+        writeln!(
+            writer,
+            "{:<12} {} ({:.4}% of pairs)",
+            "inversions",
+            self.inversions,
+            100.0 * self.inversion_ratio()
+        )?;
+        writeln!(writer, "{:<12} {}", "runs", self.runs)?;
+        writeln!(writer, "{:<12} {}", "rem", self.rem)?;
+        writeln!(writer, "{:<12} {}", "osc", self.osc)?;
+        writeln!(writer, "{:<12} {}", "max", self.max_displacement)?;
+        writeln!(writer, "{:<12} {}", "distinct", self.distinct)?;
+        writer.flush()
+    }
+}
+
+/// Counts the pairs i < j with a[i] > a[j].
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Space Complexity
+/// * O(n) - a working copy and a merge buffer
+///
+/// # Arguments
+/// * `numbers` - The input
+///
+/// # Returns
+/// * `u64` - The number of inversions, up to n(n-1)/2
+pub fn inversions(numbers: &[i32]) -> u64 {
+    count_inversions(numbers)
+}
+
+/// Counts the maximal non-decreasing runs.
+///
+/// # Arguments
+/// * `numbers` - The input
+///
+/// # Returns
+/// * `usize` - One more than the number of descents, or 0 for empty input
+pub fn runs(numbers: &[i32]) -> usize {
+    if numbers.is_empty() {
+        return 0;
+    }
+    1 + numbers.windows(2).filter(|pair| pair[1] < pair[0]).count()
+}
+
+/// Counts the values that must be removed to leave a sorted sequence.
+///
+/// Finds the longest non-decreasing subsequence by patience sorting: the
+/// smallest possible tail of a subsequence of each length is kept in a
+/// sorted array, and each value extends the longest one it can.
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Arguments
+/// * `numbers` - The input
+///
+/// # Returns
+/// * `usize` - n minus the length of a longest non-decreasing subsequence
+pub fn rem(numbers: &[i32]) -> usize {
+    let mut tails: Vec<i32> = Vec::new();
+    for &value in numbers {
+        // Equal values may extend a subsequence, so search past them
+        let position = tails.partition_point(|&tail| tail <= value);
+        if position == tails.len() {
+            tails.push(value);
+        } else {
+            tails[position] = value;
+        }
+    }
+    numbers.len() - tails.len()
+}
+
+/// Computes the oscillation: for every value, the number of adjacent pairs
+/// whose values lie strictly on either side of it, summed over all values.
+///
+/// Each adjacent pair (lo, hi) straddles exactly the values in the open
+/// interval between them, which two binary searches in the sorted input
+/// count.
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Arguments
+/// * `numbers` - The input
+/// * `sorted` - The same values in ascending order
+///
+/// # Returns
+/// * `u64` - The oscillation
+pub fn osc(numbers: &[i32], sorted: &[i32]) -> u64 {
+    numbers
+        .windows(2)
+        .map(|pair| {
+            let (lo, hi) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+            let above_lo = sorted.partition_point(|&x| x <= lo);
+            let below_hi = sorted.partition_point(|&x| x < hi);
+            below_hi.saturating_sub(above_lo) as u64
+        })
+        .sum()
+}
+
+/// Finds the largest distance any value must move to reach its sorted
+/// position.
+///
+/// Equal values are given their positions in input order, as a stable sort
+/// would, so repeated values don't count as displaced.
+///
+/// # Time Complexity
+/// * O(n log n)
+///
+/// # Arguments
+/// * `numbers` - The input
+///
+/// # Returns
+/// * `usize` - The largest |i - sorted position of a[i]|
+pub fn max_displacement(numbers: &[i32]) -> usize {
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    order.sort_by_key(|&i| numbers[i]);
+    order
+        .iter()
+        .enumerate()
+        .map(|(position, &original)| position.abs_diff(original))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Counts the distinct values.
+///
+/// # Arguments
+/// * `sorted` - The input in ascending order
+///
+/// # Returns
+/// * `usize` - The number of distinct values
+pub fn distinct(sorted: &[i32]) -> usize {
+    if sorted.is_empty() {
+        return 0;
+    }
+    1 + sorted.windows(2).filter(|pair| pair[0] != pair[1]).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::xorshift::XorShift64;
+    use super::*;
+
+    /// Counts inversions straight from the definition.
+    fn brute_inversions(a: &[i32]) -> u64 {
+        let mut count = 0;
+        for i in 0..a.len() {
+            count += a[i + 1..].iter().filter(|&&b| b < a[i]).count() as u64;
+        }
+        count
+    }
+
+    /// Computes the oscillation straight from the definition.
+    fn brute_osc(a: &[i32]) -> u64 {
+        let mut count = 0;
+        for &x in a {
+            for pair in a.windows(2) {
+                if pair[0].min(pair[1]) < x && x < pair[0].max(pair[1]) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Finds the longest non-decreasing subsequence by dynamic programming.
+    fn brute_rem(a: &[i32]) -> usize {
+        let mut best = vec![1; a.len()];
+        for j in 0..a.len() {
+            for i in 0..j {
+                if a[i] <= a[j] {
+                    best[j] = best[j].max(best[i] + 1);
+                }
+            }
+        }
+        a.len() - best.iter().copied().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_sorted_input() {
+        let sorted: Vec<i32> = (0..100).collect();
+        assert_eq!(
+            Metrics::measure(&sorted),
+            Metrics {
+                len: 100,
+                inversions: 0,
+                runs: 1,
+                rem: 0,
+                osc: 0,
+                max_displacement: 0,
+                distinct: 100,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reverse_input() {
+        let reverse: Vec<i32> = (0..100).rev().collect();
+        let metrics = Metrics::measure(&reverse);
+        assert_eq!(metrics.inversions, 4950);
+        assert_eq!(metrics.inversion_ratio(), 1.0);
+        assert_eq!(metrics.runs, 100);
+        assert_eq!(metrics.rem, 99);
+        assert_eq!(metrics.max_displacement, 99);
+        // Neighbors are consecutive, so no pair straddles another value
+        assert_eq!(metrics.osc, 0);
+    }
+
+    #[test]
+    fn test_small_example() {
+        // Descents at 5 -> 1 and 4 -> 2; the pairs (5, 1) and (1, 4) each
+        // straddle several values
+        let metrics = Metrics::measure(&[3, 5, 1, 4, 2, 2]);
+        assert_eq!(metrics.inversions, 9);
+        assert_eq!(metrics.runs, 3);
+        assert_eq!(metrics.rem, 3);
+        assert_eq!(metrics.osc, 9);
+        assert_eq!(metrics.max_displacement, 4);
+        assert_eq!(metrics.distinct, 5);
+    }
+
+    #[test]
+    fn test_empty_and_single() {
+        for input in [vec![], vec![7]].iter() {
+            let metrics = Metrics::measure(input);
+            assert_eq!(metrics.inversions, 0);
+            assert_eq!(metrics.runs, input.len());
+            assert_eq!(metrics.rem, 0);
+            assert_eq!(metrics.osc, 0);
+            assert_eq!(metrics.max_displacement, 0);
+            assert_eq!(metrics.distinct, input.len());
+            assert_eq!(metrics.inversion_ratio(), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_equal_values_are_not_disorder() {
+        let metrics = Metrics::measure(&[4; 50]);
+        assert_eq!((metrics.inversions, metrics.runs, metrics.rem), (0, 1, 0));
+        assert_eq!((metrics.osc, metrics.max_displacement, metrics.distinct), (0, 0, 1));
+    }
+
+    #[test]
+    fn test_matches_definitions_on_random_inputs() {
+        for (seed, &bound) in [5, 50, 1000].iter().enumerate() {
+            for len in [2, 3, 10, 57, 200].iter() {
+                let input = XorShift64::new(seed as u64 + 1).values(*len, 0..bound);
+                let metrics = Metrics::measure(&input);
+                assert_eq!(metrics.inversions, brute_inversions(&input), "{:?}", input);
+                assert_eq!(metrics.osc, brute_osc(&input), "{:?}", input);
+                assert_eq!(metrics.rem, brute_rem(&input), "{:?}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inversions_do_not_overflow_u32() {
+        // 100000 reversed values have about 5 * 10^9 inversions
+        let reverse: Vec<i32> = (0..100_000).rev().collect();
+        assert_eq!(inversions(&reverse), 100_000 * 99_999 / 2);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut output = Vec::new();
+        Metrics::measure(&[2, 1, 3]).report(&mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.starts_with("length       3\ninversions   1 (33.3333% of pairs)\n"));
+        assert!(text.ends_with("max          1\ndistinct     3\n"));
+    }
+}
@@ -12,13 +12,366 @@
       per line). Designed for use with input/output redirection and
       benchmarking.
 
+      Passing --network stops the recursion at subarrays of up to 16
+      elements and sorts them with an optimal sorting network instead.
+      Passing --cutoff N hands subarrays shorter than N elements to
+      insertion sort. Passing --tune times a sweep of cutoffs on the input
+      and reports the fastest one instead of printing the sorted numbers.
+
+      Passing --select K, --median or --percentile P prints order statistics
+      instead of the sorted numbers, one per line in the order given. They
+      are found with introselect, which runs in O(n) without sorting the
+      whole input. K counts from 1 (the minimum), the median is the lower
+      median, and percentiles use the nearest-rank method. The flags may be
+      combined and repeated.
+
+      Passing --pivot last|median3|mom|random chooses how the pivot is
+      picked: the last element (the default), the median of the first,
+      middle and last elements, the median of medians, which guarantees
+      O(n log n) on any input, or a uniformly random element. Random pivots
+      come from a built-in xorshift generator, and --seed N fixes its seed so
+      every run is reproducible. Passing --compare-pivots times every
+      strategy on the input and reports their cost relative to the
+      last-element pivot.
+
+      Passing --partition lomuto|block chooses the partition scheme. The
+      block scheme (BlockQuicksort) scans the range in blocks, records the
+      offsets of misplaced elements without branching on the comparisons,
+      and then swaps them in a batch, which avoids the branch mispredictions
+      that dominate Lomuto partitioning on random data. Passing
+      --compare-partitions times every scheme on the input.
+
+      Passing --simd sorts subarrays of up to 16 elements with a sorting
+      network in vector registers, and --partition simd compares a whole
+      vector of elements with the pivot at a time. Both use AVX2 or SSE4.1
+      when the processor supports them and fall back to scalar code
+      otherwise.
+
+      Passing --indices prints the original line number of each value in
+      sorted order instead of the values, for joining the result back to
+      other columns of the input. Quick sort is not stable, so lines with
+      equal values may come out in any order.
+
+      Passing --stats sorts as usual and prints the number of comparisons,
+      swaps and element writes to standard error, so the quadratic worst
+      case of the last-element pivot on sorted input can be seen next to
+      the n log n of the other strategies. The vector kernels compare
+      elements outside the scalar code, so --stats cannot be used with
+      --simd or --partition simd. The deepest recursion is reported too,
+      which stays near log2 n for balanced partitions and approaches n in
+      the worst case, along with the most heap memory the sort held at
+      once (see heapCounter.rs), which is none: quick sort works in place.
+
+      Passing --trace writes the sort's comparisons, swaps and writes to
+      stderr as JSON Lines (see opTrace.rs), or to FILE with --trace=FILE,
+      with a partition event giving the range and the final position of
+      the pivot after each partition. Like --stats, it needs the scalar
+      code.
+
+      Passing --sound=FILE writes the sound of the sort to FILE as a WAV
+      file (see opSound.rs), with a tone for each value compared, swapped
+      or written. Each partition is heard as the pivot's pitch alternating
+      with the values scanned against it, and the ranges shrink until only
+      short chirps are left. It needs the scalar code too.
+
+      Passing --timings times reading the input, sorting and printing the
+      output separately and reports them to stderr (see phaseTimer.rs),
+      which keeps the I/O out of comparisons between pivot strategies and
+      partition schemes on large inputs. It cannot be combined with the
+      other modes, nor with --stats, --trace or --sound, whose recording
+      would be timed along with the sort.
+
    Usage:
       ./quickSort < input.txt > output.txt
+      ./quickSort --network < input.txt > output.txt
+      ./quickSort --cutoff 24 < input.txt > output.txt
+      ./quickSort --tune < input.txt
+      ./quickSort --select 10 --median --percentile 99 < input.txt
+      ./quickSort --pivot mom < input.txt > output.txt
+      ./quickSort --pivot random --seed 42 < input.txt > output.txt
+      ./quickSort --compare-pivots < input.txt
+      ./quickSort --partition block < input.txt > output.txt
+      ./quickSort --compare-partitions < input.txt
+      ./quickSort --simd --partition simd < input.txt > output.txt
+      ./quickSort --indices < input.txt > order.txt
+      ./quickSort --pivot median3 --stats < input.txt > output.txt
+      ./quickSort --partition block --trace=trace.jsonl < input.txt > output.txt
+      ./quickSort --pivot median3 --sound=quick.wav < input.txt > output.txt
+      ./quickSort --simd --partition simd --timings < input.txt > output.txt
       cat input.txt | ./quickSort
 */
 
+mod argsort;
+mod heap_counter;
+mod insertion;
+mod op_counter;
+mod op_sound;
+mod op_trace;
+mod phase_timer;
+mod simd_kernels;
+mod sorting_network;
+mod three_way_partition;
+mod xorshift;
+
+use argsort::{argsort_with, print_line_numbers, read_input_with_lines, Tagged};
+use heap_counter::peak_heap;
+use insertion::insertion_sort;
+use op_counter::{count, Counted, Tally};
+use op_sound::Sounded;
+use op_trace::{Target, Traced};
+use phase_timer::PhaseTimer;
+use simd_kernels::{Kernel, SIMD_NETWORK_SIZE};
+use sorting_network::{network_sort, MAX_NETWORK_SIZE};
+use std::env;
+use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::process;
+use std::time::{Duration, Instant};
+use three_way_partition::partition_three_way;
+use xorshift::{XorShift64, DEFAULT_SEED};
+
+/// Strategy for sorting small subarrays at the bottom of the recursion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BaseCase {
+    /// Recurse all the way down to single elements
+    Single,
+    /// Sort subarrays of up to `MAX_NETWORK_SIZE` elements with a sorting network
+    Network,
+    /// Sort subarrays shorter than the given cutoff with insertion sort
+    Insertion(usize),
+    /// Sort subarrays of up to `SIMD_NETWORK_SIZE` elements with a vectorized network
+    Simd,
+}
+
+impl BaseCase {
+    /// Sorts a subarray directly if it is small enough for this base case.
+    ///
+    /// # Arguments
+    /// * `slice` - The subarray about to be divided further
+    ///
+    /// # Returns
+    /// * `bool` - true if the subarray was sorted, false if the caller
+    ///   should keep recursing
+    fn sort_small<T: Element>(self, slice: &mut [T]) -> bool {
+        match self {
+            BaseCase::Network if slice.len() <= MAX_NETWORK_SIZE => network_sort(slice),
+            BaseCase::Insertion(cutoff) if slice.len() < cutoff => insertion_sort(slice),
+            BaseCase::Simd if slice.len() <= SIMD_NETWORK_SIZE => T::simd_sort_small(slice),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// A type of value quick sort can sort.
+///
+/// The vectorized kernels only handle i32. Other types, such as the tagged
+/// values sorted by `argsort`, use scalar code for `--simd` and
+/// `--partition simd` instead.
+trait Element: Tally {
+    /// Sorts a slice of up to `SIMD_NETWORK_SIZE` values with a network.
+    fn simd_sort_small(slice: &mut [Self]);
+
+    /// Partitions a slice around its last element and returns the pivot's
+    /// final index.
+    fn simd_partition(slice: &mut [Self]) -> usize;
+}
+
+impl Element for i32 {
+    fn simd_sort_small(slice: &mut [i32]) {
+        Kernel::detect().sort_small(slice);
+    }
+
+    fn simd_partition(slice: &mut [i32]) -> usize {
+        Kernel::detect().partition(slice)
+    }
+}
+
+impl Element for Tagged {
+    fn simd_sort_small(slice: &mut [Tagged]) {
+        network_sort(slice);
+    }
+
+    fn simd_partition(slice: &mut [Tagged]) -> usize {
+        block_partition(slice, 0, slice.len() as isize - 1) as usize
+    }
+}
+
+// Tagged values are sorted for --indices, where nothing is counted
+impl Tally for Tagged {}
+
+// --stats, --trace and --sound reject the vector kernels, but counted,
+// traced and sounded values still need a scalar fallback to satisfy the trait
+impl Element for Counted {
+    fn simd_sort_small(slice: &mut [Counted]) {
+        network_sort(slice);
+    }
+
+    fn simd_partition(slice: &mut [Counted]) -> usize {
+        block_partition(slice, 0, slice.len() as isize - 1) as usize
+    }
+}
+
+impl Element for Traced {
+    fn simd_sort_small(slice: &mut [Traced]) {
+        network_sort(slice);
+    }
+
+    fn simd_partition(slice: &mut [Traced]) -> usize {
+        block_partition(slice, 0, slice.len() as isize - 1) as usize
+    }
+}
+
+impl Element for Sounded {
+    fn simd_sort_small(slice: &mut [Sounded]) {
+        network_sort(slice);
+    }
+
+    fn simd_partition(slice: &mut [Sounded]) -> usize {
+        block_partition(slice, 0, slice.len() as isize - 1) as usize
+    }
+}
+
+/// How quick sort chooses its pivot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pivot {
+    /// The last element of the range (the original Lomuto choice)
+    Last,
+    /// The median of the first, middle and last elements
+    MedianOfThree,
+    /// The median of medians of groups of five (BFPRT)
+    MedianOfMedians,
+    /// A uniformly random element, drawn from a generator with this seed
+    Random(u64),
+}
+
+impl Pivot {
+    /// Every strategy, in the order `--compare-pivots` reports them.
+    const ALL: [Pivot; 4] = [
+        Pivot::Last,
+        Pivot::MedianOfThree,
+        Pivot::MedianOfMedians,
+        Pivot::Random(DEFAULT_SEED),
+    ];
+
+    /// Returns the name used for this strategy on the command line.
+    fn name(self) -> &'static str {
+        match self {
+            Pivot::Last => "last",
+            Pivot::MedianOfThree => "median3",
+            Pivot::MedianOfMedians => "mom",
+            Pivot::Random(_) => "random",
+        }
+    }
+
+    /// Looks up a strategy by its command-line name.
+    fn from_name(name: &str) -> Option<Pivot> {
+        Pivot::ALL.iter().copied().find(|pivot| pivot.name() == name)
+    }
+}
+
+/// How quick sort rearranges a range around its pivot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Partition {
+    /// One left-to-right scan that branches on every comparison
+    Lomuto,
+    /// Branch-free offset buffers filled a block at a time (BlockQuicksort)
+    Block,
+    /// Whole vectors compared with the pivot and shuffled into place
+    Simd,
+}
+
+impl Partition {
+    /// Every scheme, in the order `--compare-partitions` reports them.
+    const ALL: [Partition; 3] = [Partition::Lomuto, Partition::Block, Partition::Simd];
+
+    /// Returns the name used for this scheme on the command line.
+    fn name(self) -> &'static str {
+        match self {
+            Partition::Lomuto => "lomuto",
+            Partition::Block => "block",
+            Partition::Simd => "simd",
+        }
+    }
+
+    /// Looks up a scheme by its command-line name.
+    fn from_name(name: &str) -> Option<Partition> {
+        Partition::ALL.iter().copied().find(|partition| partition.name() == name)
+    }
+}
+
+/// The number of elements each side of `block_partition` scans at a time.
+/// Offsets within a block must fit in a `u8`.
+const BLOCK_SIZE: usize = 128;
+
+/// Cutoffs timed by `--tune`. A cutoff of 1 never calls insertion sort, so
+/// it measures the plain recursive sort as a baseline.
+const TUNE_CUTOFFS: [usize; 11] = [1, 4, 8, 12, 16, 20, 24, 32, 48, 64, 96];
+
+/// The number of timed runs per cutoff; the median is reported.
+const TUNE_REPETITIONS: usize = 5;
+
+/// An order statistic that can be requested on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    /// The k-th smallest value, counting from 1
+    Kth(usize),
+    /// The lower median
+    Median,
+    /// The given percentile (0 to 100) by the nearest-rank method
+    Percentile(f64),
+}
+
+impl Statistic {
+    /// Returns the 0-based position of this statistic in sorted order.
+    ///
+    /// # Arguments
+    /// * `len` - The number of values; must be at least 1
+    ///
+    /// # Returns
+    /// * `Result<usize, String>` - The position, or an error if k is out of range
+    fn rank(self, len: usize) -> Result<usize, String> {
+        match self {
+            Statistic::Kth(k) if k >= 1 && k <= len => Ok(k - 1),
+            Statistic::Kth(k) => Err(format!("k = {} is out of range for {} values", k, len)),
+            Statistic::Median => Ok((len - 1) / 2),
+            Statistic::Percentile(p) => {
+                // Nearest rank: the smallest value with at least p% of the data at or below it
+                let rank = (p / 100.0 * len as f64).ceil() as usize;
+                Ok(rank.clamp(1, len) - 1)
+            }
+        }
+    }
+}
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// How subarrays at the bottom of the recursion are sorted
+    base_case: BaseCase,
+    /// How the pivot of each partition is chosen
+    pivot: Pivot,
+    /// Whether to sweep insertion sort cutoffs instead of sorting
+    tune: bool,
+    /// How each range is rearranged around its pivot
+    partition: Partition,
+    /// Whether to time every pivot strategy instead of sorting
+    compare_pivots: bool,
+    /// Whether to time every partition scheme instead of sorting
+    compare_partitions: bool,
+    /// Whether to print original line numbers instead of sorted values
+    indices: bool,
+    /// Order statistics to print instead of sorting
+    statistics: Vec<Statistic>,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Where to write a trace of the sort's operations, if anywhere
+    trace: Option<Target>,
+    /// Where to write the sound of the sort's array accesses, if anywhere
+    sound: Option<String>,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
 
 /// Reads integers from standard input, one per line.
 ///
@@ -68,7 +421,8 @@ fn read_input() -> io::Result<Vec<i32>> {
 ///
 /// This implementation uses the Lomuto partition scheme with the last element
 /// as the pivot. The algorithm sorts in-place for O(log n) space complexity
-/// (due to recursion stack in the average case).
+/// (due to recursion stack in the average case). See `Pivot` for the other
+/// pivot strategies.
 ///
 /// # Time Complexity
 /// * Best case: O(n log n) when partitions are balanced
@@ -94,11 +448,44 @@ fn read_input() -> io::Result<Vec<i32>> {
 /// # Arguments
 /// * `numbers` - A mutable slice of i32 integers to sort
 pub fn quick_sort(numbers: &mut [i32]) {
+    quick_sort_with(numbers, BaseCase::Single, Pivot::Last, Partition::Lomuto);
+}
+
+/// Sorts a slice of integers with quick sort using the given base case and
+/// pivot strategy.
+///
+/// With `Pivot::MedianOfMedians` every partition leaves at least 30% of the
+/// range on each side of the pivot, so the recursion depth is O(log n) and
+/// the sort is O(n log n) in the worst case, at the cost of a larger
+/// constant factor for finding the pivot.
+///
+/// With `Pivot::Random` the expected running time is O(n log n) on every
+/// input with distinct values, since no fixed input order can make the
+/// random pivots consistently poor. The generator is seeded from the
+/// variant, so the same seed always performs the same partitions.
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+/// * `pivot` - How the pivot of each partition is chosen
+/// * `partition` - How each range is rearranged around its pivot
+fn quick_sort_with<T: Element>(
+    numbers: &mut [T],
+    base_case: BaseCase,
+    pivot: Pivot,
+    partition: Partition,
+) {
     let len = numbers.len();
     if len <= 1 {
         return;
     }
-    quick_sort_helper(numbers, 0, (len - 1) as isize);
+    let seed = match pivot {
+        Pivot::Random(seed) => seed,
+        _ => DEFAULT_SEED,
+    };
+    let mut rng = XorShift64::new(seed);
+    let high = (len - 1) as isize;
+    quick_sort_helper(numbers, 0, high, base_case, pivot, partition, &mut rng, 1);
 }
 
 /// Helper function for quick sort that performs the recursive sorting.
@@ -107,14 +494,129 @@ pub fn quick_sort(numbers: &mut [i32]) {
 /// * `arr` - The array slice to sort
 /// * `low` - Starting index of the portion to sort (inclusive)
 /// * `high` - Ending index of the portion to sort (inclusive)
-fn quick_sort_helper(arr: &mut [i32], low: isize, high: isize) {
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+/// * `pivot` - How the pivot of each partition is chosen
+/// * `partition` - How each range is rearranged around its pivot
+/// * `rng` - The generator that random pivots are drawn from
+/// * `depth` - The depth of this call, 1 for the outermost one
+#[allow(clippy::too_many_arguments)]
+fn quick_sort_helper<T: Element>(
+    arr: &mut [T],
+    low: isize,
+    high: isize,
+    base_case: BaseCase,
+    pivot: Pivot,
+    partition: Partition,
+    rng: &mut XorShift64,
+    depth: usize,
+) {
+    T::descended(depth);
+
+    // Small subarrays go straight to the base case sort
+    if low < high && base_case.sort_small(&mut arr[low as usize..=high as usize]) {
+        return;
+    }
+
     if low < high {
-        // Partition the array and get the pivot index
-        let pivot_index = partition(arr, low, high);
+        // Partition the array around the chosen pivot
+        let (left_high, right_low) = split(arr, low, high, pivot, partition, rng);
         
         // Recursively sort elements before and after the pivot
-        quick_sort_helper(arr, low, pivot_index - 1);
-        quick_sort_helper(arr, pivot_index + 1, high);
+        let deeper = depth + 1;
+        quick_sort_helper(arr, low, left_high, base_case, pivot, partition, rng, deeper);
+        quick_sort_helper(arr, right_low, high, base_case, pivot, partition, rng, deeper);
+    }
+}
+
+/// Chooses a pivot with the given strategy and partitions the range around it.
+///
+/// # Arguments
+/// * `arr` - The array to partition
+/// * `low` - Starting index of the portion to partition (inclusive)
+/// * `high` - Ending index of the portion to partition (inclusive)
+/// * `pivot` - How the pivot is chosen
+/// * `partition` - How the range is rearranged around the pivot; the median
+///   of medians pivot always uses a three-way partition
+/// * `rng` - The generator that random pivots are drawn from
+///
+/// # Returns
+/// * `(isize, isize)` - The last index of the left part and the first index
+///   of the right part; everything in between is equal to the pivot and in
+///   its final position
+fn split<T: Element>(
+    arr: &mut [T],
+    low: isize,
+    high: isize,
+    pivot: Pivot,
+    partition: Partition,
+    rng: &mut XorShift64,
+) -> (isize, isize) {
+    // Partitions around the pivot that was moved to arr[high]
+    let around_last = |arr: &mut [T]| {
+        let pivot_index = match partition {
+            Partition::Lomuto => self::partition(arr, low, high),
+            Partition::Block => block_partition(arr, low, high),
+            Partition::Simd => {
+                let range = &mut arr[low as usize..=high as usize];
+                low + T::simd_partition(range) as isize
+            }
+        };
+        T::partitioned(&arr[low as usize..=high as usize], &arr[pivot_index as usize]);
+        (pivot_index - 1, pivot_index + 1)
+    };
+
+    match pivot {
+        Pivot::Last => around_last(arr),
+        Pivot::MedianOfThree => {
+            median_of_three(arr, low as usize, high as usize);
+            around_last(arr)
+        }
+        Pivot::MedianOfMedians => {
+            // Lomuto sends every copy of the pivot to the left, which would
+            // unbalance the split on duplicates, so partition three ways
+            let range = &mut arr[low as usize..=high as usize];
+            let value = range[median_of_medians(range)];
+            let (less, greater) = partition_three_way(range, value);
+            T::partitioned(range, &range[less]);
+            (low + less as isize - 1, low + greater as isize)
+        }
+        Pivot::Random(_) => {
+            // Move a random element to the end, where partition expects it
+            let index = low as usize + rng.below((high - low + 1) as usize);
+            arr.swap(index, high as usize);
+            T::swapped(&arr[index], &arr[high as usize]);
+            around_last(arr)
+        }
+    }
+}
+
+/// Moves the median of the first, middle and last elements of a range to
+/// its last position, where `partition` expects the pivot.
+///
+/// On sorted or reverse-sorted input the middle element is picked, which
+/// avoids the O(n²) worst case of the last-element pivot.
+///
+/// # Arguments
+/// * `arr` - The array containing the range
+/// * `low` - Starting index of the range (inclusive)
+/// * `high` - Ending index of the range (inclusive)
+fn median_of_three<T: Tally>(arr: &mut [T], low: usize, high: usize) {
+    let mid = low + (high - low) / 2;
+
+    // Put the smallest of the three at low
+    if arr[mid] < arr[low] {
+        arr.swap(mid, low);
+        T::swapped(&arr[mid], &arr[low]);
+    }
+    if arr[high] < arr[low] {
+        arr.swap(high, low);
+        T::swapped(&arr[high], &arr[low]);
+    }
+
+    // The smaller of the remaining two is the median
+    if arr[mid] < arr[high] {
+        arr.swap(mid, high);
+        T::swapped(&arr[mid], &arr[high]);
     }
 }
 
@@ -138,7 +640,7 @@ fn quick_sort_helper(arr: &mut [i32], low: isize, high: isize) {
 ///
 /// # Returns
 /// * `isize` - The final index of the pivot element
-fn partition(arr: &mut [i32], low: isize, high: isize) -> isize {
+fn partition<T: Tally>(arr: &mut [T], low: isize, high: isize) -> isize {
     let pivot = arr[high as usize];
     let mut i = low - 1;
     
@@ -148,15 +650,427 @@ fn partition(arr: &mut [i32], low: isize, high: isize) -> isize {
             i += 1;
             // Swap arr[i] and arr[j]
             arr.swap(i as usize, j as usize);
+            T::swapped(&arr[i as usize], &arr[j as usize]);
         }
     }
     
     // Place the pivot in its correct position
     arr.swap((i + 1) as usize, high as usize);
+    T::swapped(&arr[(i + 1) as usize], &arr[high as usize]);
     
     i + 1
 }
 
+/// Partitions a range like `partition`, but without a data-dependent branch
+/// per element (Edelkamp and Weiß, BlockQuicksort).
+///
+/// Lomuto's `if arr[j] <= pivot` is taken about half the time on random
+/// data, so the branch predictor misses on roughly every other element.
+/// Here a block of `BLOCK_SIZE` elements is scanned from each end of the
+/// range. Every scan step stores the element's offset unconditionally and
+/// advances the count by the comparison result (0 or 1), so only the
+/// offsets of misplaced elements are kept. The misplaced elements from the
+/// two blocks are then swapped pairwise, and a block is replaced once all of
+/// its offsets have been used. The few hundred elements left in the middle
+/// are partitioned with an ordinary loop.
+///
+/// How much this saves depends on the machine's branch predictor, so
+/// measure it with `--compare-partitions`.
+///
+/// # Time Complexity
+/// * O(n) with about n/2 fewer mispredicted branches than Lomuto
+///
+/// # Space Complexity
+/// * O(1) - two fixed offset buffers of `BLOCK_SIZE` bytes
+///
+/// # Arguments
+/// * `arr` - The array to partition
+/// * `low` - Starting index of the portion to partition
+/// * `high` - Ending index of the portion to partition (contains pivot)
+///
+/// # Returns
+/// * `isize` - The final index of the pivot element
+fn block_partition<T: Tally>(arr: &mut [T], low: isize, high: isize) -> isize {
+    let pivot = arr[high as usize];
+
+    // Everything before `left` is <= pivot and everything from `right` up to
+    // the pivot is > pivot; the range in between is still unpartitioned
+    let mut left = low as usize;
+    let mut right = high as usize;
+
+    let mut offsets_left = [0u8; BLOCK_SIZE];
+    let mut offsets_right = [0u8; BLOCK_SIZE];
+    let (mut start_left, mut count_left) = (0, 0);
+    let (mut start_right, mut count_right) = (0, 0);
+
+    while right - left > 2 * BLOCK_SIZE {
+        // Collect elements of the left block that belong on the right
+        if count_left == 0 {
+            start_left = 0;
+            for (i, &value) in arr[left..left + BLOCK_SIZE].iter().enumerate() {
+                offsets_left[count_left] = i as u8;
+                count_left += (value > pivot) as usize;
+            }
+        }
+
+        // Collect elements of the right block that belong on the left
+        if count_right == 0 {
+            start_right = 0;
+            for (i, &value) in arr[right - BLOCK_SIZE..right].iter().rev().enumerate() {
+                offsets_right[count_right] = i as u8;
+                count_right += (value <= pivot) as usize;
+            }
+        }
+
+        // Swap as many misplaced pairs as both blocks can supply
+        let count = count_left.min(count_right);
+        for j in 0..count {
+            let a = left + offsets_left[start_left + j] as usize;
+            let b = right - 1 - offsets_right[start_right + j] as usize;
+            arr.swap(a, b);
+            T::swapped(&arr[a], &arr[b]);
+        }
+        start_left += count;
+        start_right += count;
+        count_left -= count;
+        count_right -= count;
+
+        // A block with no misplaced elements left is fully partitioned
+        if count_left == 0 {
+            left += BLOCK_SIZE;
+        }
+        if count_right == 0 {
+            right -= BLOCK_SIZE;
+        }
+    }
+
+    // Finish the middle, including any half-used block, one element at a time
+    let mut store = left;
+    for j in left..right {
+        if arr[j] <= pivot {
+            arr.swap(store, j);
+            T::swapped(&arr[store], &arr[j]);
+            store += 1;
+        }
+    }
+
+    // Place the pivot in its correct position
+    arr.swap(store, high as usize);
+    T::swapped(&arr[store], &arr[high as usize]);
+
+    store as isize
+}
+
+/// Rearranges a slice so that the element at index `k` is the one that
+/// would be there if the slice were sorted, and returns it.
+///
+/// After the call, every element before index `k` is less than or equal to
+/// it and every element after is greater than or equal to it, the same
+/// guarantee as `slice::select_nth_unstable`.
+///
+/// This is introselect: quickselect driven by `partition`, which only
+/// recurses into the side that contains `k`. Quickselect is O(n) on average
+/// but O(n²) when the pivots are poor (with the last-element pivot, on
+/// sorted input). After 2·log₂ n partition steps without finishing, it
+/// switches to `median_of_medians_select`, which is O(n) in the worst case.
+///
+/// # Time Complexity
+/// * Average case: O(n)
+/// * Worst case: O(n) - guaranteed by the median-of-medians fallback
+///
+/// # Space Complexity
+/// * O(1) besides the O(log n) recursion of the fallback
+///
+/// # Arguments
+/// * `arr` - The slice to partially sort
+/// * `k` - The 0-based position to select
+///
+/// # Returns
+/// * `T` - The k-th smallest element (counting from 0)
+///
+/// # Panics
+/// * Panics if `k` is not a valid index into `arr`
+pub fn select_nth<T: Tally>(arr: &mut [T], k: usize) -> T {
+    assert!(k < arr.len(), "select_nth: k = {} out of range for length {}", k, arr.len());
+
+    let mut low = 0;
+    let mut high = arr.len() - 1;
+    let mut budget = 2 * (usize::BITS - arr.len().leading_zeros());
+
+    while low < high {
+        // Too many unproductive partitions: finish with the guaranteed O(n) method
+        if budget == 0 {
+            median_of_medians_select(&mut arr[low..=high], k - low);
+            break;
+        }
+        budget -= 1;
+
+        let pivot_index = partition(arr, low as isize, high as isize) as usize;
+        if k < pivot_index {
+            high = pivot_index - 1;
+        } else if k > pivot_index {
+            low = pivot_index + 1;
+        } else {
+            break;
+        }
+    }
+
+    arr[k]
+}
+
+/// Selects the k-th smallest element in worst-case O(n) time (BFPRT).
+///
+/// Each round picks its pivot with `median_of_medians`, which guarantees
+/// that at least 30% of the elements are on each side of the pivot, and
+/// then partitions three ways so that runs of equal values are settled in
+/// a single step.
+///
+/// # Arguments
+/// * `arr` - The slice to partially sort
+/// * `k` - The 0-based position to select
+fn median_of_medians_select<T: Tally>(arr: &mut [T], k: usize) {
+    let mut low = 0;
+    let mut high = arr.len();
+
+    // Invariant: the answer lies in arr[low..high]
+    loop {
+        let range = &mut arr[low..high];
+        if range.len() <= 5 {
+            insertion_sort(range);
+            return;
+        }
+
+        let pivot = range[median_of_medians(range)];
+        let (less, greater) = partition_three_way(range, pivot);
+
+        if k - low < less {
+            high = low + less;
+        } else if k - low >= greater {
+            low += greater;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Finds a pivot that is guaranteed to lie between the 30th and 70th
+/// percentiles of the slice.
+///
+/// 1. Split the slice into groups of 5 and sort each group
+/// 2. Move the median of each group to the front of the slice
+/// 3. Select the median of those medians recursively
+///
+/// # Arguments
+/// * `arr` - The slice to choose a pivot from (its order is changed)
+///
+/// # Returns
+/// * `usize` - The index of the chosen pivot within `arr`
+fn median_of_medians<T: Tally>(arr: &mut [T]) -> usize {
+    let len = arr.len();
+    if len <= 5 {
+        insertion_sort(arr);
+        return len / 2;
+    }
+
+    let groups = len / 5;
+    for group in 0..groups {
+        let start = group * 5;
+        insertion_sort(&mut arr[start..start + 5]);
+        // Earlier positions belong to groups that are already done
+        arr.swap(group, start + 2);
+        T::swapped(&arr[group], &arr[start + 2]);
+    }
+
+    median_of_medians_select(&mut arr[..groups], groups / 2);
+    groups / 2
+}
+
+/// Computes the requested order statistics with `select_nth`.
+///
+/// Each selection only partially reorders the numbers, so later selections
+/// still see the same values.
+///
+/// # Arguments
+/// * `numbers` - The input values (their order is changed)
+/// * `statistics` - The statistics to compute
+///
+/// # Returns
+/// * `Result<Vec<i32>, String>` - One value per statistic, or an error if
+///   the input is empty or k is out of range
+fn order_statistics(numbers: &mut [i32], statistics: &[Statistic]) -> Result<Vec<i32>, String> {
+    if numbers.is_empty() {
+        return Err("no input values to select from".to_string());
+    }
+
+    statistics
+        .iter()
+        .map(|&statistic| Ok(select_nth(numbers, statistic.rank(numbers.len())?)))
+        .collect()
+}
+
+/// Returns the permutation that sorts `numbers`, computed with quick sort.
+///
+/// Quick sort is not stable, so equal values may come out in any order.
+///
+/// # Arguments
+/// * `numbers` - The values to sort; they are not modified
+/// * `base_case` - How subarrays at the bottom of the recursion are sorted
+/// * `pivot` - How the pivot of each partition is chosen
+/// * `partition` - How each range is rearranged around its pivot
+///
+/// # Returns
+/// * `Vec<usize>` - Entry i is the original index of the i-th smallest value
+fn argsort(numbers: &[i32], base_case: BaseCase, pivot: Pivot, partition: Partition) -> Vec<usize> {
+    argsort_with(numbers, |tagged| quick_sort_with(tagged, base_case, pivot, partition))
+}
+
+/// Times a sort function on fresh copies of the input.
+///
+/// The sort is timed `TUNE_REPETITIONS` times and the median is kept, which
+/// filters out one-off interruptions on the local machine.
+///
+/// # Arguments
+/// * `numbers` - The unsorted input
+/// * `sort` - The sort to time
+///
+/// # Returns
+/// * `Duration` - The median time of one sort
+fn median_time<F: Fn(&mut [i32])>(numbers: &[i32], sort: F) -> Duration {
+    let mut times = Vec::with_capacity(TUNE_REPETITIONS);
+    for _ in 0..TUNE_REPETITIONS {
+        let mut copy = numbers.to_vec();
+        let start = Instant::now();
+        sort(&mut copy);
+        times.push(start.elapsed());
+    }
+    times.sort();
+    times[TUNE_REPETITIONS / 2]
+}
+
+/// Times quick sort with each insertion sort cutoff in `TUNE_CUTOFFS`.
+///
+/// # Arguments
+/// * `numbers` - The unsorted input to time the sweep on
+/// * `pivot` - The pivot strategy to tune the cutoff for
+/// * `partition` - The partition scheme to tune the cutoff for
+///
+/// # Returns
+/// * `Vec<(usize, Duration)>` - The median sort time for each cutoff
+fn tune_cutoff(numbers: &[i32], pivot: Pivot, partition: Partition) -> Vec<(usize, Duration)> {
+    TUNE_CUTOFFS
+        .iter()
+        .map(|&cutoff| {
+            let time = median_time(numbers, |copy| {
+                quick_sort_with(copy, BaseCase::Insertion(cutoff), pivot, partition)
+            });
+            (cutoff, time)
+        })
+        .collect()
+}
+
+/// Times quick sort with every pivot strategy in `Pivot::ALL`.
+///
+/// Note that the last-element pivot takes quadratic time on sorted and
+/// reverse-sorted input.
+///
+/// # Arguments
+/// * `numbers` - The unsorted input to time the strategies on
+/// * `base_case` - The base case used with every strategy
+/// * `partition` - The partition scheme used with every strategy
+///
+/// # Returns
+/// * `Vec<(Pivot, Duration)>` - The median sort time for each strategy
+fn compare_pivots(
+    numbers: &[i32],
+    base_case: BaseCase,
+    partition: Partition,
+) -> Vec<(Pivot, Duration)> {
+    Pivot::ALL
+        .iter()
+        .map(|&pivot| {
+            let time = median_time(numbers, |copy| {
+                quick_sort_with(copy, base_case, pivot, partition)
+            });
+            (pivot, time)
+        })
+        .collect()
+}
+
+/// Times quick sort with every partition scheme in `Partition::ALL`.
+///
+/// # Arguments
+/// * `numbers` - The unsorted input to time the schemes on
+/// * `base_case` - The base case used with every scheme
+/// * `pivot` - The pivot strategy used with every scheme
+///
+/// # Returns
+/// * `Vec<(Partition, Duration)>` - The median sort time for each scheme
+fn compare_partitions(
+    numbers: &[i32],
+    base_case: BaseCase,
+    pivot: Pivot,
+) -> Vec<(Partition, Duration)> {
+    Partition::ALL
+        .iter()
+        .map(|&partition| {
+            let time = median_time(numbers, |copy| {
+                quick_sort_with(copy, base_case, pivot, partition)
+            });
+            (partition, time)
+        })
+        .collect()
+}
+
+/// Prints the time of each variant relative to the first one.
+///
+/// # Arguments
+/// * `heading` - What the variants are, such as "pivot"
+/// * `results` - The name and median sort time of each variant, starting
+///   with the baseline
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+fn print_comparison(heading: &str, results: &[(&str, Duration)]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    let baseline = results[0].1.as_secs_f64();
+    let relative = format!("vs {}", results[0].0);
+
+    writeln!(writer, "{:>10}  {:>12}  {:>9}", heading, "median (ms)", relative)?;
+    for &(name, time) in results {
+        let seconds = time.as_secs_f64();
+        let ratio = if baseline > 0.0 { seconds / baseline } else { 1.0 };
+        writeln!(writer, "{:>10}  {:>12.3}  {:>8.2}x", name, seconds * 1000.0, ratio)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Prints the results of a cutoff sweep and the fastest cutoff.
+///
+/// # Arguments
+/// * `results` - The median sort time for each cutoff
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+fn print_tuning(results: &[(usize, Duration)]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    writeln!(writer, "{:>8}  {:>12}", "cutoff", "median (ms)")?;
+    for &(cutoff, time) in results {
+        writeln!(writer, "{:>8}  {:>12.3}", cutoff, time.as_secs_f64() * 1000.0)?;
+    }
+
+    if let Some(&(best, _)) = results.iter().min_by_key(|&&(_, time)| time) {
+        writeln!(writer, "best cutoff: {}", best)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
 /// Prints each integer in the vector to standard output, one per line.
 ///
 /// Uses a buffered writer for efficient I/O when handling large output.
@@ -181,12 +1095,196 @@ fn print_output(numbers: &[i32]) -> io::Result<()> {
     Ok(())
 }
 
+/// Parses the command-line arguments.
+///
+/// Recognizes `--network`, `--cutoff N`, `--tune`, `--select K`, `--median`,
+/// `--percentile P`, `--pivot NAME`, `--seed N`, `--compare-pivots`,
+/// `--partition NAME`, `--compare-partitions`, `--simd`, `--indices`,
+/// `--stats`, `--trace[=FILE]`, `--sound=FILE` and `--timings`. Only one base
+/// case may be chosen, and a seed requires the random pivot. `--stats` counts
+/// a plain scalar sort, so it cannot be combined with another mode or with
+/// the vector kernels; neither can `--trace`, which also excludes `--stats`,
+/// nor `--sound`, which excludes both. `--timings` times a plain sort, so
+/// it cannot be combined with another mode or with `--stats`, `--trace` or
+/// `--sound`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        base_case: BaseCase::Single,
+        pivot: Pivot::Last,
+        tune: false,
+        partition: Partition::Lomuto,
+        compare_pivots: false,
+        compare_partitions: false,
+        indices: false,
+        statistics: Vec::new(),
+        stats: false,
+        trace: None,
+        sound: None,
+        timings: false,
+    };
+    let mut seed = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--network" | "--cutoff" | "--simd" if options.base_case != BaseCase::Single => {
+                return Err("--network, --cutoff and --simd cannot be combined".to_string());
+            }
+            "--network" => options.base_case = BaseCase::Network,
+            "--simd" => options.base_case = BaseCase::Simd,
+            "--cutoff" => {
+                let value = iter.next().ok_or("--cutoff requires a value")?;
+                let cutoff = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid cutoff: {}", value))?;
+                options.base_case = BaseCase::Insertion(cutoff);
+            }
+            "--tune" => options.tune = true,
+            "--pivot" => {
+                let value = iter.next().ok_or("--pivot requires a value")?;
+                options.pivot = Pivot::from_name(value)
+                    .ok_or_else(|| format!("Unknown pivot strategy: {}", value))?;
+            }
+            "--seed" => {
+                let value = iter.next().ok_or("--seed requires a value")?;
+                let n = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid seed: {}", value))?;
+                seed = Some(n);
+            }
+            "--compare-pivots" => options.compare_pivots = true,
+            "--partition" => {
+                let value = iter.next().ok_or("--partition requires a value")?;
+                options.partition = Partition::from_name(value)
+                    .ok_or_else(|| format!("Unknown partition scheme: {}", value))?;
+            }
+            "--compare-partitions" => options.compare_partitions = true,
+            "--indices" => options.indices = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            "--select" => {
+                let value = iter.next().ok_or("--select requires a value")?;
+                let k = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid k: {}", value))?;
+                options.statistics.push(Statistic::Kth(k));
+            }
+            "--median" => options.statistics.push(Statistic::Median),
+            "--percentile" => {
+                let value = iter.next().ok_or("--percentile requires a value")?;
+                let p = match value.parse::<f64>() {
+                    Ok(p) if (0.0..=100.0).contains(&p) => p,
+                    _ => return Err(format!("Invalid percentile: {}", value)),
+                };
+                options.statistics.push(Statistic::Percentile(p));
+            }
+            other => {
+                if let Some(target) = Target::from_arg(other) {
+                    options.trace = Some(target);
+                } else if let Some(path) = op_sound::from_arg(other) {
+                    options.sound = Some(path);
+                } else {
+                    return Err(format!("Unknown argument: {}", other));
+                }
+            }
+        }
+    }
+
+    // The seed may come before or after --pivot random
+    if let Some(n) = seed {
+        match options.pivot {
+            Pivot::Random(_) => options.pivot = Pivot::Random(n),
+            _ => return Err("--seed requires --pivot random".to_string()),
+        }
+    }
+
+    let other_mode = options.tune
+        || options.compare_pivots
+        || options.compare_partitions
+        || options.indices
+        || !options.statistics.is_empty();
+    let simd = options.base_case == BaseCase::Simd || options.partition == Partition::Simd;
+
+    if options.stats {
+        if other_mode {
+            return Err("--stats cannot be combined with another mode".to_string());
+        }
+        if simd {
+            return Err("--stats cannot count the --simd kernels".to_string());
+        }
+    }
+
+    if options.trace.is_some() {
+        if other_mode || options.stats {
+            return Err("--trace cannot be combined with --stats or another mode".to_string());
+        }
+        if simd {
+            return Err("--trace cannot follow the --simd kernels".to_string());
+        }
+    }
+
+    if options.sound.is_some() {
+        if other_mode || options.stats || options.trace.is_some() {
+            let message = "--sound cannot be combined with --stats, --trace or another mode";
+            return Err(message.to_string());
+        }
+        if simd {
+            return Err("--sound cannot follow the --simd kernels".to_string());
+        }
+    }
+
+    if options.timings {
+        if other_mode {
+            return Err("--timings cannot be combined with another mode".to_string());
+        }
+        if options.stats || options.trace.is_some() || options.sound.is_some() {
+            return Err("--timings cannot be combined with --stats, --trace or --sound".to_string());
+        }
+    }
+
+    Ok(options)
+}
+
 /// Main entry point for the quick sort program.
 ///
 /// Orchestrates reading from stdin, sorting, and writing to stdout.
 /// Exits with status code 1 if any errors occur during execution.
 fn main() {
-    // Read integers from standard input
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Print the original line numbers in sorted order instead of the values
+    if options.indices {
+        let (numbers, lines) = match read_input_with_lines() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                process::exit(1);
+            }
+        };
+        let order = argsort(&numbers, options.base_case, options.pivot, options.partition);
+        if let Err(e) = print_line_numbers(&order, &lines) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
     let mut numbers = match read_input() {
         Ok(nums) => nums,
         Err(e) => {
@@ -194,15 +1292,109 @@ fn main() {
             process::exit(1);
         }
     };
+    timer.lap("read_input");
+
+    // Sweep insertion sort cutoffs instead of sorting
+    if options.tune {
+        if let Err(e) = print_tuning(&tune_cutoff(&numbers, options.pivot, options.partition)) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
 
-    // Sort the numbers using quick sort
-    quick_sort(&mut numbers);
+    // Time every pivot strategy instead of sorting
+    if options.compare_pivots {
+        let results: Vec<(&str, Duration)> =
+            compare_pivots(&numbers, options.base_case, options.partition)
+                .into_iter()
+                .map(|(pivot, time)| (pivot.name(), time))
+                .collect();
+        if let Err(e) = print_comparison("pivot", &results) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Time every partition scheme instead of sorting
+    if options.compare_partitions {
+        let results: Vec<(&str, Duration)> =
+            compare_partitions(&numbers, options.base_case, options.pivot)
+                .into_iter()
+                .map(|(partition, time)| (partition.name(), time))
+                .collect();
+        if let Err(e) = print_comparison("partition", &results) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Print order statistics instead of sorting
+    if !options.statistics.is_empty() {
+        let values = match order_statistics(&mut numbers, &options.statistics) {
+            Ok(values) => values,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = print_output(&values) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Sort the numbers using quick sort, tracing, playing or counting operations if asked
+    if let Some(ref target) = options.trace {
+        let written = target.open().and_then(|writer| {
+            op_trace::record(&mut numbers, "quick sort", writer, |slice| {
+                quick_sort_with(slice, options.base_case, options.pivot, options.partition)
+            })
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing trace: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(ref path) = options.sound {
+        let written = File::create(path).and_then(|file| {
+            op_sound::record(&mut numbers, BufWriter::new(file), |slice| {
+                quick_sort_with(slice, options.base_case, options.pivot, options.partition)
+            })
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing sound: {}", e);
+            process::exit(1);
+        }
+    } else if options.stats {
+        let mut heap = 0;
+        let mut counts = count(&mut numbers, |slice| {
+            heap = peak_heap(|| {
+                quick_sort_with(slice, options.base_case, options.pivot, options.partition)
+            })
+            .1;
+        });
+        counts.peak_heap_bytes = Some(heap as u64);
+        eprint!("{}", counts.summary("quick sort", numbers.len()));
+    } else {
+        quick_sort_with(&mut numbers, options.base_case, options.pivot, options.partition);
+    }
+
+    timer.lap("sort");
 
     // Print the sorted numbers to standard output
     if let Err(e) = print_output(&numbers) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        eprint!("{}", timer.summary("quick sort", numbers.len()));
+    }
 }
 
 #[cfg(test)]
@@ -307,4 +1499,589 @@ mod tests {
             assert!(arr[i] <= 5);
         }
     }
+
+    #[test]
+    fn test_network_base_case() {
+        // Cover lengths below, at and well above the network size
+        let mut rng = XorShift64::new(2026);
+        for len in 0..200 {
+            let arr: Vec<i32> = rng.values(len, -50..50);
+            let mut expected = arr.clone();
+            quick_sort(&mut expected);
+            let mut actual = arr;
+            quick_sort_with(&mut actual, BaseCase::Network, Pivot::Last, Partition::Lomuto);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_insertion_cutoff() {
+        let mut rng = XorShift64::new(416);
+        for &cutoff in &[0, 1, 2, 5, 16, 32, 100] {
+            for len in 0..150 {
+                let arr: Vec<i32> = rng.values(len, -50..50);
+                let mut expected = arr.clone();
+                quick_sort(&mut expected);
+                let mut actual = arr;
+                let base_case = BaseCase::Insertion(cutoff);
+                quick_sort_with(&mut actual, base_case, Pivot::Last, Partition::Lomuto);
+                assert_eq!(actual, expected, "cutoff {}, length {}", cutoff, len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                base_case: BaseCase::Single,
+                pivot: Pivot::Last,
+                tune: false,
+                partition: Partition::Lomuto,
+                compare_pivots: false,
+                compare_partitions: false,
+                indices: false,
+                statistics: vec![],
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--cutoff", "24"])),
+            Ok(Options {
+                base_case: BaseCase::Insertion(24),
+                pivot: Pivot::Last,
+                tune: false,
+                partition: Partition::Lomuto,
+                compare_pivots: false,
+                compare_partitions: false,
+                indices: false,
+                statistics: vec![],
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--network", "--tune"])),
+            Ok(Options {
+                base_case: BaseCase::Network,
+                pivot: Pivot::Last,
+                tune: true,
+                partition: Partition::Lomuto,
+                compare_pivots: false,
+                compare_partitions: false,
+                indices: false,
+                statistics: vec![],
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--select", "3", "--median", "--percentile", "99.5"])),
+            Ok(Options {
+                base_case: BaseCase::Single,
+                pivot: Pivot::Last,
+                tune: false,
+                partition: Partition::Lomuto,
+                compare_pivots: false,
+                compare_partitions: false,
+                indices: false,
+                statistics: vec![
+                    Statistic::Kth(3),
+                    Statistic::Median,
+                    Statistic::Percentile(99.5),
+                ],
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--pivot", "mom", "--compare-pivots"])),
+            Ok(Options {
+                base_case: BaseCase::Single,
+                pivot: Pivot::MedianOfMedians,
+                tune: false,
+                partition: Partition::Lomuto,
+                compare_pivots: true,
+                compare_partitions: false,
+                indices: false,
+                statistics: vec![],
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--seed", "42", "--pivot", "random"])).map(|o| o.pivot),
+            Ok(Pivot::Random(42))
+        );
+        assert_eq!(
+            parse_args(&args(&["--pivot", "random"])).map(|o| o.pivot),
+            Ok(Pivot::Random(DEFAULT_SEED))
+        );
+        assert!(parse_args(&args(&["--seed", "42"])).is_err());
+        assert!(parse_args(&args(&["--pivot", "random", "--seed", "-1"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--partition", "block", "--compare-partitions"]))
+                .map(|o| (o.partition, o.compare_partitions)),
+            Ok((Partition::Block, true))
+        );
+        assert!(parse_args(&args(&["--partition", "hoare"])).is_err());
+        assert_eq!(parse_args(&args(&["--indices"])).map(|o| o.indices), Ok(true));
+        assert!(parse_args(&args(&["--pivot", "first"])).is_err());
+        assert!(parse_args(&args(&["--select"])).is_err());
+        assert!(parse_args(&args(&["--percentile", "101"])).is_err());
+        assert!(parse_args(&args(&["--cutoff"])).is_err());
+        assert!(parse_args(&args(&["--cutoff", "abc"])).is_err());
+        assert!(parse_args(&args(&["--network", "--cutoff", "8"])).is_err());
+        assert!(parse_args(&args(&["--simd", "--network"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--simd", "--partition", "simd"]))
+                .map(|o| (o.base_case, o.partition)),
+            Ok((BaseCase::Simd, Partition::Simd))
+        );
+        assert_eq!(
+            parse_args(&args(&["--stats", "--pivot", "median3", "--partition", "block"]))
+                .map(|o| (o.stats, o.pivot, o.partition)),
+            Ok((true, Pivot::MedianOfThree, Partition::Block))
+        );
+        assert!(parse_args(&args(&["--stats", "--median"])).is_err());
+        assert!(parse_args(&args(&["--compare-pivots", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--stats", "--simd"])).is_err());
+        assert!(parse_args(&args(&["--stats", "--partition", "simd"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--pivot", "mom", "--trace=q.jsonl"])).map(|o| o.trace),
+            Ok(Some(Target::File("q.jsonl".to_string())))
+        );
+        assert!(parse_args(&args(&["--trace", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--trace", "--median"])).is_err());
+        assert!(parse_args(&args(&["--simd", "--trace"])).is_err());
+        assert!(parse_args(&args(&["--trace=x", "--partition", "simd"])).is_err());
+        assert!(parse_args(&args(&["--tracefile"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--sound=q.wav", "--partition", "block"])).map(|o| o.sound),
+            Ok(Some("q.wav".to_string()))
+        );
+        assert!(parse_args(&args(&["--sound=x", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--sound=x", "--trace"])).is_err());
+        assert!(parse_args(&args(&["--compare-pivots", "--sound=x"])).is_err());
+        assert!(parse_args(&args(&["--simd", "--sound=x"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["--simd", "--timings"])).map(|o| (o.base_case, o.timings)),
+            Ok((BaseCase::Simd, true))
+        );
+        assert!(parse_args(&args(&["--timings", "--compare-pivots"])).is_err());
+        assert!(parse_args(&args(&["--median", "--timings"])).is_err());
+        assert!(parse_args(&args(&["--timings", "--stats"])).is_err());
+        assert!(parse_args(&args(&["--trace", "--timings"])).is_err());
+        assert!(parse_args(&args(&["--timings", "--sound=x"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_tune_cutoff_covers_every_cutoff() {
+        let numbers: Vec<i32> = (0..500).rev().collect();
+        let results = tune_cutoff(&numbers, Pivot::Last, Partition::Lomuto);
+        let cutoffs: Vec<usize> = results.iter().map(|&(cutoff, _)| cutoff).collect();
+        assert_eq!(cutoffs, TUNE_CUTOFFS.to_vec());
+    }
+
+    #[test]
+    fn test_select_nth_matches_sorted() {
+        let mut rng = XorShift64::new(1961);
+        for len in 1..=120 {
+            let arr: Vec<i32> = rng.values(len, -20..20);
+            let mut sorted = arr.clone();
+            sorted.sort();
+            for k in 0..len {
+                let mut work = arr.clone();
+                assert_eq!(select_nth(&mut work, k), sorted[k]);
+                assert!(work[..k].iter().all(|&x| x <= work[k]));
+                assert!(work[k + 1..].iter().all(|&x| x >= work[k]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_nth_adversarial_inputs() {
+        // Sorted, reverse and identical inputs exhaust the quickselect budget
+        // and must be finished by the median-of-medians fallback
+        let n = 100_000;
+        let sorted: Vec<i32> = (0..n).collect();
+        let reverse: Vec<i32> = (0..n).rev().collect();
+        let identical = vec![7; n as usize];
+        for k in [0, 1, n as usize / 2, n as usize - 1] {
+            assert_eq!(select_nth(&mut sorted.clone(), k), k as i32);
+            assert_eq!(select_nth(&mut reverse.clone(), k), k as i32);
+            assert_eq!(select_nth(&mut identical.clone(), k), 7);
+        }
+    }
+
+    #[test]
+    fn test_median_of_medians_select() {
+        let mut rng = XorShift64::new(73);
+        let arr: Vec<i32> = rng.values(1000, 0..100);
+        let mut sorted = arr.clone();
+        sorted.sort();
+        for k in (0..1000).step_by(37) {
+            let mut work = arr.clone();
+            median_of_medians_select(&mut work, k);
+            assert_eq!(work[k], sorted[k]);
+        }
+    }
+
+    // Lets the tests select among strings
+    impl Tally for &str {}
+
+    #[test]
+    fn test_select_nth_generic() {
+        let mut words = vec!["pear", "apple", "fig", "kiwi"];
+        assert_eq!(select_nth(&mut words, 1), "fig");
+    }
+
+    #[test]
+    fn test_order_statistics() {
+        let mut numbers: Vec<i32> = (1..=10).rev().collect();
+        let statistics = [
+            Statistic::Kth(1),
+            Statistic::Kth(10),
+            Statistic::Median,
+            Statistic::Percentile(0.0),
+            Statistic::Percentile(90.0),
+            Statistic::Percentile(100.0),
+        ];
+        assert_eq!(order_statistics(&mut numbers, &statistics), Ok(vec![1, 10, 5, 1, 9, 10]));
+        assert!(order_statistics(&mut numbers, &[Statistic::Kth(11)]).is_err());
+        assert!(order_statistics(&mut numbers, &[Statistic::Kth(0)]).is_err());
+        assert!(order_statistics(&mut [], &[Statistic::Median]).is_err());
+    }
+
+    #[test]
+    fn test_every_pivot_strategy() {
+        let mut rng = XorShift64::new(1973);
+        for &pivot in Pivot::ALL.iter() {
+            for len in 0..150 {
+                let arr: Vec<i32> = rng.values(len, -15..15);
+                let mut expected = arr.clone();
+                expected.sort();
+                let mut actual = arr;
+                quick_sort_with(&mut actual, BaseCase::Single, pivot, Partition::Lomuto);
+                assert_eq!(actual, expected, "pivot {}, length {}", pivot.name(), len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_median_of_medians_adversarial_inputs() {
+        // These inputs take quadratic time with the last-element pivot
+        let n = 200_000;
+        let sorted: Vec<i32> = (0..n).collect();
+        let reverse: Vec<i32> = (0..n).rev().collect();
+        let identical = vec![7; n as usize];
+        for input in [sorted.clone(), reverse, identical.clone()] {
+            let mut actual = input.clone();
+            let pivot = Pivot::MedianOfMedians;
+            quick_sort_with(&mut actual, BaseCase::Single, pivot, Partition::Lomuto);
+            let mut expected = input;
+            expected.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_median_of_three() {
+        for arr in [[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1]] {
+            let mut arr = arr.to_vec();
+            median_of_three(&mut arr, 0, 2);
+            assert_eq!(arr[2], 2, "{:?}", arr);
+        }
+    }
+
+    #[test]
+    fn test_compare_pivots_reports_every_strategy() {
+        let numbers: Vec<i32> = (0..300).map(|x| (x * 37) % 101).collect();
+        let results = compare_pivots(&numbers, BaseCase::Single, Partition::Lomuto);
+        let pivots: Vec<Pivot> = results.iter().map(|&(pivot, _)| pivot).collect();
+        assert_eq!(pivots, Pivot::ALL.to_vec());
+    }
+
+    #[test]
+    fn test_random_pivot_sorted_and_reverse_inputs() {
+        // With the last-element pivot these inputs recurse n levels deep; a
+        // random pivot keeps the depth logarithmic, so they finish quickly
+        let n = 1_000_000;
+        let sorted: Vec<i32> = (0..n).collect();
+        let reverse: Vec<i32> = (0..n).rev().collect();
+        for input in [sorted.clone(), reverse] {
+            let mut actual = input;
+            let pivot = Pivot::Random(DEFAULT_SEED);
+            quick_sort_with(&mut actual, BaseCase::Single, pivot, Partition::Lomuto);
+            assert_eq!(actual, sorted);
+        }
+    }
+
+    #[test]
+    fn test_random_pivot_is_reproducible() {
+        // Record the partition boundaries chosen for each seed
+        let boundaries = |seed| {
+            let mut arr: Vec<i32> = (0..200).map(|x| (x * 73) % 199).collect();
+            let mut rng = XorShift64::new(seed);
+            let mut result = Vec::new();
+            let mut high = arr.len() as isize - 1;
+            while high > 0 {
+                let pivot = Pivot::Random(seed);
+                let (left_high, _) = split(&mut arr, 0, high, pivot, Partition::Lomuto, &mut rng);
+                result.push(left_high);
+                high = left_high;
+            }
+            result
+        };
+        assert_eq!(boundaries(7), boundaries(7));
+        assert_ne!(boundaries(7), boundaries(8));
+    }
+
+    #[test]
+    fn test_block_partition_matches_contract() {
+        let mut rng = XorShift64::new(4242);
+        // Lengths around the block boundaries, with few and many distinct values
+        for &len in &[1, 2, 3, 255, 256, 257, 258, 600, 1000, 5000] {
+            for &range in &[3, 1_000_000] {
+                let mut arr: Vec<i32> = rng.values(len, 0..range);
+                let mut expected = arr.clone();
+                let pivot = arr[len - 1];
+                let pivot_index = block_partition(&mut arr, 0, len as isize - 1) as usize;
+
+                assert_eq!(arr[pivot_index], pivot);
+                assert!(arr[..pivot_index].iter().all(|&x| x <= pivot));
+                assert!(arr[pivot_index + 1..].iter().all(|&x| x > pivot));
+
+                // The partition only rearranges elements
+                expected.sort();
+                arr.sort();
+                assert_eq!(arr, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_partition_sorts_with_every_pivot() {
+        let mut rng = XorShift64::new(99);
+        let arr: Vec<i32> = rng.values(20_000, 0..1 << 28);
+        let mut expected = arr.clone();
+        expected.sort();
+        for &pivot in Pivot::ALL.iter() {
+            let mut actual = arr.clone();
+            quick_sort_with(&mut actual, BaseCase::Insertion(16), pivot, Partition::Block);
+            assert_eq!(actual, expected, "pivot {}", pivot.name());
+        }
+    }
+
+    #[test]
+    fn test_compare_partitions_reports_every_scheme() {
+        let numbers: Vec<i32> = (0..300).map(|x| (x * 37) % 101).collect();
+        let results = compare_partitions(&numbers, BaseCase::Single, Pivot::Last);
+        let schemes: Vec<Partition> = results.iter().map(|&(partition, _)| partition).collect();
+        assert_eq!(schemes, Partition::ALL.to_vec());
+    }
+
+    #[test]
+    fn test_simd_kernels_match_scalar_sort() {
+        let mut rng = XorShift64::new(31337);
+        for &len in &[0, 1, 2, 15, 16, 17, 100, 1000, 50_000] {
+            let arr: Vec<i32> = rng.values(len, 0..1 << 30);
+            let mut expected = arr.clone();
+            quick_sort(&mut expected);
+            for &partition in &[Partition::Lomuto, Partition::Simd] {
+                let mut actual = arr.clone();
+                quick_sort_with(&mut actual, BaseCase::Simd, Pivot::Last, partition);
+                assert_eq!(actual, expected, "length {}, {}", len, partition.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_argsort_every_configuration() {
+        let mut rng = XorShift64::new(8128);
+        let numbers: Vec<i32> = rng.values(2000, 0..300);
+        let mut sorted = numbers.clone();
+        sorted.sort();
+
+        for &base_case in &[BaseCase::Single, BaseCase::Insertion(16), BaseCase::Simd] {
+            for &pivot in Pivot::ALL.iter() {
+                for &partition in Partition::ALL.iter() {
+                    let order = argsort(&numbers, base_case, pivot, partition);
+                    let values: Vec<i32> = order.iter().map(|&i| numbers[i]).collect();
+                    assert_eq!(values, sorted, "{:?} {:?} {:?}", base_case, pivot, partition);
+
+                    // Every index appears exactly once
+                    let mut seen = order;
+                    seen.sort();
+                    assert_eq!(seen, (0..numbers.len()).collect::<Vec<usize>>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stats_show_pivot_worst_case() {
+        // The last-element pivot peels one element off sorted input per
+        // partition, so every pair is compared once
+        let mut sorted: Vec<i32> = (0..64).collect();
+        let counts = count(&mut sorted, |slice| {
+            quick_sort_with(slice, BaseCase::Single, Pivot::Last, Partition::Lomuto)
+        });
+        assert_eq!(sorted, (0..64).collect::<Vec<i32>>());
+        assert_eq!(counts.comparisons, 64 * 63 / 2);
+        // Lomuto swaps every element of a range that is all <= pivot
+        assert_eq!(counts.swaps, (2..=64).sum::<u64>());
+        assert_eq!(counts.writes, 0);
+
+        // Median of three splits sorted input in half every time
+        let mut sorted: Vec<i32> = (0..1024).collect();
+        let counts = count(&mut sorted, |slice| {
+            quick_sort_with(slice, BaseCase::Single, Pivot::MedianOfThree, Partition::Lomuto)
+        });
+        assert!(counts.comparisons < 2 * 1024 * 10, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_stats_every_scalar_configuration() {
+        let mut rng = XorShift64::new(99);
+        let numbers: Vec<i32> = rng.values(3000, 0..500);
+        let mut sorted = numbers.clone();
+        sorted.sort();
+
+        for &base_case in &[BaseCase::Single, BaseCase::Network, BaseCase::Insertion(16)] {
+            for &pivot in Pivot::ALL.iter() {
+                for &partition in &[Partition::Lomuto, Partition::Block] {
+                    let mut arr = numbers.clone();
+                    let counts = count(&mut arr, |slice| {
+                        quick_sort_with(slice, base_case, pivot, partition)
+                    });
+                    assert_eq!(arr, sorted, "{:?} {:?} {:?}", base_case, pivot, partition);
+                    assert!(counts.comparisons > 0 && counts.swaps > 0);
+                    assert_eq!(counts.buffer_copies, 0);
+                    // Only insertion sort and the groups of five of the
+                    // median of medians write outside a swap
+                    let insertion = matches!(base_case, BaseCase::Insertion(_));
+                    let writes = insertion || pivot == Pivot::MedianOfMedians;
+                    assert_eq!(counts.writes > 0, writes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth_and_heap_match_documented_space() {
+        let depth_and_heap = |numbers: &[i32], pivot: Pivot, partition: Partition| {
+            let mut heap = 0;
+            let counts = count(&mut numbers.to_vec(), |slice| {
+                heap = peak_heap(|| quick_sort_with(slice, BaseCase::Single, pivot, partition)).1;
+            });
+            (counts.max_depth, heap)
+        };
+
+        // The last-element pivot recurses once per element of sorted input,
+        // the O(n) worst case of the stack
+        let sorted: Vec<i32> = (0..1000).collect();
+        assert_eq!(depth_and_heap(&sorted, Pivot::Last, Partition::Lomuto), (1000, 0));
+
+        // Every other pivot keeps the depth logarithmic on sorted and random
+        // input, and no configuration allocates
+        let mut rng = XorShift64::new(3);
+        let random: Vec<i32> = rng.values(1000, 0..1 << 16);
+        for &pivot in Pivot::ALL.iter() {
+            for &partition in &[Partition::Lomuto, Partition::Block] {
+                let (depth, heap) = depth_and_heap(&random, pivot, partition);
+                assert!(depth <= 4 * 10, "{:?} {:?}: {}", pivot, partition, depth);
+                assert_eq!(heap, 0, "{:?} {:?}", pivot, partition);
+                if pivot != Pivot::Last {
+                    let (depth, _) = depth_and_heap(&sorted, pivot, partition);
+                    assert!(depth <= 4 * 10, "{:?} {:?}: {}", pivot, partition, depth);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_trace_partitions_every_scalar_configuration() {
+        use op_trace::Event;
+        use std::fs;
+
+        let mut rng = XorShift64::new(7);
+        let numbers: Vec<i32> = rng.values(60, 0..20);
+        let path = env::temp_dir().join(format!("quick_trace_{}.jsonl", process::id()));
+        let target = Target::File(path.to_string_lossy().into_owned());
+
+        for &pivot in Pivot::ALL.iter() {
+            for &partition in &[Partition::Lomuto, Partition::Block] {
+                let mut arr = numbers.clone();
+                let writer = target.open().unwrap();
+                op_trace::record(&mut arr, "quick sort", writer, |slice| {
+                    quick_sort_with(slice, BaseCase::Single, pivot, partition)
+                })
+                .unwrap();
+                let text = fs::read_to_string(&path).unwrap();
+
+                // Replaying up to each partition event shows the range split
+                // around the pivot at its final position
+                let mut array = Vec::new();
+                let mut partitions = 0;
+                for line in text.lines() {
+                    let event = Event::parse(line).unwrap();
+                    if let Event::Partition { low, high, pivot: at } = event {
+                        let (low, high, at) = (low.unwrap(), high.unwrap(), at.unwrap());
+                        assert!(low <= at && at <= high);
+                        assert!(array[low..at].iter().all(|&value| value <= array[at]));
+                        assert!(array[at + 1..=high].iter().all(|&value| value >= array[at]));
+                        partitions += 1;
+                    }
+                    event.apply(&mut array);
+                }
+                assert!(partitions > 0, "{:?} {:?}", pivot, partition);
+                assert_eq!(array, arr, "{:?} {:?}", pivot, partition);
+            }
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sound_has_a_tone_per_access() {
+        let numbers = vec![9, 2, 7, 4, 4, 8, 1, 6, 3, 5, 0, 2];
+        for &pivot in Pivot::ALL.iter() {
+            for &partition in &[Partition::Lomuto, Partition::Block] {
+                let sort = |slice: &mut [Sounded]| {
+                    quick_sort_with(slice, BaseCase::Single, pivot, partition)
+                };
+                let mut arr = numbers.clone();
+                let mut wav = Vec::new();
+                op_sound::record(&mut arr, &mut wav, sort).unwrap();
+                let mut expected = numbers.clone();
+                expected.sort();
+                assert_eq!(arr, expected);
+
+                // Comparisons and swaps access two values, writes one
+                let counts = count(&mut numbers.clone(), |slice| {
+                    quick_sort_with(slice, BaseCase::Single, pivot, partition)
+                });
+                let accesses = (2 * counts.comparisons + 2 * counts.swaps + counts.writes) as usize;
+                let samples = accesses * op_sound::tone_length(accesses);
+                assert_eq!(wav.len(), 44 + 2 * samples, "{:?} {:?}", pivot, partition);
+            }
+        }
+    }
 }
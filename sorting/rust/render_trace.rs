@@ -0,0 +1,727 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: renderTrace.rs
+
+   Description:
+      This program renders an operation trace written by the --trace flag
+      of the sorting programs (see opTrace.rs) as images for reports. The
+      trace is read from the file given as an argument or from standard
+      input, replayed step by step, and drawn in any of three forms:
+
+        * --svg FILE - a timeline with one row per step and one column per
+          position, each cell colored by the value it holds, from blue for
+          the smallest to red for the largest. Rows of merge and partition
+          events are outlined across their range, which shows how quick
+          sort's partitions shrink top-down while merge sort's merges grow
+          bottom-up.
+        * --ppm DIR - one binary PPM image per step, named
+          frame_00000.ppm and up, drawing the array as bars colored the
+          same way. Positions the step compared, swapped or wrote are
+          drawn black, and the range of a merge or partition is underlined
+          in gray.
+        * --gif FILE - the same frames as a looping animated GIF, written
+          by the encoder in gif.rs.
+
+      Passing --every N keeps only every Nth step (and the last), which
+      keeps long traces manageable: the trace is replayed on a single
+      array, and only the kept steps are copied out of it. --scale N sets
+      the size of a cell in pixels (4 by default), --height N the height
+      of a frame in pixels (128 by default), and --delay N the time each
+      GIF frame is shown in hundredths of a second (5 by default).
+
+   Usage:
+      ./quickSort --trace=quick.jsonl < input.txt > /dev/null
+      ./renderTrace --svg quick.svg quick.jsonl
+      ./renderTrace --gif merge.gif --every 4 --delay 2 < merge.jsonl
+      ./renderTrace --ppm frames --scale 8 --height 200 bubble.jsonl
+*/
+
+mod gif;
+mod op_counter;
+mod op_trace;
+#[cfg(test)]
+mod xorshift;
+
+use gif::{GifWriter, PALETTE_SIZE};
+use op_trace::Event;
+use std::convert::TryFrom;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+/// The size of a cell in pixels unless --scale is given.
+const DEFAULT_SCALE: usize = 4;
+
+/// The height of a frame in pixels unless --height is given.
+const DEFAULT_HEIGHT: usize = 128;
+
+/// How long each GIF frame is shown unless --delay is given, in 1/100 s.
+const DEFAULT_DELAY: u16 = 5;
+
+/// The palette index of the frame background.
+const BACKGROUND: u8 = 0;
+
+/// The palette index of positions touched by the step.
+const TOUCHED: u8 = 1;
+
+/// The palette index of the underline marking a merge or partition range.
+const RANGE: u8 = 2;
+
+/// The first palette index of the value gradient, which runs to 255.
+const GRADIENT_START: u8 = 3;
+
+/// The rows at the bottom of a frame kept for the range underline.
+const UNDERLINE_ROWS: usize = 3;
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Where to write the SVG timeline, if anywhere
+    svg: Option<String>,
+    /// The directory to write PPM frames into, if any
+    ppm: Option<String>,
+    /// Where to write the animated GIF, if anywhere
+    gif: Option<String>,
+    /// Keep every Nth step
+    every: usize,
+    /// The size of a cell in pixels
+    scale: usize,
+    /// The height of a frame in pixels
+    height: usize,
+    /// How long each GIF frame is shown, in hundredths of a second
+    delay: u16,
+    /// The trace file, or None to read standard input
+    path: Option<String>,
+}
+
+/// One kept step of a replayed trace.
+struct Frame {
+    /// The event of the step
+    event: Event,
+    /// The array after the event
+    array: Vec<i32>,
+}
+
+/// A trace replayed into the states it passes through.
+struct Replay {
+    /// The kept steps in order
+    frames: Vec<Frame>,
+    /// The number of steps in the whole trace
+    steps: usize,
+    /// The algorithm named by the start event
+    algorithm: String,
+    /// The smallest and largest input values
+    scale: (i32, i32),
+}
+
+impl Replay {
+    /// Reads a trace and replays it, keeping every `every`th step and the
+    /// last.
+    ///
+    /// # Arguments
+    /// * `reader` - The trace, one JSON event per line
+    /// * `every` - Keep steps 0, every, 2 * every, ...
+    ///
+    /// # Returns
+    /// * `io::Result<Replay>` - Each kept event with the array after it
+    ///
+    /// # Errors
+    /// * Returns an error if reading fails, if a line is not an event (the
+    ///   message gives the line number), or if there is nothing to draw
+    fn read<R: BufRead>(reader: R, every: usize) -> io::Result<Replay> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut frames = Vec::new();
+        let mut array = Vec::new();
+        let mut algorithm = String::new();
+        let mut scale = (0, 0);
+        let mut steps = 0;
+        // The latest event, if it was not kept
+        let mut pending = None;
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = Event::parse(&line).map_err(|e| {
+                invalid(&format!("line {} of the trace: {}", number + 1, e))
+            })?;
+
+            if steps == 0 {
+                let values = match event {
+                    Event::Start { algorithm: ref name, ref values } => {
+                        algorithm = name.clone();
+                        values
+                    }
+                    _ => return Err(invalid("the trace does not begin with a start event")),
+                };
+                if values.is_empty() {
+                    return Err(invalid("the traced array is empty"));
+                }
+                let min = values.iter().cloned().min().unwrap_or(0);
+                let max = values.iter().cloned().max().unwrap_or(0);
+                scale = (min, max);
+            }
+
+            event.apply(&mut array);
+            if steps % every == 0 {
+                frames.push(Frame {
+                    event,
+                    array: array.clone(),
+                });
+                pending = None;
+            } else {
+                pending = Some(event);
+            }
+            steps += 1;
+        }
+
+        if steps == 0 {
+            return Err(invalid("the trace does not begin with a start event"));
+        }
+        if let Some(event) = pending {
+            frames.push(Frame { event, array });
+        }
+
+        Ok(Replay {
+            frames,
+            steps,
+            algorithm,
+            scale,
+        })
+    }
+
+    /// Returns the number of positions in the array.
+    fn len(&self) -> usize {
+        self.frames[0].array.len()
+    }
+
+    /// Returns where a value lies between the smallest and largest input
+    /// values, from 0 to `levels`.
+    ///
+    /// Values a merge holds twice while it writes back are still input
+    /// values, so the result never leaves that range; it is clamped anyway.
+    fn level(&self, value: i32, levels: i64) -> i64 {
+        let (min, max) = self.scale;
+        if max == min {
+            return levels;
+        }
+        let span = i64::from(max) - i64::from(min);
+        let offset = (i64::from(value) - i64::from(min)).clamp(0, span);
+        offset * levels / span
+    }
+
+    /// Returns the palette index of a value on the gradient.
+    fn shade(&self, value: i32) -> u8 {
+        GRADIENT_START + self.level(value, i64::from(255 - GRADIENT_START)) as u8
+    }
+}
+
+/// Builds the palette: the background, the touched and range colors, and
+/// a gradient from blue through green and yellow to red.
+fn palette() -> [[u8; 3]; PALETTE_SIZE] {
+    let mut palette = [[0u8; 3]; PALETTE_SIZE];
+    palette[BACKGROUND as usize] = [255, 255, 255];
+    palette[TOUCHED as usize] = [0, 0, 0];
+    palette[RANGE as usize] = [150, 150, 150];
+
+    let levels = 255 - GRADIENT_START as usize;
+    for (level, color) in palette[GRADIENT_START as usize..].iter_mut().enumerate() {
+        // Hue runs from 240 degrees (blue) down to 0 (red) in four segments
+        let position = level * 4 * 255 / levels;
+        let ramp = (position % 255) as u8;
+        *color = match position / 255 {
+            0 => [0, ramp, 255],
+            1 => [0, 255, 255 - ramp],
+            2 => [ramp, 255, 0],
+            3 => [255, 255 - ramp, 0],
+            _ => [255, 0, 0],
+        };
+    }
+    palette
+}
+
+/// Returns the positions an event touched and the range it works on.
+fn touched(event: &Event) -> (Vec<usize>, Option<(usize, usize)>) {
+    let known = |positions: &[Option<usize>]| positions.iter().filter_map(|&i| i).collect();
+    match *event {
+        Event::Compare { i, j, .. } | Event::Swap { i, j, .. } => (known(&[i, j]), None),
+        Event::Write { i, .. } => (known(&[i]), None),
+        Event::Merge { left: Some(low), right: Some(high), .. }
+        | Event::Partition { low: Some(low), high: Some(high), .. } => {
+            (Vec::new(), Some((low, high)))
+        }
+        _ => (Vec::new(), None),
+    }
+}
+
+/// Draws the array after one kept step as bars.
+///
+/// Each position is a column `scale` pixels wide, with a bar from one
+/// pixel for the smallest value up to the full drawing area for the
+/// largest. The bottom rows are left for the underline of a range.
+///
+/// # Arguments
+/// * `replay` - The replayed trace, for its scale
+/// * `frame` - The step to draw
+/// * `scale` - The width of a column in pixels
+/// * `height` - The height of the frame in pixels
+///
+/// # Returns
+/// * `Vec<u8>` - One palette index per pixel, row by row
+fn draw_frame(replay: &Replay, frame: &Frame, scale: usize, height: usize) -> Vec<u8> {
+    let width = replay.len() * scale;
+    let bars = height - UNDERLINE_ROWS;
+    let (touched, range) = touched(&frame.event);
+    let mut pixels = vec![BACKGROUND; width * height];
+
+    for (index, &value) in frame.array.iter().enumerate() {
+        let bar = 1 + replay.level(value, bars as i64 - 1) as usize;
+        let color = if touched.contains(&index) { TOUCHED } else { replay.shade(value) };
+        for row in bars - bar..bars {
+            let start = row * width + index * scale;
+            pixels[start..start + scale].iter_mut().for_each(|pixel| *pixel = color);
+        }
+    }
+
+    if let Some((low, high)) = range {
+        let high = high.min(replay.len() - 1);
+        for row in height - UNDERLINE_ROWS + 1..height {
+            let start = row * width + low * scale;
+            let end = row * width + (high + 1) * scale;
+            pixels[start..end].iter_mut().for_each(|pixel| *pixel = RANGE);
+        }
+    }
+    pixels
+}
+
+/// Writes one frame as a binary PPM (P6) image.
+///
+/// # Arguments
+/// * `path` - The file to create
+/// * `width` - The width of the frame in pixels
+/// * `pixels` - One palette index per pixel, row by row
+/// * `palette` - The colors of the palette indices
+///
+/// # Errors
+/// * Returns an error if the file cannot be written
+fn write_ppm(
+    path: &Path,
+    width: usize,
+    pixels: &[u8],
+    palette: &[[u8; 3]; PALETTE_SIZE],
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write!(writer, "P6\n{} {}\n255\n", width, pixels.len() / width)?;
+    for &pixel in pixels {
+        writer.write_all(&palette[pixel as usize])?;
+    }
+    writer.flush()
+}
+
+/// Writes the timeline: one row per kept step, one cell per position.
+///
+/// Neighboring cells of the same color are drawn as one rectangle, and
+/// the rows of merge and partition events are outlined across their range.
+///
+/// # Arguments
+/// * `writer` - Where the SVG document is written
+/// * `replay` - The replayed trace, whose kept steps are the rows
+/// * `scale` - The size of a cell in pixels
+///
+/// # Errors
+/// * Returns an error if writing fails
+fn write_svg<W: Write>(writer: &mut W, replay: &Replay, scale: usize) -> io::Result<()> {
+    let palette = palette();
+    let (columns, rows) = (replay.len(), replay.frames.len());
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+        columns * scale,
+        rows * scale,
+        columns,
+        rows
+    )?;
+    writeln!(writer, "<title>{}: {} steps</title>", replay.algorithm, replay.steps)?;
+
+    for (row, frame) in replay.frames.iter().enumerate() {
+        let array = &frame.array;
+        let mut start = 0;
+        while start < array.len() {
+            let shade = replay.shade(array[start]);
+            let mut end = start + 1;
+            while end < array.len() && replay.shade(array[end]) == shade {
+                end += 1;
+            }
+            let [r, g, b] = palette[shade as usize];
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                start,
+                row,
+                end - start,
+                r,
+                g,
+                b
+            )?;
+            start = end;
+        }
+
+        if let (_, Some((low, high))) = touched(&frame.event) {
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"none\" \
+                 stroke=\"#000\" stroke-width=\"0.1\"/>",
+                low,
+                row,
+                high + 1 - low
+            )?;
+        }
+    }
+    writeln!(writer, "</svg>")
+}
+
+/// Renders the trace to every output the options ask for.
+///
+/// # Arguments
+/// * `replay` - The replayed trace, already thinned out by --every
+/// * `options` - The outputs and their sizes
+///
+/// # Errors
+/// * Returns an error if an output cannot be written, or if a GIF frame
+///   would exceed the 65535 pixels GIF allows per side
+fn render(replay: &Replay, options: &Options) -> io::Result<()> {
+    let palette = palette();
+    let width = replay.len() * options.scale;
+
+    if let Some(ref path) = options.svg {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_svg(&mut writer, replay, options.scale)?;
+        writer.flush()?;
+    }
+
+    if let Some(ref directory) = options.ppm {
+        fs::create_dir_all(directory)?;
+        for (number, frame) in replay.frames.iter().enumerate() {
+            let pixels = draw_frame(replay, frame, options.scale, options.height);
+            let path = Path::new(directory).join(format!("frame_{:05}.ppm", number));
+            write_ppm(&path, width, &pixels, &palette)?;
+        }
+    }
+
+    if let Some(ref path) = options.gif {
+        let too_large = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("a {}x{} frame is too large for a GIF", width, options.height),
+            )
+        };
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(options.height).map_err(|_| too_large())?;
+        let writer = BufWriter::new(File::create(path)?);
+        let mut gif = GifWriter::new(writer, gif_width, gif_height, &palette)?;
+        for frame in &replay.frames {
+            let pixels = draw_frame(replay, frame, options.scale, options.height);
+            gif.add_frame(&pixels, options.delay)?;
+        }
+        gif.finish()?;
+    }
+    Ok(())
+}
+
+/// Parses a positive integer option value.
+fn positive(flag: &str, value: Option<&String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("Invalid value for {}: {}", flag, value)),
+    }
+}
+
+/// Parses the command-line arguments.
+///
+/// Recognizes `--svg FILE`, `--ppm DIR`, `--gif FILE`, `--every N`,
+/// `--scale N`, `--height N` and `--delay N`, and at most one trace file.
+/// At least one output must be chosen. A frame must be tall enough for the
+/// range underline and a bar.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        svg: None,
+        ppm: None,
+        gif: None,
+        every: 1,
+        scale: DEFAULT_SCALE,
+        height: DEFAULT_HEIGHT,
+        delay: DEFAULT_DELAY,
+        path: None,
+    };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--svg" | "--ppm" | "--gif" => {
+                let value = iter.next().ok_or_else(|| format!("{} requires a value", arg))?;
+                let output = match arg.as_str() {
+                    "--svg" => &mut options.svg,
+                    "--ppm" => &mut options.ppm,
+                    _ => &mut options.gif,
+                };
+                *output = Some(value.to_string());
+            }
+            "--every" => options.every = positive(arg, iter.next())?,
+            "--scale" => options.scale = positive(arg, iter.next())?,
+            "--height" => options.height = positive(arg, iter.next())?,
+            "--delay" => {
+                let value = iter.next().ok_or("--delay requires a value")?;
+                options.delay = value
+                    .parse::<u16>()
+                    .map_err(|_| format!("Invalid delay: {}", value))?;
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {}", other));
+            }
+            path if options.path.is_none() => options.path = Some(path.to_string()),
+            path => return Err(format!("Unexpected argument: {} (only one trace)", path)),
+        }
+    }
+
+    if options.svg.is_none() && options.ppm.is_none() && options.gif.is_none() {
+        return Err("Nothing to render: pass --svg, --ppm or --gif".to_string());
+    }
+    if options.height <= UNDERLINE_ROWS + 1 {
+        return Err(format!("--height must be more than {}", UNDERLINE_ROWS + 1));
+    }
+
+    Ok(options)
+}
+
+/// Main entry point for the trace renderer.
+///
+/// Reads the trace from the given file or standard input and writes the
+/// requested images. Exits with status code 1 if any errors occur.
+fn main() {
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Read and replay the trace from the file, or from standard input
+    let stdin = io::stdin();
+    let every = options.every;
+    let replay = match options.path {
+        Some(ref path) => {
+            File::open(path).and_then(|file| Replay::read(BufReader::new(file), every))
+        }
+        None => Replay::read(stdin.lock(), every),
+    };
+    let replay = match replay {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!("Error reading trace: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Write the requested images
+    if let Err(e) = render(&replay, &options) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trace of a merge of [2, 1] into [1, 2], as mergeSort --trace writes it.
+    const TRACE: &str = r#"{"step":0,"op":"start","algorithm":"merge sort","values":[2,1]}
+{"step":1,"op":"merge","left":0,"mid":0,"right":1}
+{"step":2,"op":"compare","i":0,"j":1,"a":2,"b":1}
+{"step":3,"op":"write","i":0,"value":1}
+{"step":4,"op":"write","i":1,"value":2}
+{"step":5,"op":"end"}
+"#;
+
+    fn replay() -> Replay {
+        Replay::read(TRACE.as_bytes(), 1).unwrap()
+    }
+
+    /// Returns the steps a replay kept, found by their events.
+    fn kept(replay: &Replay) -> Vec<usize> {
+        let events: Vec<Event> = TRACE.lines().map(|line| Event::parse(line).unwrap()).collect();
+        let step = |frame: &Frame| events.iter().position(|event| *event == frame.event);
+        replay.frames.iter().map(|frame| step(frame).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_replay_tracks_every_step() {
+        let replay = replay();
+        assert_eq!((replay.steps, replay.frames.len()), (6, 6));
+        assert_eq!(replay.algorithm, "merge sort");
+        assert_eq!(replay.frames[0].array, vec![2, 1]);
+        assert_eq!(replay.frames[3].array, vec![1, 1]);
+        assert_eq!(replay.frames[5].array, vec![1, 2]);
+        assert_eq!(replay.frames[5].event, Event::End);
+        assert_eq!(replay.scale, (1, 2));
+        assert_eq!((replay.shade(1), replay.shade(2)), (GRADIENT_START, 255));
+
+        assert!(Replay::read(&b""[..], 1).is_err());
+        assert!(Replay::read(&b"{\"op\":\"end\"}\n"[..], 1).is_err());
+        let error = Replay::read(format!("{}oops\n", TRACE).as_bytes(), 1).err().unwrap();
+        assert!(error.to_string().starts_with("line 7 of the trace"), "{}", error);
+    }
+
+    #[test]
+    fn test_every_keeps_the_last_step() {
+        let every = |n: usize| Replay::read(TRACE.as_bytes(), n).unwrap();
+        assert_eq!(kept(&every(1)), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(kept(&every(5)), vec![0, 5]);
+
+        // The last step is kept even off the stride, with the final array
+        let replay = every(4);
+        assert_eq!(kept(&replay), vec![0, 4, 5]);
+        assert_eq!(replay.steps, 6);
+        assert_eq!(replay.frames[1].array, vec![1, 2]);
+        assert_eq!(replay.frames[2].array, vec![1, 2]);
+
+        // Steps between the kept ones still change the array
+        let short: String = TRACE.lines().take(5).map(|line| format!("{}\n", line)).collect();
+        let replay = Replay::read(short.as_bytes(), 4).unwrap();
+        assert_eq!(kept(&replay), vec![0, 4]);
+        assert_eq!(replay.frames[1].array, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_palette_runs_from_blue_to_red() {
+        let palette = palette();
+        assert_eq!(palette[BACKGROUND as usize], [255, 255, 255]);
+        assert_eq!(palette[GRADIENT_START as usize], [0, 0, 255]);
+        assert_eq!(palette[255], [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_draw_frame() {
+        let replay = replay();
+        let (scale, height) = (2, 6);
+        let width = 2 * scale;
+        let (low, high) = (replay.shade(1), replay.shade(2));
+
+        // Step 1 is the merge: value 2 fills the three bar rows, value 1
+        // one row, and the range is underlined below
+        let pixels = draw_frame(&replay, &replay.frames[1], scale, height);
+        let rows: Vec<&[u8]> = pixels.chunks(width).collect();
+        assert_eq!(rows[0], &[high, high, BACKGROUND, BACKGROUND]);
+        assert_eq!(rows[2], &[high, high, low, low]);
+        assert_eq!(rows[3], &[BACKGROUND; 4]);
+        assert_eq!(rows[4], &[RANGE; 4]);
+
+        // Step 2 compares both positions, which are drawn as touched
+        let pixels = draw_frame(&replay, &replay.frames[2], scale, height);
+        assert_eq!(&pixels[2 * width..3 * width], &[TOUCHED; 4]);
+        assert_eq!(&pixels[4 * width..5 * width], &[BACKGROUND; 4]);
+    }
+
+    #[test]
+    fn test_svg_timeline() {
+        let replay = replay();
+        let mut output = Vec::new();
+        write_svg(&mut output, &replay, 10).unwrap();
+        let svg = String::from_utf8(output).unwrap();
+
+        let header = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"60\"";
+        assert!(svg.starts_with(header));
+        assert!(svg.contains("<title>merge sort: 6 steps</title>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        // Row 0 has two cells of different colors; the merge row is outlined
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ff0000\"/>"));
+        assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"#0000ff\"/>"));
+        assert_eq!(svg.matches("stroke=").count(), 1);
+        assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"none\""));
+        // Step 3 holds [1, 1], one cell of a single color
+        assert!(svg.contains("<rect x=\"0\" y=\"3\" width=\"2\" height=\"1\" fill=\"#0000ff\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 2 + (2 + 1) + 2 + 1 + 2 + 2);
+    }
+
+    #[test]
+    fn test_render_writes_every_output() {
+        let directory = env::temp_dir().join(format!("render_trace_{}", process::id()));
+        let path = |name: &str| directory.join(name).to_string_lossy().into_owned();
+        fs::create_dir_all(&directory).unwrap();
+        let options = Options {
+            svg: Some(path("timeline.svg")),
+            ppm: Some(path("frames")),
+            gif: Some(path("sort.gif")),
+            every: 2,
+            scale: 3,
+            height: 10,
+            delay: 1,
+            path: None,
+        };
+        render(&Replay::read(TRACE.as_bytes(), options.every).unwrap(), &options).unwrap();
+
+        // Steps 0, 2, 4 and the last, 5
+        let frames = fs::read_dir(path("frames")).unwrap().count();
+        assert_eq!(frames, 4);
+        let ppm = fs::read(directory.join("frames").join("frame_00003.ppm")).unwrap();
+        let header = b"P6\n6 10\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 10 * 3);
+
+        let gif = fs::read(path("sort.gif")).unwrap();
+        assert_eq!(&gif[..10], b"GIF89a\x06\x00\x0a\x00");
+        assert_eq!(gif.last(), Some(&0x3B));
+        assert!(fs::read_to_string(path("timeline.svg")).unwrap().contains("<svg"));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&["--svg", "t.svg", "trace.jsonl", "--every", "3"])),
+            Ok(Options {
+                svg: Some("t.svg".to_string()),
+                ppm: None,
+                gif: None,
+                every: 3,
+                scale: DEFAULT_SCALE,
+                height: DEFAULT_HEIGHT,
+                delay: DEFAULT_DELAY,
+                path: Some("trace.jsonl".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--gif", "a.gif", "--ppm", "out", "--delay", "0"]))
+                .map(|o| (o.gif, o.ppm, o.delay)),
+            Ok((Some("a.gif".to_string()), Some("out".to_string()), 0))
+        );
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["trace.jsonl"])).is_err());
+        assert!(parse_args(&args(&["--svg"])).is_err());
+        assert!(parse_args(&args(&["--svg", "a", "--every", "0"])).is_err());
+        assert!(parse_args(&args(&["--gif", "a", "--height", "4"])).is_err());
+        assert!(parse_args(&args(&["--gif", "a", "--delay", "-1"])).is_err());
+        assert!(parse_args(&args(&["--gif", "a", "x.jsonl", "y.jsonl"])).is_err());
+        assert!(parse_args(&args(&["--png", "a"])).is_err());
+    }
+}
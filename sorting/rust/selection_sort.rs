@@ -11,14 +11,79 @@
       result to standard output (one integer per line). Designed for use with
       input/output redirection and benchmarking.
 
+      Passing --double switches to the double-ended variant, which selects
+      both the minimum and the maximum on each pass and places one at each
+      end of the unsorted region.
+
+      Passing --indices prints the original line number of each value in
+      sorted order instead of the values, for joining the result back to
+      other columns of the input. Selection sort is not stable, so lines
+      with equal values may come out in any order. It replaces the output,
+      so it cannot be combined with --stats, --trace, --sound or --timings.
+
+      Passing --stats prints the number of comparisons and swaps to
+      stderr. The comparisons depend only on the input length, while the
+      swaps depend on how many elements start out of place.
+
+      Passing --trace writes the comparisons of each scan and the swap
+      that ends it to stderr as JSON Lines (see opTrace.rs); --trace=FILE
+      writes them to FILE instead. It works with --double too.
+
+      Passing --sound=FILE writes the sound of the sort to FILE as a WAV
+      file (see opSound.rs), one tone per value compared or swapped. The
+      scan for each minimum is heard as a run of tones that settles on
+      lower and lower pitches as the sorted region grows.
+
+      Passing --timings times reading, sorting and printing separately and
+      reports the three to stderr (see phaseTimer.rs), for either variant.
+      It cannot be combined with --stats, --trace or --sound, whose
+      recording would be timed with the sort.
+
    Usage:
       ./selectionSort < input.txt > output.txt
+      ./selectionSort --double < input.txt > output.txt
+      ./selectionSort --indices < input.txt > order.txt
+      ./selectionSort --double --stats < input.txt > output.txt
+      ./selectionSort --trace=trace.jsonl < input.txt > output.txt
+      ./selectionSort --double --sound=selection.wav < input.txt > output.txt
+      ./selectionSort --timings < input.txt > output.txt
       cat input.txt | ./selectionSort
 */
 
+mod argsort;
+mod op_counter;
+mod op_sound;
+mod op_trace;
+mod phase_timer;
+#[cfg(test)]
+mod xorshift;
+
+use argsort::{argsort_with, print_line_numbers, read_input_with_lines, Tagged};
+use op_counter::{count, Tally};
+use op_trace::Target;
+use phase_timer::PhaseTimer;
+use std::env;
+use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 use std::process;
 
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Whether to use the double-ended variant
+    double: bool,
+    /// Whether to print original line numbers instead of sorted values
+    indices: bool,
+    /// Whether to report operation counts for the sort to stderr
+    stats: bool,
+    /// Where to write a trace of the sort's operations, if anywhere
+    trace: Option<Target>,
+    /// Where to write the sound of the sort's array accesses, if anywhere
+    sound: Option<String>,
+    /// Whether to report the time of each phase to stderr
+    timings: bool,
+}
+
 /// Reads integers from standard input, one per line.
 ///
 /// Uses a buffered reader for efficient I/O when handling large input files.
@@ -91,8 +156,8 @@ fn read_input() -> io::Result<Vec<i32>> {
 /// * Poor performance on large datasets
 ///
 /// # Arguments
-/// * `numbers` - A mutable slice of i32 integers to sort
-fn selection_sort(numbers: &mut [i32]) {
+/// * `numbers` - A mutable slice of values to sort
+fn selection_sort<T: Tally>(numbers: &mut [T]) {
     let n = numbers.len();
     
     // Iterate through the array, expanding the sorted region
@@ -110,7 +175,108 @@ fn selection_sort(numbers: &mut [i32]) {
         // Only swap if necessary (optimization)
         if min_index != i {
             numbers.swap(i, min_index);
+            T::swapped(&numbers[i], &numbers[min_index]);
+        }
+    }
+}
+
+/// Sorts a slice of integers in ascending order using Double Selection Sort.
+///
+/// A double-ended variant of Selection Sort. The unsorted region is bounded
+/// on both sides by sorted regions:
+/// * **Left sorted region**: Elements from index 0 to left-1 (the smallest values)
+/// * **Unsorted region**: Elements from index left to right
+/// * **Right sorted region**: Elements from index right+1 to n-1 (the largest values)
+///
+/// For each pass:
+/// 1. Scan the unsorted region once, tracking both the minimum and the maximum
+/// 2. Swap the minimum to the left end of the unsorted region
+/// 3. Swap the maximum to the right end of the unsorted region
+/// 4. Both sorted regions grow by one element
+///
+/// Care is needed when the maximum sits at the left end: the first swap moves
+/// it to the old position of the minimum, so the maximum index is redirected
+/// there before the second swap.
+///
+/// # Time Complexity
+/// * Best case: O(n²) - about n/2 passes, each scanning the unsorted region
+/// * Average case: O(n²)
+/// * Worst case: O(n²)
+///
+/// The number of passes is halved compared to `selection_sort`, but the
+/// comparisons are not: every element that is not a new minimum is also
+/// compared with the maximum, so a scan costs up to two comparisons per
+/// element and the total stays close to n²/2.
+///
+/// # Space Complexity
+/// * O(1) - sorts in place with only a constant amount of extra space
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+fn double_selection_sort<T: Tally>(numbers: &mut [T]) {
+    let n = numbers.len();
+    if n < 2 {
+        return;
+    }
+
+    let mut left = 0;
+    let mut right = n - 1;
+
+    // Shrink the unsorted region from both ends until it is empty or a single element
+    while left < right {
+        let mut min_index = left;
+        let mut max_index = left;
+
+        // Find both extremes of the unsorted region in a single scan
+        for j in (left + 1)..=right {
+            if numbers[j] < numbers[min_index] {
+                min_index = j;
+            } else if numbers[j] > numbers[max_index] {
+                max_index = j;
+            }
+        }
+
+        // Place the minimum at the left end
+        if min_index != left {
+            numbers.swap(left, min_index);
+            T::swapped(&numbers[left], &numbers[min_index]);
+        }
+
+        // If the maximum was at the left end, the swap above moved it to min_index
+        if max_index == left {
+            max_index = min_index;
         }
+
+        // Place the maximum at the right end
+        if max_index != right {
+            numbers.swap(right, max_index);
+            T::swapped(&numbers[right], &numbers[max_index]);
+        }
+
+        left += 1;
+        right -= 1;
+    }
+}
+
+// Tagged values are sorted for --indices, where nothing is counted
+impl Tally for Tagged {}
+
+/// Returns the permutation that sorts `numbers`, computed with selection sort.
+///
+/// Selection sort is not stable: swapping the minimum forward can carry an
+/// element past an equal one, so equal values may come out in any order.
+///
+/// # Arguments
+/// * `numbers` - The values to sort; they are not modified
+/// * `double` - Whether to use `double_selection_sort`
+///
+/// # Returns
+/// * `Vec<usize>` - Entry i is the original index of the i-th smallest value
+fn argsort(numbers: &[i32], double: bool) -> Vec<usize> {
+    if double {
+        argsort_with(numbers, double_selection_sort)
+    } else {
+        argsort_with(numbers, selection_sort)
     }
 }
 
@@ -138,12 +304,107 @@ fn print_output(numbers: &[i32]) -> io::Result<()> {
     Ok(())
 }
 
+/// Parses the command-line arguments.
+///
+/// Recognizes `--double`, `--indices`, `--stats`, `--trace[=FILE]`,
+/// `--sound=FILE` and `--timings`; anything else is an error. `--double`
+/// combines with all of them. `--indices` replaces the output, so it cannot
+/// be combined with any of the others. `--trace` and `--sound` record a
+/// plain sort, so neither can be combined with `--stats` or with each
+/// other. `--timings` times a plain sort too, so it cannot be combined with
+/// `--stats`, `--trace` or `--sound`.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        double: false,
+        indices: false,
+        stats: false,
+        trace: None,
+        sound: None,
+        timings: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--double" => options.double = true,
+            "--indices" => options.indices = true,
+            "--stats" => options.stats = true,
+            "--timings" => options.timings = true,
+            other => {
+                if let Some(target) = Target::from_arg(other) {
+                    options.trace = Some(target);
+                } else if let Some(path) = op_sound::from_arg(other) {
+                    options.sound = Some(path);
+                } else {
+                    return Err(format!("Unknown argument: {}", other));
+                }
+            }
+        }
+    }
+
+    if options.stats && options.indices {
+        return Err("--stats cannot be combined with --indices".to_string());
+    }
+
+    if options.trace.is_some() && (options.indices || options.stats) {
+        return Err("--trace cannot be combined with --stats or --indices".to_string());
+    }
+
+    let recorded = options.stats || options.trace.is_some();
+    if options.sound.is_some() && (options.indices || recorded) {
+        return Err("--sound cannot be combined with --stats, --trace or --indices".to_string());
+    }
+
+    let recorded = recorded || options.sound.is_some();
+    if options.timings && (options.indices || recorded) {
+        return Err(
+            "--timings cannot be combined with --stats, --trace, --sound or --indices"
+                .to_string(),
+        );
+    }
+
+    Ok(options)
+}
+
 /// Main entry point for the selection sort program.
 ///
 /// Orchestrates reading from stdin, sorting, and writing to stdout.
 /// Exits with status code 1 if any errors occur during execution.
 fn main() {
-    // Read integers from standard input
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    let double = options.double;
+
+    // Print the original line numbers in sorted order instead of the values
+    if options.indices {
+        let (numbers, lines) = match read_input_with_lines() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = print_line_numbers(&argsort(&numbers, double), &lines) {
+            eprintln!("Error writing output: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
     let mut numbers = match read_input() {
         Ok(nums) => nums,
         Err(e) => {
@@ -151,20 +412,68 @@ fn main() {
             process::exit(1);
         }
     };
+    timer.lap("read_input");
+
+    // Sort the numbers using selection sort, tracing, playing or counting operations if asked
+    if let Some(target) = options.trace {
+        let written = target.open().and_then(|writer| {
+            if double {
+                let name = "double selection sort";
+                op_trace::record(&mut numbers, name, writer, double_selection_sort)
+            } else {
+                op_trace::record(&mut numbers, "selection sort", writer, selection_sort)
+            }
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing trace: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(path) = options.sound {
+        let written = File::create(&path).and_then(|file| {
+            let writer = BufWriter::new(file);
+            if double {
+                op_sound::record(&mut numbers, writer, double_selection_sort)
+            } else {
+                op_sound::record(&mut numbers, writer, selection_sort)
+            }
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing sound: {}", e);
+            process::exit(1);
+        }
+    } else if options.stats {
+        let (counts, name) = if double {
+            (count(&mut numbers, double_selection_sort), "double selection sort")
+        } else {
+            (count(&mut numbers, selection_sort), "selection sort")
+        };
+        eprint!("{}", counts.summary(name, numbers.len()));
+    } else if double {
+        double_selection_sort(&mut numbers);
+    } else {
+        selection_sort(&mut numbers);
+    }
 
-    // Sort the numbers using selection sort
-    selection_sort(&mut numbers);
+    timer.lap("sort");
 
     // Print the sorted numbers to standard output
     if let Err(e) = print_output(&numbers) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if options.timings {
+        let name = if double { "double selection sort" } else { "selection sort" };
+        eprint!("{}", timer.summary(name, numbers.len()));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use xorshift::XorShift64;
 
     #[test]
     fn test_empty_array() {
@@ -258,4 +567,229 @@ mod tests {
         selection_sort(&mut arr);
         assert_eq!(arr, vec![1, 2, 3, 4, 5]);
     }
+
+    #[test]
+    fn test_double_empty_and_single() {
+        let mut arr: Vec<i32> = vec![];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+
+        let mut arr = vec![42];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_double_odd_length() {
+        let mut arr = vec![9, 4, 7, 1, 8, 2, 5];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 4, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_double_even_length() {
+        let mut arr = vec![3, -1, 6, 0, 10, -7, 2, 2];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![-7, -1, 0, 2, 2, 3, 6, 10]);
+    }
+
+    #[test]
+    fn test_double_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7, 7];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_double_max_at_min_destination() {
+        // The maximum sits at index 0, exactly where the minimum is swapped into
+        let mut arr = vec![9, 3, 5, 1, 7];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![1, 3, 5, 7, 9]);
+
+        // Minimum and maximum trade places on the first pass
+        let mut arr = vec![5, 2, 3, 4, 1];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+
+        let mut arr = vec![2, 1];
+        double_selection_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_double_matches_selection_sort() {
+        // Deterministic pseudo-random inputs of every length from 0 to 64
+        let mut rng = XorShift64::new(12345);
+        for len in 0..=64 {
+            let arr: Vec<i32> = rng.values(len, -10..10);
+            let mut expected = arr.clone();
+            selection_sort(&mut expected);
+            let mut actual = arr;
+            double_selection_sort(&mut actual);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_argsort_sorts() {
+        let numbers = vec![4, -2, 9, 4, 0, -2, 7];
+        for &double in &[false, true] {
+            let order = argsort(&numbers, double);
+            let values: Vec<i32> = order.iter().map(|&i| numbers[i]).collect();
+            assert_eq!(values, vec![-2, -2, 0, 4, 4, 7, 9]);
+
+            // Every index appears exactly once
+            let mut seen = order.clone();
+            seen.sort();
+            assert_eq!(seen, (0..numbers.len()).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn test_argsort_is_not_stable() {
+        // The first swap moves index 0 behind the other 2
+        assert_eq!(argsort(&[2, 2, 1], false), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let n = 64;
+        let random = XorShift64::new(99).values(n as usize, 0..1 << 16);
+        let sorted: Vec<i32> = (0..n).collect();
+
+        // Always n(n-1)/2 comparisons; no swaps at all on sorted input
+        let pairs = (n * (n - 1) / 2) as u64;
+        let counts = count(&mut sorted.clone(), selection_sort);
+        assert_eq!((counts.comparisons, counts.swaps), (pairs, 0));
+        let counts = count(&mut random.clone(), selection_sort);
+        assert_eq!(counts.comparisons, pairs);
+        assert!(counts.swaps < n as u64);
+
+        // The double-ended scan compares most elements with both the
+        // minimum and the maximum, so it saves passes but not comparisons
+        let counts = count(&mut random.clone(), double_selection_sort);
+        assert!(counts.comparisons > pairs * 9 / 10, "{:?}", counts);
+        assert!(counts.comparisons <= 2 * pairs);
+    }
+
+    /// Traces a sort of a fixed input and checks the trace against its stats.
+    fn check_trace(
+        name: &str,
+        traced_sort: fn(&mut [op_trace::Traced]),
+        counted_sort: fn(&mut [op_counter::Counted]),
+    ) {
+        use op_trace::Event;
+        use std::fs;
+
+        let path = env::temp_dir().join(format!("{}_trace_{}.jsonl", name, process::id()));
+        let target = Target::File(path.to_string_lossy().into_owned());
+        let mut numbers = vec![5, 2, 9, 1, 7, 3];
+        let writer = target.open().unwrap();
+        op_trace::record(&mut numbers, name, writer, traced_sort).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 5, 7, 9]);
+
+        // The running extremes stay in the array, so every index is known
+        let counts = count(&mut [5, 2, 9, 1, 7, 3], counted_sort);
+        let (mut compares, mut swaps) = (0, 0);
+        let mut array = Vec::new();
+        for line in text.lines() {
+            let event = Event::parse(line).unwrap();
+            match event {
+                Event::Compare { i, j, .. } => {
+                    assert!(i.is_some() && j.is_some(), "{}", line);
+                    compares += 1;
+                }
+                Event::Swap { .. } => swaps += 1,
+                _ => {}
+            }
+            event.apply(&mut array);
+        }
+        assert_eq!((compares, swaps), (counts.comparisons, counts.swaps), "{}", name);
+        assert_eq!(array, numbers);
+    }
+
+    #[test]
+    fn test_trace_matches_stats() {
+        check_trace("selection", selection_sort, selection_sort);
+        check_trace("double_selection", double_selection_sort, double_selection_sort);
+    }
+
+    #[test]
+    fn test_sound_has_a_tone_per_access() {
+        let mut numbers = vec![5, 1, 4, 2, 3];
+        let mut wav = Vec::new();
+        op_sound::record(&mut numbers, &mut wav, double_selection_sort).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+
+        // A comparison accesses two values and so does a swap
+        let counts = count(&mut [5, 1, 4, 2, 3], double_selection_sort);
+        let accesses = (2 * counts.comparisons + 2 * counts.swaps) as usize;
+        let samples = accesses * op_sound::tone_length(accesses);
+        assert_eq!(wav.len(), 44 + 2 * samples);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                double: false,
+                indices: false,
+                stats: false,
+                trace: None,
+                sound: None,
+                timings: false,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--double", "--trace=out.jsonl"])).map(|o| o.trace),
+            Ok(Some(Target::File("out.jsonl".to_string())))
+        );
+        assert_eq!(
+            parse_args(&args(&["--sound=out.wav"])).map(|o| o.sound),
+            Ok(Some("out.wav".to_string()))
+        );
+        assert_eq!(
+            parse_args(&args(&["--double", "--indices"])).map(|o| (o.double, o.indices)),
+            Ok((true, true))
+        );
+        assert_eq!(
+            parse_args(&args(&["--timings"])).map(|o| o.timings),
+            Ok(true)
+        );
+
+        // --indices replaces the output, and the others each record or time
+        // a plain sort
+        for flags in [
+            ["--indices", "--stats"],
+            ["--indices", "--trace"],
+            ["--indices", "--sound=out.wav"],
+            ["--indices", "--timings"],
+            ["--stats", "--trace"],
+            ["--trace", "--sound=out.wav"],
+            ["--sound=out.wav", "--stats"],
+            ["--timings", "--stats"],
+            ["--trace=out.jsonl", "--timings"],
+            ["--timings", "--sound=out.wav"],
+        ]
+        .iter()
+        {
+            let error = parse_args(&args(flags)).unwrap_err();
+            assert!(error.contains("cannot be combined"), "{:?}: {}", flags, error);
+        }
+
+        // Misspelled flags are rejected rather than ignored
+        assert_eq!(
+            parse_args(&args(&["--stat"])),
+            Err("Unknown argument: --stat".to_string())
+        );
+        assert!(parse_args(&args(&["input.txt"])).is_err());
+    }
 }
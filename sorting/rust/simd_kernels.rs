@@ -0,0 +1,653 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: simdKernels.rs
+
+   Description:
+      This module provides vectorized building blocks for sorting i32
+      values on x86_64: a bitonic sorting network for up to 16 elements
+      held in vector registers, and a partition that compares a whole
+      vector against the pivot at once and moves the smaller and larger
+      elements to opposite ends of the range with a single shuffle.
+
+      Each kernel has an AVX2 version (8 lanes), an SSE4.1 version (4
+      lanes) and a portable scalar version that performs the same steps
+      one element at a time. The best kernel the processor supports is
+      picked at run time with `Kernel::detect`, so the same binary runs on
+      any x86_64 machine and on other architectures, where only the scalar
+      kernel exists.
+
+   Usage:
+      mod simd_kernels;
+      use simd_kernels::{Kernel, SIMD_NETWORK_SIZE};
+*/
+
+// Programs that include this module don't necessarily use every method
+#![allow(dead_code)]
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// The largest slice `Kernel::sort_small` accepts.
+pub const SIMD_NETWORK_SIZE: usize = 16;
+
+/// The (k, j) steps of the bitonic network on 16 elements. In each step,
+/// element i is compare-exchanged with element i ^ j, ascending if
+/// i & k == 0 and descending otherwise.
+const STAGES: [(usize, usize); 10] = [
+    (2, 1),
+    (4, 2),
+    (4, 1),
+    (8, 4),
+    (8, 2),
+    (8, 1),
+    (16, 8),
+    (16, 4),
+    (16, 2),
+    (16, 1),
+];
+
+/// For every step in `STAGES`, -1 for the elements that keep the larger
+/// value of their pair and 0 for those that keep the smaller one.
+const MAX_MASKS: [[i32; SIMD_NETWORK_SIZE]; 10] = build_max_masks();
+
+/// Shuffle indices for the AVX2 partition, indexed by the bit mask of
+/// lanes greater than the pivot. Each entry lists the other lanes first,
+/// in order, followed by the greater lanes.
+const PARTITION_LANES_8: [[i32; 8]; 256] = build_partition_lanes_8();
+
+/// Byte shuffles for the SSE4.1 partition, laid out like
+/// `PARTITION_LANES_8` but for 4 lanes of 4 bytes.
+const PARTITION_BYTES_4: [[u8; 16]; 16] = build_partition_bytes_4();
+
+/// Builds `MAX_MASKS` from `STAGES`.
+const fn build_max_masks() -> [[i32; SIMD_NETWORK_SIZE]; 10] {
+    let mut masks = [[0; SIMD_NETWORK_SIZE]; 10];
+    let mut stage = 0;
+    while stage < STAGES.len() {
+        let (k, j) = STAGES[stage];
+        let mut i = 0;
+        while i < SIMD_NETWORK_SIZE {
+            let ascending = i & k == 0;
+            let upper = i & j != 0;
+            if upper == ascending {
+                masks[stage][i] = -1;
+            }
+            i += 1;
+        }
+        stage += 1;
+    }
+    masks
+}
+
+/// Builds `PARTITION_LANES_8`.
+const fn build_partition_lanes_8() -> [[i32; 8]; 256] {
+    let mut table = [[0; 8]; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut next = 0;
+        let mut greater = 0;
+        while greater < 2 {
+            let mut lane: usize = 0;
+            while lane < 8 {
+                if (mask >> lane) & 1 == greater {
+                    table[mask][next] = lane as i32;
+                    next += 1;
+                }
+                lane += 1;
+            }
+            greater += 1;
+        }
+        mask += 1;
+    }
+    table
+}
+
+/// Builds `PARTITION_BYTES_4`.
+const fn build_partition_bytes_4() -> [[u8; 16]; 16] {
+    let mut table = [[0; 16]; 16];
+    let mut mask = 0;
+    while mask < 16 {
+        let mut next = 0;
+        let mut greater = 0;
+        while greater < 2 {
+            let mut lane = 0;
+            while lane < 4 {
+                if (mask >> lane) & 1 == greater {
+                    let mut byte = 0;
+                    while byte < 4 {
+                        table[mask][4 * next + byte] = (4 * lane + byte) as u8;
+                        byte += 1;
+                    }
+                    next += 1;
+                }
+                lane += 1;
+            }
+            greater += 1;
+        }
+        mask += 1;
+    }
+    table
+}
+
+/// An instruction set to run the sorting kernels with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kernel {
+    /// 256-bit vectors of 8 lanes
+    Avx2,
+    /// 128-bit vectors of 4 lanes
+    Sse41,
+    /// Plain Rust, one element at a time
+    Scalar,
+}
+
+impl Kernel {
+    /// Every kernel, from widest to the scalar fallback.
+    pub const ALL: [Kernel; 3] = [Kernel::Avx2, Kernel::Sse41, Kernel::Scalar];
+
+    /// Returns the widest kernel the current processor supports.
+    pub fn detect() -> Kernel {
+        Kernel::ALL
+            .iter()
+            .copied()
+            .find(|kernel| kernel.is_available())
+            .unwrap_or(Kernel::Scalar)
+    }
+
+    /// Returns true if the current processor can run this kernel.
+    ///
+    /// Feature detection is cached by the standard library, so this is
+    /// cheap enough to call before every kernel invocation.
+    pub fn is_available(self) -> bool {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse41 => is_x86_feature_detected!("sse4.1"),
+            Kernel::Scalar => true,
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+
+    /// Returns a short name for this kernel.
+    pub fn name(self) -> &'static str {
+        match self {
+            Kernel::Avx2 => "avx2",
+            Kernel::Sse41 => "sse4.1",
+            Kernel::Scalar => "scalar",
+        }
+    }
+
+    /// Sorts a slice of up to `SIMD_NETWORK_SIZE` integers with a bitonic
+    /// sorting network.
+    ///
+    /// The values are padded with `i32::MAX` to 16, sorted in registers and
+    /// the first `numbers.len()` are copied back. Falls back to the scalar
+    /// kernel if the processor doesn't support this one.
+    ///
+    /// # Time Complexity
+    /// * O(1) - always 80 compare-exchanges, 8 or 4 at a time
+    ///
+    /// # Arguments
+    /// * `numbers` - The slice to sort
+    ///
+    /// # Panics
+    /// * If `numbers` has more than `SIMD_NETWORK_SIZE` elements
+    pub fn sort_small(self, numbers: &mut [i32]) {
+        let len = numbers.len();
+        assert!(len <= SIMD_NETWORK_SIZE, "too many elements for the network");
+        if len <= 1 {
+            return;
+        }
+
+        let mut values = [i32::MAX; SIMD_NETWORK_SIZE];
+        values[..len].copy_from_slice(numbers);
+
+        match self {
+            // Safe because the processor was just checked for the feature
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 if self.is_available() => unsafe { sort16_avx2(&mut values) },
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse41 if self.is_available() => unsafe { sort16_sse41(&mut values) },
+            _ => sort16_scalar(&mut values),
+        }
+
+        numbers.copy_from_slice(&values[..len]);
+    }
+
+    /// Partitions a slice around its last element, like Lomuto's partition.
+    ///
+    /// Afterwards every element before the returned index is less than or
+    /// equal to the pivot, the pivot is at the returned index, and every
+    /// element after it is greater. The order within each side is not
+    /// specified and may differ between kernels.
+    ///
+    /// The vector kernels load one vector at a time from whichever end of
+    /// the range has less free space, compare it with the pivot in one
+    /// instruction, and shuffle it so the smaller lanes come first. The
+    /// shuffled vector is stored at both write fronts; each front keeps only
+    /// its own lanes and the rest is overwritten later. Two vectors are read
+    /// ahead so the stores never overwrite unread elements.
+    ///
+    /// # Time Complexity
+    /// * O(n) with no data-dependent branches in the vector loop
+    ///
+    /// # Space Complexity
+    /// * O(1)
+    ///
+    /// # Arguments
+    /// * `numbers` - The slice to partition, with the pivot as its last element
+    ///
+    /// # Returns
+    /// * `usize` - The final index of the pivot
+    ///
+    /// # Panics
+    /// * If `numbers` is empty
+    pub fn partition(self, numbers: &mut [i32]) -> usize {
+        let last = numbers.len() - 1;
+        let pivot = numbers[last];
+        let body = &mut numbers[..last];
+
+        let split = match self {
+            // Safe because the processor was just checked for the feature,
+            // and each kernel needs at least two vectors of input
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx2 if body.len() >= 16 && self.is_available() => unsafe {
+                partition_avx2(body, pivot)
+            },
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse41 if body.len() >= 8 && self.is_available() => unsafe {
+                partition_sse41(body, pivot)
+            },
+            _ => partition_scalar(body, pivot),
+        };
+
+        numbers.swap(split, last);
+        split
+    }
+}
+
+/// Runs the bitonic network on 16 values one compare-exchange at a time.
+///
+/// # Arguments
+/// * `values` - The values to sort
+fn sort16_scalar(values: &mut [i32; SIMD_NETWORK_SIZE]) {
+    for &(k, j) in STAGES.iter() {
+        for i in 0..SIMD_NETWORK_SIZE {
+            let partner = i ^ j;
+            if partner > i {
+                let ascending = i & k == 0;
+                if (values[i] > values[partner]) == ascending {
+                    values.swap(i, partner);
+                }
+            }
+        }
+    }
+}
+
+/// Partitions around a pivot one element at a time.
+///
+/// # Arguments
+/// * `numbers` - The slice to partition
+/// * `pivot` - The value to partition around
+///
+/// # Returns
+/// * `usize` - The number of elements less than or equal to the pivot
+fn partition_scalar(numbers: &mut [i32], pivot: i32) -> usize {
+    let mut store = 0;
+    for j in 0..numbers.len() {
+        if numbers[j] <= pivot {
+            numbers.swap(store, j);
+            store += 1;
+        }
+    }
+    store
+}
+
+/// Runs the bitonic network on 16 values in two 8-lane AVX2 registers.
+///
+/// Steps with j < 8 pair lanes within a register: a lane permute brings
+/// each partner alongside, and a blend keeps the minimum or maximum as
+/// `MAX_MASKS` says. The j = 8 step pairs the two registers lane by lane.
+///
+/// # Safety
+/// The processor must support AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn sort16_avx2(values: &mut [i32; SIMD_NETWORK_SIZE]) {
+    let ptr = values.as_mut_ptr() as *mut __m256i;
+    let mut regs = [_mm256_loadu_si256(ptr), _mm256_loadu_si256(ptr.add(1))];
+    let lanes = _mm256_setr_epi32(0, 1, 2, 3, 4, 5, 6, 7);
+
+    for (stage, &(_, j)) in STAGES.iter().enumerate() {
+        if j == 8 {
+            // The last merge is ascending throughout
+            let low = _mm256_min_epi32(regs[0], regs[1]);
+            regs[1] = _mm256_max_epi32(regs[0], regs[1]);
+            regs[0] = low;
+            continue;
+        }
+
+        let partners = _mm256_xor_si256(lanes, _mm256_set1_epi32(j as i32));
+        for (r, reg) in regs.iter_mut().enumerate() {
+            let other = _mm256_permutevar8x32_epi32(*reg, partners);
+            let mask_ptr = MAX_MASKS[stage][8 * r..].as_ptr() as *const __m256i;
+            *reg = _mm256_blendv_epi8(
+                _mm256_min_epi32(*reg, other),
+                _mm256_max_epi32(*reg, other),
+                _mm256_loadu_si256(mask_ptr),
+            );
+        }
+    }
+
+    _mm256_storeu_si256(ptr, regs[0]);
+    _mm256_storeu_si256(ptr.add(1), regs[1]);
+}
+
+/// Runs the bitonic network on 16 values in four 4-lane SSE registers.
+///
+/// Steps with j < 4 pair lanes within a register with a fixed shuffle;
+/// steps with j >= 4 pair whole registers, and the direction is the same
+/// for every lane of a register.
+///
+/// # Safety
+/// The processor must support SSE4.1.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn sort16_sse41(values: &mut [i32; SIMD_NETWORK_SIZE]) {
+    let ptr = values.as_mut_ptr() as *mut __m128i;
+    let mut regs = [
+        _mm_loadu_si128(ptr),
+        _mm_loadu_si128(ptr.add(1)),
+        _mm_loadu_si128(ptr.add(2)),
+        _mm_loadu_si128(ptr.add(3)),
+    ];
+
+    for (stage, &(k, j)) in STAGES.iter().enumerate() {
+        if j >= 4 {
+            let step = j / 4;
+            for r in (0..4).filter(|r| r & step == 0) {
+                let low = _mm_min_epi32(regs[r], regs[r + step]);
+                let high = _mm_max_epi32(regs[r], regs[r + step]);
+                let ascending = (4 * r) & k == 0;
+                regs[r] = if ascending { low } else { high };
+                regs[r + step] = if ascending { high } else { low };
+            }
+            continue;
+        }
+
+        for (r, reg) in regs.iter_mut().enumerate() {
+            let other = if j == 1 {
+                _mm_shuffle_epi32(*reg, 0b10_11_00_01)
+            } else {
+                _mm_shuffle_epi32(*reg, 0b01_00_11_10)
+            };
+            let mask_ptr = MAX_MASKS[stage][4 * r..].as_ptr() as *const __m128i;
+            *reg = _mm_blendv_epi8(
+                _mm_min_epi32(*reg, other),
+                _mm_max_epi32(*reg, other),
+                _mm_loadu_si128(mask_ptr),
+            );
+        }
+    }
+
+    for (r, &reg) in regs.iter().enumerate() {
+        _mm_storeu_si128(ptr.add(r), reg);
+    }
+}
+
+/// Defines a vector partition function for one instruction set.
+///
+/// The caller provides the vector type, the number of lanes, and a
+/// `$split` function that compares a vector with the splatted pivot and
+/// returns it shuffled (smaller lanes first) with the number of greater
+/// lanes.
+#[cfg(target_arch = "x86_64")]
+macro_rules! vector_partition {
+    (
+        $name:ident,
+        $feature:tt,
+        $lanes:expr,
+        $splat:ident,
+        $load:ident,
+        $store:ident,
+        $split:ident
+    ) => {
+        /// Partitions around a pivot a whole vector at a time.
+        ///
+        /// # Returns
+        /// * `usize` - The number of elements less than or equal to the pivot
+        ///
+        /// # Safety
+        /// The processor must support the instruction set, and `numbers`
+        /// must hold at least two vectors.
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(numbers: &mut [i32], pivot: i32) -> usize {
+            const LANES: usize = $lanes;
+            let len = numbers.len();
+            debug_assert!(len >= 2 * LANES);
+
+            let ptr = numbers.as_mut_ptr();
+            let pivots = $splat(pivot);
+
+            // Read one vector ahead at each end to make room for the stores
+            let first = $load(ptr);
+            let last = $load(ptr.add(len - LANES));
+            let (mut read_left, mut read_right) = (LANES, len - LANES);
+            let (mut write_left, mut write_right) = (0, len);
+
+            while read_right - read_left >= LANES {
+                // Read from the side with less free space, so both sides
+                // have room for a whole vector afterwards
+                let vector = if read_left - write_left <= write_right - read_right {
+                    read_left += LANES;
+                    $load(ptr.add(read_left - LANES))
+                } else {
+                    read_right -= LANES;
+                    $load(ptr.add(read_right))
+                };
+
+                let (shuffled, greater) = $split(vector, pivots);
+                $store(ptr.add(write_left), shuffled);
+                $store(ptr.add(write_right - LANES), shuffled);
+                write_left += LANES - greater;
+                write_right -= greater;
+            }
+
+            // Place the last few unread elements one at a time
+            let mut tail = [0; LANES];
+            let rest = read_right - read_left;
+            tail[..rest].copy_from_slice(&numbers[read_left..read_right]);
+            for &value in &tail[..rest] {
+                if value <= pivot {
+                    *ptr.add(write_left) = value;
+                    write_left += 1;
+                } else {
+                    write_right -= 1;
+                    *ptr.add(write_right) = value;
+                }
+            }
+
+            // Two vectors of room are left for the read-ahead vectors
+            let (shuffled, greater) = $split(first, pivots);
+            $store(ptr.add(write_left), shuffled);
+            $store(ptr.add(write_right - LANES), shuffled);
+            write_left += LANES - greater;
+
+            // The last one fills the remaining gap exactly
+            let (shuffled, greater) = $split(last, pivots);
+            $store(ptr.add(write_left), shuffled);
+            write_left + LANES - greater
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+vector_partition!(partition_avx2, "avx2", 8, splat_avx2, load_avx2, store_avx2, split_avx2);
+
+#[cfg(target_arch = "x86_64")]
+vector_partition!(partition_sse41, "sse4.1", 4, splat_sse41, load_sse41, store_sse41, split_sse41);
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn splat_avx2(value: i32) -> __m256i {
+    _mm256_set1_epi32(value)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn load_avx2(ptr: *const i32) -> __m256i {
+    _mm256_loadu_si256(ptr as *const __m256i)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn store_avx2(ptr: *mut i32, vector: __m256i) {
+    _mm256_storeu_si256(ptr as *mut __m256i, vector)
+}
+
+/// Shuffles the lanes of an AVX2 vector that are at most the pivot to
+/// the front and returns the number of lanes greater than the pivot.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn split_avx2(vector: __m256i, pivots: __m256i) -> (__m256i, usize) {
+    let greater = _mm256_cmpgt_epi32(vector, pivots);
+    let mask = _mm256_movemask_ps(_mm256_castsi256_ps(greater)) as usize;
+    let lanes = _mm256_loadu_si256(PARTITION_LANES_8[mask].as_ptr() as *const __m256i);
+    (_mm256_permutevar8x32_epi32(vector, lanes), mask.count_ones() as usize)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+#[inline]
+unsafe fn splat_sse41(value: i32) -> __m128i {
+    _mm_set1_epi32(value)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+#[inline]
+unsafe fn load_sse41(ptr: *const i32) -> __m128i {
+    _mm_loadu_si128(ptr as *const __m128i)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+#[inline]
+unsafe fn store_sse41(ptr: *mut i32, vector: __m128i) {
+    _mm_storeu_si128(ptr as *mut __m128i, vector)
+}
+
+/// Shuffles the lanes of an SSE vector that are at most the pivot to the
+/// front and returns the number of lanes greater than the pivot.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+#[inline]
+unsafe fn split_sse41(vector: __m128i, pivots: __m128i) -> (__m128i, usize) {
+    let greater = _mm_cmpgt_epi32(vector, pivots);
+    let mask = _mm_movemask_ps(_mm_castsi128_ps(greater)) as usize;
+    let bytes = _mm_loadu_si128(PARTITION_BYTES_4[mask].as_ptr() as *const __m128i);
+    (_mm_shuffle_epi8(vector, bytes), mask.count_ones() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::xorshift::XorShift64;
+    use super::*;
+
+    /// The kernels this machine can run.
+    fn available() -> Vec<Kernel> {
+        Kernel::ALL.iter().copied().filter(|k| k.is_available()).collect()
+    }
+
+    #[test]
+    fn test_scalar_network_is_a_sorting_network() {
+        // By the 0-1 principle, sorting every 0-1 input proves the network
+        for bits in 0u32..1 << 16 {
+            let mut values = [0; SIMD_NETWORK_SIZE];
+            for (i, value) in values.iter_mut().enumerate() {
+                *value = ((bits >> i) & 1) as i32;
+            }
+            sort16_scalar(&mut values);
+            assert!(values.windows(2).all(|w| w[0] <= w[1]), "input {:016b}", bits);
+        }
+    }
+
+    #[test]
+    fn test_sort_small_matches_scalar() {
+        for kernel in available() {
+            for len in 0..=SIMD_NETWORK_SIZE {
+                for seed in 0..50 {
+                    let input = XorShift64::new(seed).values(len, -10..10);
+                    let mut expected = input.clone();
+                    Kernel::Scalar.sort_small(&mut expected);
+                    let mut actual = input.clone();
+                    kernel.sort_small(&mut actual);
+                    assert_eq!(actual, expected, "{} on {:?}", kernel.name(), input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_small_extreme_values() {
+        for kernel in available() {
+            let mut arr = vec![i32::MAX, i32::MIN, 0, -1, i32::MAX, 1, i32::MIN];
+            kernel.sort_small(&mut arr);
+            assert_eq!(arr, vec![i32::MIN, i32::MIN, -1, 0, 1, i32::MAX, i32::MAX]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sort_small_too_many() {
+        Kernel::Scalar.sort_small(&mut [0; SIMD_NETWORK_SIZE + 1]);
+    }
+
+    #[test]
+    fn test_partition_matches_scalar() {
+        for kernel in available() {
+            for len in 1..200 {
+                for &range in &[4, 1_000_000] {
+                    let half = range / 2;
+                    let input = XorShift64::new(len as u64).values(len, -half..range - half);
+                    let mut expected = input.clone();
+                    let expected_index = Kernel::Scalar.partition(&mut expected);
+                    let mut actual = input.clone();
+                    let index = kernel.partition(&mut actual);
+
+                    let pivot = input[len - 1];
+                    assert_eq!(index, expected_index, "{} on {:?}", kernel.name(), input);
+                    assert_eq!(actual[index], pivot);
+                    assert!(actual[..index].iter().all(|&x| x <= pivot));
+                    assert!(actual[index + 1..].iter().all(|&x| x > pivot));
+
+                    // Only the order within each side may differ
+                    actual.sort();
+                    expected.sort();
+                    assert_eq!(actual, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_tables() {
+        assert_eq!(PARTITION_LANES_8[0], [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(PARTITION_LANES_8[0b0000_0101], [1, 3, 4, 5, 6, 7, 0, 2]);
+        assert_eq!(PARTITION_BYTES_4[0b0001][..4], [4, 5, 6, 7]);
+        assert_eq!(PARTITION_BYTES_4[0b0001][12..], [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_detect_is_available() {
+        assert!(Kernel::detect().is_available());
+        assert!(Kernel::Scalar.is_available());
+    }
+}
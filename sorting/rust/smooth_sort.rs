@@ -0,0 +1,518 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: smoothSort.rs
+
+   Description:
+      This program implements Dijkstra's Smoothsort algorithm to sort a list
+      of integers. It reads integers from standard input (one per line),
+      sorts them in ascending order using Smoothsort, and prints the sorted
+      result to standard output (one integer per line). Designed for use
+      with input/output redirection and benchmarking.
+
+      Smoothsort is an in-place heapsort variant built on Leonardo heaps.
+      It runs in O(n) time on already sorted input and degrades smoothly
+      to O(n log n) as the input becomes less sorted, while using only O(1)
+      extra memory.
+
+      Passing --stats prints the comparisons and element writes to stderr.
+      On sorted input both grow linearly, which is the adaptivity that sets
+      Smoothsort apart from heapsort's n log n on every input.
+
+      Passing --timings reports how the run time divides between reading
+      the input, sorting and printing (see phaseTimer.rs), so the linear
+      time on sorted input can be seen apart from the I/O. It cannot be
+      combined with --stats, whose counting would be timed too.
+
+   Usage:
+      ./smoothSort < input.txt > output.txt
+      ./smoothSort --stats < input.txt > output.txt
+      ./smoothSort --timings < input.txt > output.txt
+      cat input.txt | ./smoothSort
+*/
+
+mod op_counter;
+mod phase_timer;
+#[cfg(test)]
+mod xorshift;
+
+use op_counter::{count, Tally};
+use phase_timer::PhaseTimer;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Leonardo numbers: L(0) = L(1) = 1 and L(k) = L(k-1) + L(k-2) + 1.
+///
+/// A Leonardo tree of order k holds exactly L(k) elements. Orders up to 63
+/// cover any slice that fits in memory.
+const LEONARDO: [usize; 64] = leonardo_numbers();
+
+/// Computes the Leonardo numbers at compile time.
+const fn leonardo_numbers() -> [usize; 64] {
+    let mut numbers = [1; 64];
+    let mut k = 2;
+    while k < 64 {
+        numbers[k] = numbers[k - 1] + numbers[k - 2] + 1;
+        k += 1;
+    }
+    numbers
+}
+
+/// Sorts a slice of integers in ascending order using the Smoothsort algorithm.
+///
+/// Smoothsort keeps the sorted prefix as a forest of max-heaps whose sizes
+/// are distinct Leonardo numbers, in decreasing size from left to right. A
+/// Leonardo tree of order k is a root with a left subtree of order k-1 and
+/// a right subtree of order k-2, stored in post-order so the root is the
+/// last element of the tree. The roots themselves are kept in ascending
+/// order, so the rightmost root is always the maximum of the whole forest.
+///
+/// The algorithm runs in two phases:
+/// 1. **Build**: Add elements one at a time. The new element either merges
+///    the two rightmost trees (if their orders are consecutive) or starts a
+///    new tree of order 1 or 0. It is then moved into place among the roots
+///    and sifted down.
+/// 2. **Shrink**: Repeatedly remove the rightmost root, which is the largest
+///    remaining element and already sits in its final position. Removing it
+///    exposes its two subtrees, whose roots are moved into place.
+///
+/// The forest shape is stored as a bit vector (`trees`) relative to the
+/// order of the smallest tree (`order`), so no extra memory is needed.
+///
+/// # Time Complexity
+/// * Best case: O(n) when the array is already sorted
+/// * Average case: O(n log n)
+/// * Worst case: O(n log n)
+///
+/// # Space Complexity
+/// * O(1) - sorts in place with only a constant amount of extra space
+///
+/// # Advantages
+/// * In-place with guaranteed O(n log n) worst case, like heapsort
+/// * Adaptive: approaches O(n) as the input approaches sorted order
+/// * No recursion, so no risk of stack overflow
+///
+/// # Disadvantages
+/// * Complicated to implement compared to heapsort or insertion sort
+/// * Not stable (relative order of equal elements may change)
+/// * Poor cache behaviour on large inputs, like other heap-based sorts
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+pub fn smooth_sort<T: Tally>(numbers: &mut [T]) {
+    let n = numbers.len();
+    if n <= 1 {
+        return;
+    }
+
+    // Bit i of `trees` is set if the forest has a tree of order `order + i`
+    let mut trees: u64 = 1;
+    let mut order: usize = 1;
+    let mut head = 0;
+
+    // Build phase: grow the forest over the whole array
+    while head < n - 1 {
+        if trees & 3 == 3 {
+            // The two rightmost trees have consecutive orders, so the new
+            // element becomes the root that joins them
+            sift(numbers, order, head);
+            trees >>= 2;
+            order += 2;
+        } else {
+            // Start a new tree. Its root only needs to be ordered among the
+            // other roots if it will never be merged into a larger tree.
+            if LEONARDO[order - 1] >= n - 1 - head {
+                trinkle(numbers, trees, order, head, false);
+            } else {
+                sift(numbers, order, head);
+            }
+
+            if order == 1 {
+                trees <<= 1;
+                order = 0;
+            } else {
+                trees <<= order - 1;
+                order = 1;
+            }
+        }
+
+        trees |= 1;
+        head += 1;
+    }
+
+    // Order the last root among the others
+    trinkle(numbers, trees, order, head, false);
+
+    // Shrink phase: remove the maximum root until one element remains
+    while order != 1 || trees != 1 {
+        if order <= 1 {
+            // The rightmost tree is a single element; drop it
+            let trail = (trees & !1).trailing_zeros() as usize;
+            trees >>= trail;
+            order += trail;
+        } else {
+            // Split the rightmost tree into its two subtrees and move both
+            // of their roots into place
+            trees <<= 2;
+            trees ^= 7;
+            order -= 2;
+            trinkle(numbers, trees >> 1, order + 1, head - LEONARDO[order] - 1, true);
+            trinkle(numbers, trees, order, head - 1, true);
+        }
+        head -= 1;
+    }
+}
+
+/// Restores the max-heap property of a single Leonardo tree.
+///
+/// The root at `head` is sifted down, swapping with the larger child until
+/// it is at least as large as both children.
+///
+/// # Arguments
+/// * `numbers` - The array containing the tree
+/// * `order` - The order of the tree rooted at `head`
+/// * `head` - The index of the root (the last element of the tree)
+fn sift<T: Tally>(numbers: &mut [T], mut order: usize, mut head: usize) {
+    let value = numbers[head];
+
+    while order > 1 {
+        let right = head - 1;
+        let left = head - 1 - LEONARDO[order - 2];
+
+        if value >= numbers[left] && value >= numbers[right] {
+            break;
+        }
+
+        if numbers[left] >= numbers[right] {
+            numbers[head] = numbers[left];
+            T::wrote(&numbers[head]);
+            head = left;
+            order -= 1;
+        } else {
+            numbers[head] = numbers[right];
+            T::wrote(&numbers[head]);
+            head = right;
+            order -= 2;
+        }
+    }
+
+    numbers[head] = value;
+    T::wrote(&numbers[head]);
+}
+
+/// Moves the root at `head` left along the list of roots into ascending
+/// order, then sifts it down into the tree where it stops.
+///
+/// A root moves past its left neighbour (its "stepson") only if the stepson
+/// is larger than both the root and the root's children.
+///
+/// # Arguments
+/// * `numbers` - The array containing the forest
+/// * `trees` - The forest bit vector, with bit 0 for the tree at `head`
+/// * `order` - The order of the tree rooted at `head`
+/// * `head` - The index of the root to move
+/// * `trusty` - Whether the tree at `head` is already a valid heap, in which
+///   case its children need not be checked on the first step
+fn trinkle<T: Tally>(
+    numbers: &mut [T],
+    mut trees: u64,
+    mut order: usize,
+    mut head: usize,
+    mut trusty: bool,
+) {
+    let value = numbers[head];
+
+    while trees != 1 {
+        let stepson = head - LEONARDO[order];
+
+        if numbers[stepson] <= value {
+            break;
+        }
+
+        if !trusty && order > 1 {
+            let right = head - 1;
+            let left = head - 1 - LEONARDO[order - 2];
+            if numbers[right] >= numbers[stepson] || numbers[left] >= numbers[stepson] {
+                break;
+            }
+        }
+
+        numbers[head] = numbers[stepson];
+        T::wrote(&numbers[head]);
+        head = stepson;
+
+        // Move on to the next tree to the left
+        let trail = (trees & !1).trailing_zeros() as usize;
+        trees >>= trail;
+        order += trail;
+        trusty = false;
+    }
+
+    if !trusty {
+        numbers[head] = value;
+        T::wrote(&numbers[head]);
+        sift(numbers, order, head);
+    }
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Main entry point for the smoothsort program.
+///
+/// Orchestrates reading from stdin, sorting, and writing to stdout.
+/// Exits with status code 1 if any errors occur during execution.
+fn main() {
+    // Check for the --stats flag to count the operations of the sort
+    let stats = env::args().skip(1).any(|arg| arg == "--stats");
+
+    // Check for the --timings flag to time reading, sorting and printing; it
+    // times a plain sort, so it cannot be combined with counting one
+    let timings = env::args().skip(1).any(|arg| arg == "--timings");
+    if timings && stats {
+        eprintln!("Error: --timings cannot be combined with --stats");
+        process::exit(1);
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // Sort the numbers using smoothsort, counting operations if asked
+    if stats {
+        let counts = count(&mut numbers, smooth_sort);
+        eprint!("{}", counts.summary("smoothsort", numbers.len()));
+    } else {
+        smooth_sort(&mut numbers);
+    }
+
+    timer.lap("sort");
+
+    // Print the sorted numbers to standard output
+    if let Err(e) = print_output(&numbers) {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if timings {
+        eprint!("{}", timer.summary("smoothsort", numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xorshift::XorShift64;
+
+    #[test]
+    fn test_empty_array() {
+        let mut arr: Vec<i32> = vec![];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+    }
+
+    #[test]
+    fn test_single_element() {
+        let mut arr = vec![42];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_already_sorted() {
+        let mut arr = vec![1, 2, 3, 4, 5];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        let mut arr = vec![5, 4, 3, 2, 1];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_order() {
+        let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr = vec![5, 2, 8, 2, 9, 1, 5, 5];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 2, 5, 5, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let mut arr = vec![-3, 5, -1, 0, -9, 2];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![-9, -3, -1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let mut arr = vec![1000000, -1000000, 0, 999999, -999999];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
+    }
+
+    #[test]
+    fn test_two_elements_sorted() {
+        let mut arr = vec![1, 2];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_two_elements_unsorted() {
+        let mut arr = vec![2, 1];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        smooth_sort(&mut arr);
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_leonardo_numbers() {
+        assert_eq!(&LEONARDO[..10], &[1, 1, 3, 5, 9, 15, 25, 41, 67, 109]);
+    }
+
+    #[test]
+    fn test_every_length_up_to_200() {
+        let mut rng = XorShift64::new(1981);
+        for len in 0..=200 {
+            let arr: Vec<i32> = rng.values(len, -32..32);
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut actual = arr;
+            smooth_sort(&mut actual);
+            assert_eq!(actual, expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_nearly_sorted() {
+        // Sorted input with a few adjacent pairs swapped
+        let mut arr: Vec<i32> = (0..10_000).collect();
+        for i in (0..10_000).step_by(97) {
+            arr.swap(i, i + 1);
+        }
+        smooth_sort(&mut arr);
+        let expected: Vec<i32> = (0..10_000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let mut arr = vec![i32::MAX, 0, i32::MIN, -1, i32::MAX, 1, i32::MIN];
+        smooth_sort(&mut arr);
+        assert_eq!(arr, vec![i32::MIN, i32::MIN, -1, 0, 1, i32::MAX, i32::MAX]);
+    }
+
+    #[test]
+    fn test_stats_show_adaptivity() {
+        let n = 8000;
+
+        // Sorted input: about two comparisons and one write per element
+        let mut sorted: Vec<i32> = (0..n).collect();
+        let sorted_counts = count(&mut sorted, smooth_sort);
+        assert_eq!(sorted, (0..n).collect::<Vec<i32>>());
+        assert!(sorted_counts.comparisons < 2 * n as u64, "{:?}", sorted_counts);
+        assert!(sorted_counts.writes < n as u64 + 16, "{:?}", sorted_counts);
+
+        // Random input needs on the order of n log2 n comparisons
+        let mut random = XorShift64::new(3).values(n as usize, 0..1 << 24);
+        let counts = count(&mut random, smooth_sort);
+        assert!(random.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(counts.comparisons > n as u64 * 13, "{:?}", counts);
+        assert!(counts.comparisons < 3 * n as u64 * 13, "{:?}", counts);
+        assert_eq!((counts.swaps, counts.buffer_copies), (0, 0));
+    }
+}
@@ -0,0 +1,344 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: sortingNetwork.rs
+
+   Description:
+      This module provides hard-coded sorting networks for slices of up to
+      16 integers. A sorting network is a fixed sequence of compare-exchange
+      operations that sorts every input of a given length; the sequence does
+      not depend on the data. The networks below use the fewest comparators
+      known for each length (proven optimal for n <= 12, best known for
+      13 <= n <= 16).
+
+      The module has no main function. Programs include it as a module and
+      use `network_sort` either as a standalone small-sort or as the base
+      case of a recursive sort. It counts operations with the counter in
+      opCounter.rs, which the program must include next to it.
+
+   Usage:
+      mod op_counter;
+      mod sorting_network;
+      use sorting_network::{network_sort, MAX_NETWORK_SIZE};
+*/
+
+use super::op_counter::Tally;
+
+/// The largest slice length that `network_sort` can handle.
+pub const MAX_NETWORK_SIZE: usize = 16;
+
+const NETWORK_2: [(usize, usize); 1] = [
+    (0, 1),
+];
+
+const NETWORK_3: [(usize, usize); 3] = [
+    (0, 2),
+    (0, 1),
+    (1, 2),
+];
+
+const NETWORK_4: [(usize, usize); 5] = [
+    (0, 2), (1, 3),
+    (0, 1), (2, 3),
+    (1, 2),
+];
+
+const NETWORK_5: [(usize, usize); 9] = [
+    (0, 3), (1, 4),
+    (0, 2), (1, 3),
+    (0, 1), (2, 4),
+    (1, 2), (3, 4),
+    (2, 3),
+];
+
+const NETWORK_6: [(usize, usize); 12] = [
+    (0, 5), (1, 3), (2, 4),
+    (1, 2), (3, 4),
+    (0, 3), (2, 5),
+    (0, 1), (2, 3), (4, 5),
+    (1, 2), (3, 4),
+];
+
+const NETWORK_7: [(usize, usize); 16] = [
+    (0, 6), (2, 3), (4, 5),
+    (0, 2), (1, 4), (3, 6),
+    (0, 1), (2, 5), (3, 4),
+    (1, 2), (4, 6),
+    (2, 3), (4, 5),
+    (1, 2), (3, 4), (5, 6),
+];
+
+const NETWORK_8: [(usize, usize); 19] = [
+    (0, 2), (1, 3), (4, 6), (5, 7),
+    (0, 4), (1, 5), (2, 6), (3, 7),
+    (0, 1), (2, 3), (4, 5), (6, 7),
+    (2, 4), (3, 5),
+    (1, 4), (3, 6),
+    (1, 2), (3, 4), (5, 6),
+];
+
+const NETWORK_9: [(usize, usize); 25] = [
+    (0, 3), (1, 7), (2, 5), (4, 8),
+    (0, 7), (2, 4), (3, 8), (5, 6),
+    (0, 2), (1, 3), (4, 5), (7, 8),
+    (1, 4), (3, 6), (5, 7),
+    (0, 1), (2, 4), (3, 5), (6, 8),
+    (2, 3), (4, 5), (6, 7),
+    (1, 2), (3, 4), (5, 6),
+];
+
+const NETWORK_10: [(usize, usize); 29] = [
+    (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+    (0, 2), (1, 4), (5, 8), (7, 9),
+    (0, 3), (2, 4), (5, 7), (6, 9),
+    (0, 1), (3, 6), (8, 9),
+    (1, 5), (2, 3), (4, 8), (6, 7),
+    (1, 2), (3, 5), (4, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+    (3, 4), (5, 6),
+];
+
+const NETWORK_11: [(usize, usize); 35] = [
+    (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+    (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+    (1, 3), (2, 5), (4, 7), (8, 10),
+    (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+    (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+    (2, 4), (3, 6), (5, 7), (8, 9),
+    (1, 2), (3, 4), (5, 6), (7, 8),
+    (2, 3), (4, 5), (6, 7),
+];
+
+const NETWORK_12: [(usize, usize); 39] = [
+    (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+    (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+    (0, 2), (1, 6), (5, 10), (9, 11),
+    (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+    (1, 4), (3, 5), (6, 8), (7, 10),
+    (1, 3), (2, 5), (6, 9), (8, 10),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (4, 6), (5, 7),
+    (3, 4), (5, 6), (7, 8),
+];
+
+const NETWORK_13: [(usize, usize); 45] = [
+    (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+    (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+    (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+    (4, 6), (5, 9), (8, 11), (10, 12),
+    (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+    (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+    (1, 3), (2, 4), (5, 6), (9, 10),
+    (1, 2), (3, 4), (5, 7), (6, 8),
+    (2, 3), (4, 5), (6, 7), (8, 9),
+    (3, 4), (5, 6),
+];
+
+const NETWORK_14: [(usize, usize); 51] = [
+    (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13),
+    (0, 2), (1, 3), (4, 8), (5, 9), (10, 12), (11, 13),
+    (0, 4), (1, 2), (3, 7), (5, 8), (6, 10), (9, 13), (11, 12),
+    (0, 6), (1, 5), (3, 9), (4, 10), (7, 13), (8, 12),
+    (2, 10), (3, 11), (4, 6), (7, 9),
+    (1, 3), (2, 8), (5, 11), (6, 7), (10, 12),
+    (1, 4), (2, 6), (3, 5), (7, 11), (8, 10), (9, 12),
+    (2, 4), (3, 6), (5, 8), (7, 10), (9, 11),
+    (3, 4), (5, 6), (7, 8), (9, 10),
+    (6, 7),
+];
+
+const NETWORK_15: [(usize, usize); 56] = [
+    (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+    (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+    (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+    (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+    (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+    (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+    (2, 4), (3, 6), (9, 12), (11, 13),
+    (3, 5), (6, 8), (7, 9), (10, 12),
+    (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+    (6, 7), (8, 9),
+];
+
+const NETWORK_16: [(usize, usize); 60] = [
+    (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+    (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+    (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+    (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+    (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+    (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+    (2, 4), (3, 6), (9, 12), (11, 13),
+    (3, 5), (6, 8), (7, 9), (10, 12),
+    (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+    (6, 7), (8, 9),
+];
+
+/// Comparator lists indexed by slice length. Lengths 0 and 1 need no comparators.
+const NETWORKS: [&[(usize, usize)]; MAX_NETWORK_SIZE + 1] = [
+    &[],
+    &[],
+    &NETWORK_2,
+    &NETWORK_3,
+    &NETWORK_4,
+    &NETWORK_5,
+    &NETWORK_6,
+    &NETWORK_7,
+    &NETWORK_8,
+    &NETWORK_9,
+    &NETWORK_10,
+    &NETWORK_11,
+    &NETWORK_12,
+    &NETWORK_13,
+    &NETWORK_14,
+    &NETWORK_15,
+    &NETWORK_16,
+];
+
+/// Sorts a slice of at most 16 integers in ascending order using an optimal
+/// sorting network.
+///
+/// Each comparator `(i, j)` with `i < j` is a compare-exchange: afterwards
+/// `numbers[i]` holds the smaller and `numbers[j]` the larger of the two
+/// values. Comparators on the same line of each table touch disjoint
+/// positions and could run in parallel.
+///
+/// # Time Complexity
+/// * O(1) for a fixed length - the number of comparisons is the size of the
+///   network (at most 60 for n = 16), regardless of input order
+///
+/// # Space Complexity
+/// * O(1) - sorts in place
+///
+/// # Advantages
+/// * No data-dependent branches, so it is fast for tiny subarrays
+/// * Makes a good base case for merge sort and quick sort
+///
+/// # Disadvantages
+/// * Only defined for slices of up to `MAX_NETWORK_SIZE` elements
+/// * Not stable (relative order of equal elements may change)
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of at most `MAX_NETWORK_SIZE` values to sort
+///
+/// # Panics
+/// * Panics if the slice is longer than `MAX_NETWORK_SIZE`
+pub fn network_sort<T: Tally>(numbers: &mut [T]) {
+    assert!(
+        numbers.len() <= MAX_NETWORK_SIZE,
+        "network_sort supports at most {} elements, got {}",
+        MAX_NETWORK_SIZE,
+        numbers.len()
+    );
+
+    for &(i, j) in NETWORKS[numbers.len()] {
+        compare_exchange(numbers, i, j);
+    }
+}
+
+/// Orders the elements at positions `i` and `j` so that `numbers[i] <= numbers[j]`.
+///
+/// The elements are compared once and swapped if they are out of order.
+/// The result of the comparison picks the minimum and the maximum out of
+/// the pair by index rather than behind a branch, and the swap is reported
+/// through `Tally::exchanged`, so plain and counted integers alike run
+/// without branching on the values.
+///
+/// # Arguments
+/// * `numbers` - The slice containing both elements
+/// * `i` - The position that receives the smaller value
+/// * `j` - The position that receives the larger value
+#[inline(always)]
+fn compare_exchange<T: Tally>(numbers: &mut [T], i: usize, j: usize) {
+    let pair = [numbers[i], numbers[j]];
+    let swap = (pair[1] < pair[0]) as usize;
+    numbers[i] = pair[swap];
+    numbers[j] = pair[1 - swap];
+    T::exchanged(&numbers[i], &numbers[j], swap == 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::op_counter;
+    use super::*;
+
+    #[test]
+    fn test_network_sizes_are_optimal() {
+        // Best known comparator counts for n = 0..=16
+        let optimal = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+        for (n, network) in NETWORKS.iter().enumerate() {
+            assert_eq!(network.len(), optimal[n], "network for n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_comparators_are_in_range() {
+        for (n, network) in NETWORKS.iter().enumerate() {
+            for &(i, j) in network.iter() {
+                assert!(i < j && j < n, "bad comparator ({}, {}) for n = {}", i, j, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_one_principle() {
+        // A comparator network sorts every input if and only if it sorts every
+        // sequence of 0s and 1s, so checking all 2^n binary inputs is exhaustive.
+        for n in 0..=MAX_NETWORK_SIZE {
+            for mask in 0u32..(1 << n) {
+                let mut arr: Vec<i32> = (0..n).map(|bit| ((mask >> bit) & 1) as i32).collect();
+                network_sort(&mut arr);
+                assert!(
+                    arr.windows(2).all(|w| w[0] <= w[1]),
+                    "network for n = {} fails on input mask {:#b}",
+                    n,
+                    mask
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_general_values() {
+        let mut arr = vec![1000000, -3, 5, -1, 0, -9, 2, 7, 7, -1000000, 42, 3, 3, 8, -2, 1];
+        let mut expected = arr.clone();
+        expected.sort();
+        network_sort(&mut arr);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_empty_and_single() {
+        let mut arr: Vec<i32> = vec![];
+        network_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+
+        let mut arr = vec![42];
+        network_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_counted_operations() {
+        let mut arr: Vec<i32> = (0..16).rev().collect();
+        let counts = op_counter::count(&mut arr, network_sort);
+        assert_eq!(arr, (0..16).collect::<Vec<i32>>());
+        // 60 comparators, each comparing once and swapping only when the
+        // pair is out of order
+        assert_eq!(counts.comparisons, 60);
+        assert!(counts.swaps > 0 && counts.swaps <= 60, "{:?}", counts);
+        assert_eq!(counts.writes, 0);
+
+        // Sorted input is never swapped
+        let mut arr: Vec<i32> = (0..16).collect();
+        let counts = op_counter::count(&mut arr, network_sort);
+        assert_eq!((counts.comparisons, counts.swaps), (60, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_long_panics() {
+        let mut arr: Vec<i32> = (0..17).collect();
+        network_sort(&mut arr);
+    }
+}
@@ -0,0 +1,301 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: stageExecutor.rs
+
+   Description:
+      This module runs the stages of a data-oblivious sorting network on
+      multiple threads. A stage is a set of compare-exchange operations on
+      disjoint positions, described as pairs of equal-length segments: the
+      i-th element of the low segment is compared with the i-th element of
+      the high segment (or with the i-th element from the end when the high
+      segment is reversed). Because no two comparators in a stage touch the
+      same position, the segments can be cut into pieces and handed to
+      worker threads without any locking.
+
+      It also pads inputs whose length is not a power of two, which the
+      bitonic and odd-even merge networks require.
+
+      The stages are generic over the element type so the networks can also
+      sort counted values for --stats with the operation counter in
+      opCounter.rs, which the program must include next to it.
+
+   Usage:
+      mod op_counter;
+      mod stage_executor;
+      use stage_executor::{run_stage, sort_padded};
+*/
+
+use super::op_counter::Tally;
+use std::thread;
+
+/// Stages with fewer comparators than this run on the calling thread, since
+/// spawning workers would cost more than the comparisons themselves.
+const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// The number of comparators in each piece handed to a worker thread.
+const PIECE_SIZE: usize = 1 << 12;
+
+/// Returns the number of worker threads to use by default.
+///
+/// Falls back to a single thread if the available parallelism is unknown.
+pub fn available_threads() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Sorts a slice of any length with a network that only handles powers of two.
+///
+/// The input is copied into a buffer whose length is the next power of two,
+/// and the extra slots are filled with copies of the largest input value.
+/// Since the padding values are never smaller than a real element, they all
+/// end up at the back of the sorted buffer, and the first `numbers.len()`
+/// elements are copied back. Finding the largest value costs n - 1
+/// comparisons on top of the network's.
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+/// * `threads` - The number of worker threads passed on to the network
+/// * `sort_power_of_two` - Sorts a slice whose length is a power of two
+pub fn sort_padded<T, F>(numbers: &mut [T], threads: usize, sort_power_of_two: F)
+where
+    T: Tally,
+    F: Fn(&mut [T], usize),
+{
+    let len = numbers.len();
+    if len <= 1 {
+        return;
+    }
+
+    if len.is_power_of_two() {
+        sort_power_of_two(numbers, threads);
+        return;
+    }
+
+    // The slice holds at least two values here
+    let largest = *numbers.iter().max().unwrap();
+    let mut padded = vec![largest; len.next_power_of_two()];
+    padded[..len].copy_from_slice(numbers);
+    T::copied(len);
+    sort_power_of_two(&mut padded, threads);
+    numbers.copy_from_slice(&padded[..len]);
+    T::copied(len);
+}
+
+/// Runs one stage of compare-exchange operations, possibly on several threads.
+///
+/// For every pair `(low, high)`, element `low[i]` is compared with
+/// `high[i]` (or `high[high.len() - 1 - i]` if `reversed` is set) and the
+/// smaller value is stored in the low segment. The comparisons performed do
+/// not depend on the data, and each one is branch-free.
+///
+/// # Arguments
+/// * `pairs` - Disjoint pairs of equal-length segments to compare
+/// * `reversed` - Whether each high segment is traversed back to front
+/// * `threads` - The maximum number of worker threads to use
+pub fn run_stage<T>(pairs: Vec<(&mut [T], &mut [T])>, reversed: bool, threads: usize)
+where
+    T: Tally + Send,
+{
+    let comparators: usize = pairs.iter().map(|(low, _)| low.len()).sum();
+
+    if threads <= 1 || comparators < PARALLEL_THRESHOLD {
+        for (low, high) in pairs {
+            compare_segments(low, high, reversed);
+        }
+        return;
+    }
+
+    // Cut the segments into pieces so the work can be balanced across threads
+    let mut pieces = Vec::new();
+    for (low, high) in pairs {
+        if reversed {
+            pieces.extend(low.chunks_mut(PIECE_SIZE).zip(high.rchunks_mut(PIECE_SIZE)));
+        } else {
+            pieces.extend(low.chunks_mut(PIECE_SIZE).zip(high.chunks_mut(PIECE_SIZE)));
+        }
+    }
+
+    // Give each thread a contiguous run of pieces
+    let per_thread = pieces.len().div_ceil(threads);
+    thread::scope(|scope| {
+        while !pieces.is_empty() {
+            let take = per_thread.min(pieces.len());
+            let batch: Vec<_> = pieces.drain(..take).collect();
+            scope.spawn(move || {
+                for (low, high) in batch {
+                    compare_segments(low, high, reversed);
+                }
+            });
+        }
+    });
+}
+
+/// Compare-exchanges two equal-length segments element by element.
+///
+/// # Arguments
+/// * `low` - The segment that receives the smaller values
+/// * `high` - The segment that receives the larger values
+/// * `reversed` - Whether `high` is traversed back to front
+fn compare_segments<T: Tally>(low: &mut [T], high: &mut [T], reversed: bool) {
+    debug_assert_eq!(low.len(), high.len());
+
+    if reversed {
+        for (a, b) in low.iter_mut().zip(high.iter_mut().rev()) {
+            compare_exchange(a, b);
+        }
+    } else {
+        for (a, b) in low.iter_mut().zip(high.iter_mut()) {
+            compare_exchange(a, b);
+        }
+    }
+}
+
+/// Orders two values so that `*a <= *b` without branching on the data.
+///
+/// The values are compared once, and the result of that comparison picks
+/// the minimum and the maximum out of the pair by index, so no branch
+/// depends on the values. Under `--stats` a comparator counts as one
+/// comparison, plus a swap when the values were out of order, which
+/// `Tally::exchanged` adds without a branch.
+///
+/// # Arguments
+/// * `a` - Receives the smaller value
+/// * `b` - Receives the larger value
+#[inline(always)]
+fn compare_exchange<T: Tally>(a: &mut T, b: &mut T) {
+    let pair = [*a, *b];
+    let swap = (pair[1] < pair[0]) as usize;
+    *a = pair[swap];
+    *b = pair[1 - swap];
+    T::exchanged(a, b, swap == 1);
+}
+
+/// Times `sort` on each of the six test distributions and returns how many
+/// times longer the slowest one took than the fastest. Each time is the
+/// best of several runs, so that a stray pause of the test machine doesn't
+/// count against the sort.
+///
+/// # Arguments
+/// * `len` - The number of values in each distribution
+/// * `sort` - Sorts a slice of integers in place
+///
+/// # Returns
+/// * `f64` - The slowest time divided by the fastest
+#[cfg(test)]
+pub fn timing_spread<F: Fn(&mut [i32])>(len: usize, sort: F) -> f64 {
+    use super::xorshift::XorShift64;
+    use std::time::Instant;
+
+    // The distributions of test_suite_generate.py
+    let mut rng = XorShift64::new(41600);
+    let sorted: Vec<i32> = (0..len as i32).collect();
+    let mut nearly_sorted = sorted.clone();
+    for _ in 0..len / 100 {
+        let (i, j) = (rng.below(len), rng.below(len));
+        nearly_sorted.swap(i, j);
+    }
+    let distributions = [
+        rng.values(len, 0..len as i32),
+        sorted.iter().rev().cloned().collect(),
+        nearly_sorted,
+        rng.values(len, 0..10),
+        vec![7; len],
+        sorted,
+    ];
+
+    // Rounds through all six in turn, so that a slow spell of the machine
+    // hits every distribution rather than just one
+    let mut times = [f64::INFINITY; 6];
+    for _ in 0..15 {
+        for (time, input) in times.iter_mut().zip(distributions.iter()) {
+            let mut arr = input.clone();
+            let start = Instant::now();
+            sort(&mut arr);
+            *time = time.min(start.elapsed().as_secs_f64());
+        }
+    }
+    let slowest = times.iter().cloned().fold(0.0, f64::max);
+    let fastest = times.iter().cloned().fold(f64::INFINITY, f64::min);
+    slowest / fastest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::op_counter;
+    use super::*;
+
+    #[test]
+    fn test_compare_segments_forward() {
+        let mut low = vec![5, 1, 7];
+        let mut high = vec![2, 3, 7];
+        compare_segments(&mut low, &mut high, false);
+        assert_eq!(low, vec![2, 1, 7]);
+        assert_eq!(high, vec![5, 3, 7]);
+    }
+
+    #[test]
+    fn test_compare_segments_reversed() {
+        let mut low = vec![5, 1, 7];
+        let mut high = vec![2, 3, 4];
+        compare_segments(&mut low, &mut high, true);
+        // Pairs compared: (5, 4), (1, 3), (7, 2)
+        assert_eq!(low, vec![4, 1, 2]);
+        assert_eq!(high, vec![7, 3, 5]);
+    }
+
+    #[test]
+    fn test_run_stage_threaded_matches_single() {
+        let len = PARALLEL_THRESHOLD * 4;
+        let data: Vec<i32> = (0..len as i32).map(|x| (x * 7919) % 1000).collect();
+        for &reversed in &[false, true] {
+            let mut single = data.clone();
+            let mut threaded = data.clone();
+            {
+                let (low, high) = single.split_at_mut(len / 2);
+                run_stage(vec![(low, high)], reversed, 1);
+            }
+            {
+                let (low, high) = threaded.split_at_mut(len / 2);
+                run_stage(vec![(low, high)], reversed, 4);
+            }
+            assert_eq!(single, threaded);
+        }
+    }
+
+    #[test]
+    fn test_sort_padded_truncates() {
+        let mut arr = vec![3, i32::MAX, 1, 2, 9];
+        sort_padded(&mut arr, 1, |slice, _| slice.sort());
+        assert_eq!(arr, vec![1, 2, 3, 9, i32::MAX]);
+    }
+
+    #[test]
+    fn test_stage_counts_across_threads() {
+        let len = PARALLEL_THRESHOLD * 4;
+        let mut data: Vec<i32> = (0..len as i32).rev().collect();
+        let counts = op_counter::count(&mut data, |slice| {
+            let (low, high) = slice.split_at_mut(len / 2);
+            run_stage(vec![(low, high)], false, 4);
+        });
+        // Every worker's comparators are counted, and all of them swap
+        assert_eq!(counts.comparisons, len as u64 / 2);
+        assert_eq!((counts.swaps, counts.writes), (len as u64 / 2, 0));
+    }
+
+    #[test]
+    fn test_sort_padded_counts_copies() {
+        let mut arr = vec![4, 2, 7, 1, 3];
+        let counts = op_counter::count(&mut arr, |slice| {
+            sort_padded(slice, 1, |padded, _| {
+                assert_eq!(padded.len(), 8);
+                assert!(padded[5..].iter().all(|value| value.0 == 7));
+                padded.sort();
+            })
+        });
+        assert_eq!(arr, vec![1, 2, 3, 4, 7]);
+        assert_eq!(counts.buffer_copies, 10);
+    }
+}
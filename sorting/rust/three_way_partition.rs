@@ -0,0 +1,84 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: threeWayPartition.rs
+
+   Description:
+      This module provides the three-way (Dutch national flag) partition
+      shared by quick sort, which uses it around median of medians pivots
+      and to select order statistics, and by the adaptive sort's quick
+      sort for inputs with few distinct values. Values equal to the pivot
+      end up together in the middle, so they are placed once and never
+      looked at again. Swaps are reported through the `Tally` hooks of
+      opCounter.rs, which the program must include next to this module.
+
+   Usage:
+      mod op_counter;
+      mod three_way_partition;
+      use three_way_partition::partition_three_way;
+*/
+
+use super::op_counter::Tally;
+
+/// Partitions a slice into elements less than, equal to and greater than
+/// the pivot value (Dutch national flag partition).
+///
+/// After the call, `arr[..less]` < pivot, `arr[less..greater]` == pivot and
+/// `arr[greater..]` > pivot.
+///
+/// # Time Complexity
+/// * O(n) - at most two comparisons and one swap per element
+///
+/// # Arguments
+/// * `arr` - The slice to partition
+/// * `pivot` - The value to partition around
+///
+/// # Returns
+/// * `(usize, usize)` - The start and end of the run of elements equal to the pivot
+pub fn partition_three_way<T: Tally>(arr: &mut [T], pivot: T) -> (usize, usize) {
+    let mut less = 0;
+    let mut i = 0;
+    let mut greater = arr.len();
+
+    while i < greater {
+        if arr[i] < pivot {
+            arr.swap(less, i);
+            T::swapped(&arr[less], &arr[i]);
+            less += 1;
+            i += 1;
+        } else if arr[i] > pivot {
+            greater -= 1;
+            arr.swap(i, greater);
+            T::swapped(&arr[i], &arr[greater]);
+        } else {
+            i += 1;
+        }
+    }
+
+    (less, greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_three_way() {
+        let mut arr = vec![4, 9, 4, 1, 7, 4, 0, 4];
+        let (less, greater) = partition_three_way(&mut arr, 4);
+        assert_eq!((less, greater), (2, 6));
+        assert!(arr[..less].iter().all(|&x| x < 4));
+        assert!(arr[less..greater].iter().all(|&x| x == 4));
+        assert!(arr[greater..].iter().all(|&x| x > 4));
+    }
+
+    #[test]
+    fn test_pivot_outside_the_values() {
+        let mut arr = vec![3, 1, 2];
+        assert_eq!(partition_three_way(&mut arr, 0), (0, 0));
+        assert_eq!(partition_three_way(&mut arr, 9), (3, 3));
+        let mut empty: Vec<i32> = Vec::new();
+        assert_eq!(partition_three_way(&mut empty, 5), (0, 0));
+    }
+}
@@ -0,0 +1,325 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: tournamentSort.rs
+
+   Description:
+      This program implements Tournament Sort to sort a list of integers. It
+      reads integers from standard input (one per line), sorts them in
+      ascending order using a loser tree, and prints the sorted result to
+      standard output (one integer per line). Designed for use with
+      input/output redirection and benchmarking.
+
+      Passing --stats prints the number of comparisons and buffer copies
+      to stderr. A match between two values costs one comparison, or two
+      when the first one finds them not less, since ties are broken by
+      testing for equality.
+
+      Passing --timings reports the time taken by reading the input, by
+      the tournament and by printing the output to stderr (see
+      phaseTimer.rs). It times a plain sort, so it cannot be combined
+      with --stats.
+
+   Usage:
+      ./tournamentSort < input.txt > output.txt
+      ./tournamentSort --stats < input.txt > output.txt
+      ./tournamentSort --timings < input.txt > output.txt
+      cat input.txt | ./tournamentSort
+*/
+
+mod loser_tree;
+mod op_counter;
+mod phase_timer;
+#[cfg(test)]
+mod xorshift;
+
+use loser_tree::LoserTree;
+use op_counter::{count, Tally};
+use phase_timer::PhaseTimer;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Sorts a slice of integers in ascending order using Tournament Sort.
+///
+/// Tournament Sort plays a knockout tournament between all elements:
+/// 1. **Build**: Every element is a leaf of a loser tree. Each internal node
+///    records the loser of the match played there, and the overall winner
+///    (the minimum) comes out at the top.
+/// 2. **Select**: Take the winner as the next output element and replace
+///    its leaf with an exhausted (infinitely large) entry.
+/// 3. **Replay**: Only the matches on the path from that leaf to the root
+///    need to be replayed to find the next winner.
+///
+/// The loser tree stores only the losers, so a replay compares the new
+/// candidate against one stored loser per level without looking at siblings.
+///
+/// # Time Complexity
+/// * Best case: O(n log n)
+/// * Average case: O(n log n)
+/// * Worst case: O(n log n)
+///
+/// # Space Complexity
+/// * O(n) - one leaf and one internal node per element
+///
+/// # Advantages
+/// * Guaranteed O(n log n) performance with about n log n comparisons
+/// * Stable: equal elements keep their order, since ties go to the lower leaf
+/// * The same tree merges k sorted runs in O(log k) per element
+///
+/// # Disadvantages
+/// * Requires O(n) extra space
+/// * Slower in practice than quick sort due to poor memory locality
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+pub fn tournament_sort<T: Tally>(numbers: &mut [T]) {
+    // Each element enters the tournament as a source holding one value
+    let leaves: Vec<_> = numbers.iter().map(|&num| Some(num).into_iter()).collect();
+    T::copied(numbers.len());
+    let tree = LoserTree::new(leaves);
+
+    // The winners come out in ascending order
+    for (slot, winner) in numbers.iter_mut().zip(tree) {
+        *slot = winner;
+    }
+    T::copied(numbers.len());
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Main entry point for the tournament sort program.
+///
+/// Orchestrates reading from stdin, sorting, and writing to stdout.
+/// Exits with status code 1 if any errors occur during execution.
+fn main() {
+    // Check for the --stats flag to count the operations of the sort
+    let stats = env::args().skip(1).any(|arg| arg == "--stats");
+
+    // Check for the --timings flag to time reading, sorting and printing; it
+    // times a plain sort, so it cannot be combined with counting one
+    let timings = env::args().skip(1).any(|arg| arg == "--timings");
+    if timings && stats {
+        eprintln!("Error: --timings cannot be combined with --stats");
+        process::exit(1);
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // Sort the numbers using tournament sort, counting operations if asked
+    if stats {
+        let counts = count(&mut numbers, tournament_sort);
+        eprint!("{}", counts.summary("tournament sort", numbers.len()));
+    } else {
+        tournament_sort(&mut numbers);
+    }
+
+    timer.lap("sort");
+
+    // Print the sorted numbers to standard output
+    if let Err(e) = print_output(&numbers) {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if timings {
+        eprint!("{}", timer.summary("tournament sort", numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xorshift::XorShift64;
+
+    #[test]
+    fn test_empty_array() {
+        let mut arr: Vec<i32> = vec![];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+    }
+
+    #[test]
+    fn test_single_element() {
+        let mut arr = vec![42];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_already_sorted() {
+        let mut arr = vec![1, 2, 3, 4, 5];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        let mut arr = vec![5, 4, 3, 2, 1];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_order() {
+        let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr = vec![5, 2, 8, 2, 9, 1, 5, 5];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 2, 5, 5, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let mut arr = vec![-3, 5, -1, 0, -9, 2];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![-9, -3, -1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let mut arr = vec![1000000, -1000000, 0, 999999, -999999];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
+    }
+
+    #[test]
+    fn test_two_elements_sorted() {
+        let mut arr = vec![1, 2];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_two_elements_unsorted() {
+        let mut arr = vec![2, 1];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7];
+        tournament_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        tournament_sort(&mut arr);
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = XorShift64::new(1962);
+        for len in 0..=300 {
+            let arr: Vec<i32> = rng.values(len, -32..32);
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut actual = arr;
+            tournament_sort(&mut actual);
+            assert_eq!(actual, expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_stats_stay_near_n_log_n() {
+        let n: usize = 4096;
+        let n_log_n = (n * 12) as u64;
+        let mut rng = XorShift64::new(2718);
+        let random: Vec<i32> = rng.values(n, 0..1 << 24);
+        let sorted: Vec<i32> = (0..n as i32).collect();
+
+        for input in [random, sorted].iter() {
+            let mut arr = input.clone();
+            let counts = count(&mut arr, tournament_sort);
+            assert!(arr.windows(2).all(|pair| pair[0] <= pair[1]));
+            // Between one and two comparisons per match played
+            assert!(counts.comparisons >= n_log_n - n as u64, "{:?}", counts);
+            assert!(counts.comparisons <= 2 * n_log_n, "{:?}", counts);
+            // Every value goes into the tree and back out
+            assert_eq!(counts.buffer_copies, 2 * n as u64);
+            assert_eq!((counts.swaps, counts.writes), (0, 0));
+        }
+    }
+}
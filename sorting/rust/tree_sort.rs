@@ -0,0 +1,518 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: treeSort.rs
+
+   Description:
+      This program implements Tree Sort to sort a list of integers. It reads
+      integers from standard input (one per line), inserts them into a
+      self-balancing AVL tree, and prints the in-order traversal of the tree
+      to standard output (one integer per line). Designed for use with
+      input/output redirection and benchmarking.
+
+      Passing --stats prints to stderr how many comparisons the inserts
+      made and how many values were copied into the tree and back out.
+      Duplicates only bump a count, so inputs with few distinct values
+      copy far fewer values into the tree than there are lines.
+
+      Passing --timings reports the time spent reading the input, building
+      and traversing the tree, and printing the output to stderr (see
+      phaseTimer.rs). Counting would slow the sort down, so it cannot be
+      combined with --stats.
+
+   Usage:
+      ./treeSort < input.txt > output.txt
+      ./treeSort --stats < input.txt > output.txt
+      ./treeSort --timings < input.txt > output.txt
+      cat input.txt | ./treeSort
+*/
+
+mod op_counter;
+mod phase_timer;
+#[cfg(test)]
+mod xorshift;
+
+use op_counter::{count, Tally};
+use phase_timer::PhaseTimer;
+use std::env;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+/// Reads integers from standard input, one per line.
+///
+/// Uses a buffered reader for efficient I/O when handling large input files.
+/// Empty lines and whitespace-only lines are skipped. The function will
+/// return an error if a line cannot be parsed as an i32.
+///
+/// # Returns
+/// * `io::Result<Vec<i32>>` - A vector of integers read from stdin
+///
+/// # Errors
+/// * Returns an error if reading from stdin fails or if a line cannot be
+///   parsed as an integer
+fn read_input() -> io::Result<Vec<i32>> {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+    let mut numbers = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        
+        if !trimmed.is_empty() {
+            match trimmed.parse::<i32>() {
+                Ok(num) => numbers.push(num),
+                Err(e) => {
+                    eprintln!("Error parsing '{}': {}", trimmed, e);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Failed to parse integer: {}", trimmed)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Marks a missing child in the node arena.
+const NIL: usize = usize::MAX;
+
+/// A node of the AVL tree. Equal values share a node and are counted.
+struct Node<T> {
+    /// The value stored at this node
+    value: T,
+    /// How many times the value was inserted
+    count: usize,
+    /// Arena index of the left child, or NIL
+    left: usize,
+    /// Arena index of the right child, or NIL
+    right: usize,
+    /// Height of the subtree rooted here (a leaf has height 1)
+    height: u32,
+}
+
+/// A self-balancing binary search tree (AVL tree) stored in a node arena.
+///
+/// Nodes live in a vector and refer to their children by index, which keeps
+/// the tree compact and avoids one allocation per node. After every insert
+/// the heights of the two subtrees of each node differ by at most one, so
+/// the tree height stays below 1.45 log₂ n.
+struct AvlTree<T> {
+    /// Storage for all nodes
+    nodes: Vec<Node<T>>,
+    /// Arena index of the root, or NIL for an empty tree
+    root: usize,
+}
+
+impl<T: Tally> AvlTree<T> {
+    /// Creates an empty tree; the arena grows as distinct values arrive.
+    fn new() -> Self {
+        AvlTree {
+            nodes: Vec::new(),
+            root: NIL,
+        }
+    }
+
+    /// Inserts a value, rebalancing on the way back up.
+    fn insert(&mut self, value: T) {
+        self.root = self.insert_at(self.root, value);
+    }
+
+    /// Inserts a value into the subtree rooted at `node`.
+    ///
+    /// # Returns
+    /// * `usize` - The index of the subtree root after rebalancing
+    fn insert_at(&mut self, node: usize, value: T) -> usize {
+        if node == NIL {
+            T::copied(1);
+            self.nodes.push(Node {
+                value,
+                count: 1,
+                left: NIL,
+                right: NIL,
+                height: 1,
+            });
+            return self.nodes.len() - 1;
+        }
+
+        if value < self.nodes[node].value {
+            let left = self.nodes[node].left;
+            self.nodes[node].left = self.insert_at(left, value);
+        } else if value > self.nodes[node].value {
+            let right = self.nodes[node].right;
+            self.nodes[node].right = self.insert_at(right, value);
+        } else {
+            // Duplicates only bump the count, so the shape doesn't change
+            self.nodes[node].count += 1;
+            return node;
+        }
+
+        self.rebalance(node)
+    }
+
+    /// Returns the height of a subtree (0 for NIL).
+    fn height(&self, node: usize) -> u32 {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].height
+        }
+    }
+
+    /// Recomputes the height of `node` from its children.
+    fn update_height(&mut self, node: usize) {
+        let left = self.height(self.nodes[node].left);
+        let right = self.height(self.nodes[node].right);
+        self.nodes[node].height = 1 + left.max(right);
+    }
+
+    /// Returns the height of the left subtree minus the height of the right.
+    fn balance_factor(&self, node: usize) -> i64 {
+        let left = self.height(self.nodes[node].left);
+        let right = self.height(self.nodes[node].right);
+        left as i64 - right as i64
+    }
+
+    /// Rotates the subtree rooted at `node` to the right.
+    ///
+    /// # Returns
+    /// * `usize` - The new subtree root (the former left child)
+    fn rotate_right(&mut self, node: usize) -> usize {
+        let pivot = self.nodes[node].left;
+        self.nodes[node].left = self.nodes[pivot].right;
+        self.nodes[pivot].right = node;
+        self.update_height(node);
+        self.update_height(pivot);
+        pivot
+    }
+
+    /// Rotates the subtree rooted at `node` to the left.
+    ///
+    /// # Returns
+    /// * `usize` - The new subtree root (the former right child)
+    fn rotate_left(&mut self, node: usize) -> usize {
+        let pivot = self.nodes[node].right;
+        self.nodes[node].right = self.nodes[pivot].left;
+        self.nodes[pivot].left = node;
+        self.update_height(node);
+        self.update_height(pivot);
+        pivot
+    }
+
+    /// Restores the AVL balance condition at `node` after an insert below it.
+    ///
+    /// Handles the four cases: left-left and right-right need one rotation,
+    /// left-right and right-left need two.
+    ///
+    /// # Returns
+    /// * `usize` - The index of the subtree root after rebalancing
+    fn rebalance(&mut self, node: usize) -> usize {
+        self.update_height(node);
+        let balance = self.balance_factor(node);
+
+        if balance > 1 {
+            let left = self.nodes[node].left;
+            if self.balance_factor(left) < 0 {
+                self.nodes[node].left = self.rotate_left(left);
+            }
+            return self.rotate_right(node);
+        }
+
+        if balance < -1 {
+            let right = self.nodes[node].right;
+            if self.balance_factor(right) > 0 {
+                self.nodes[node].right = self.rotate_right(right);
+            }
+            return self.rotate_left(node);
+        }
+
+        node
+    }
+
+    /// Writes the stored values into `output` in ascending order.
+    ///
+    /// Uses an explicit stack for the in-order traversal; its depth is
+    /// bounded by the tree height.
+    fn write_in_order(&self, output: &mut [T]) {
+        let mut stack = Vec::new();
+        let mut node = self.root;
+        let mut k = 0;
+
+        while node != NIL || !stack.is_empty() {
+            // Walk as far left as possible
+            while node != NIL {
+                stack.push(node);
+                node = self.nodes[node].left;
+            }
+
+            node = stack.pop().unwrap();
+            let current = &self.nodes[node];
+            for slot in &mut output[k..k + current.count] {
+                *slot = current.value;
+            }
+            T::copied(current.count);
+            k += current.count;
+            node = current.right;
+        }
+    }
+}
+
+/// Sorts a slice of integers in ascending order using Tree Sort.
+///
+/// Tree Sort inserts every element into a binary search tree and then reads
+/// the tree back with an in-order traversal. A plain BST degrades to a
+/// linked list on sorted input, so this version uses an AVL tree, which
+/// rotates nodes after each insert to keep the height logarithmic.
+///
+/// # Time Complexity
+/// * Best case: O(n) when all elements are equal (a single counted node)
+/// * Average case: O(n log n)
+/// * Worst case: O(n log n) - the AVL tree never degenerates
+///
+/// # Space Complexity
+/// * O(d) - one node per distinct value d ≤ n
+///
+/// # Advantages
+/// * Guaranteed O(n log n), even on sorted or reverse-sorted input
+/// * Online: the tree stays sorted while elements arrive
+/// * Very fast on inputs with few distinct values
+///
+/// # Disadvantages
+/// * Requires O(n) extra space in the worst case
+/// * Pointer chasing makes it slower than array-based sorts
+///
+/// # Arguments
+/// * `numbers` - A mutable slice of values to sort
+pub fn tree_sort<T: Tally>(numbers: &mut [T]) {
+    let mut tree = AvlTree::new();
+    for &num in numbers.iter() {
+        tree.insert(num);
+    }
+    tree.write_in_order(numbers);
+}
+
+/// Prints each integer in the vector to standard output, one per line.
+///
+/// Uses a buffered writer for efficient I/O when handling large output.
+///
+/// # Arguments
+/// * `numbers` - A slice of integers to print
+///
+/// # Returns
+/// * `io::Result<()>` - Ok if successful, Err if writing fails
+///
+/// # Errors
+/// * Returns an error if writing to stdout fails
+fn print_output(numbers: &[i32]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    for &num in numbers {
+        writeln!(writer, "{}", num)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Main entry point for the tree sort program.
+///
+/// Orchestrates reading from stdin, sorting, and writing to stdout.
+/// Exits with status code 1 if any errors occur during execution.
+fn main() {
+    // Check for the --stats flag to count the operations of the sort
+    let stats = env::args().skip(1).any(|arg| arg == "--stats");
+
+    // Check for the --timings flag to time reading, sorting and printing; it
+    // times a plain sort, so it cannot be combined with counting one
+    let timings = env::args().skip(1).any(|arg| arg == "--timings");
+    if timings && stats {
+        eprintln!("Error: --timings cannot be combined with --stats");
+        process::exit(1);
+    }
+
+    // Read integers from standard input, timing each phase from here on
+    let mut timer = PhaseTimer::start();
+    let mut numbers = match read_input() {
+        Ok(nums) => nums,
+        Err(e) => {
+            eprintln!("Error reading input: {}", e);
+            process::exit(1);
+        }
+    };
+    timer.lap("read_input");
+
+    // Sort the numbers using tree sort, counting operations if asked
+    if stats {
+        let counts = count(&mut numbers, tree_sort);
+        eprint!("{}", counts.summary("tree sort", numbers.len()));
+    } else {
+        tree_sort(&mut numbers);
+    }
+
+    timer.lap("sort");
+
+    // Print the sorted numbers to standard output
+    if let Err(e) = print_output(&numbers) {
+        eprintln!("Error writing output: {}", e);
+        process::exit(1);
+    }
+    timer.lap("print_output");
+
+    // Report how long each phase took
+    if timings {
+        eprint!("{}", timer.summary("tree sort", numbers.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xorshift::XorShift64;
+
+    #[test]
+    fn test_empty_array() {
+        let mut arr: Vec<i32> = vec![];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![]);
+    }
+
+    #[test]
+    fn test_single_element() {
+        let mut arr = vec![42];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![42]);
+    }
+
+    #[test]
+    fn test_already_sorted() {
+        let mut arr = vec![1, 2, 3, 4, 5];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        let mut arr = vec![5, 4, 3, 2, 1];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_order() {
+        let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut arr = vec![5, 2, 8, 2, 9, 1, 5, 5];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2, 2, 5, 5, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let mut arr = vec![-3, 5, -1, 0, -9, 2];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![-9, -3, -1, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let mut arr = vec![1000000, -1000000, 0, 999999, -999999];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![-1000000, -999999, 0, 999999, 1000000]);
+    }
+
+    #[test]
+    fn test_two_elements_sorted() {
+        let mut arr = vec![1, 2];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_two_elements_unsorted() {
+        let mut arr = vec![2, 1];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_all_same_elements() {
+        let mut arr = vec![7, 7, 7, 7, 7];
+        tree_sort(&mut arr);
+        assert_eq!(arr, vec![7, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let mut arr: Vec<i32> = (0..1000).rev().collect();
+        tree_sort(&mut arr);
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_matches_std_sort() {
+        let mut rng = XorShift64::new(1959);
+        for len in 0..=300 {
+            let arr: Vec<i32> = rng.values(len, -32..32);
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut actual = arr;
+            tree_sort(&mut actual);
+            assert_eq!(actual, expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_tree_stays_balanced() {
+        // Sorted insertion would build a linked list in an unbalanced BST
+        let mut tree = AvlTree::new();
+        for value in 0..(1 << 12) {
+            tree.insert(value);
+        }
+        // A perfectly balanced tree of 4096 nodes has height 13
+        assert_eq!(tree.height(tree.root), 13);
+    }
+
+    #[test]
+    fn test_duplicates_share_nodes() {
+        let mut tree = AvlTree::new();
+        for &value in &[4, 4, 1, 4, 1] {
+            tree.insert(value);
+        }
+        // The arena holds the distinct values, not one slot per input
+        assert_eq!(tree.nodes.len(), 2);
+        assert!(tree.nodes.capacity() < 5);
+        let mut output = vec![0; 5];
+        tree.write_in_order(&mut output);
+        assert_eq!(output, vec![1, 1, 4, 4, 4]);
+    }
+
+    #[test]
+    fn test_stats_count_tree_traffic() {
+        // Identical values: one node, and every later insert stops at the
+        // root after a less-than and a greater-than comparison
+        let mut identical = vec![9; 500];
+        let counts = count(&mut identical, tree_sort);
+        assert_eq!(counts.comparisons, 2 * 499);
+        assert_eq!(counts.buffer_copies, 1 + 500);
+
+        // Sorted input stays within the AVL height bound of 1.45 log2 n
+        // levels, each costing at most two comparisons
+        let n: usize = 4096;
+        let mut sorted: Vec<i32> = (0..n as i32).collect();
+        let counts = count(&mut sorted, tree_sort);
+        assert_eq!(sorted, (0..n as i32).collect::<Vec<i32>>());
+        assert!(counts.comparisons <= (2 * n * 18) as u64, "{:?}", counts);
+        assert_eq!(counts.buffer_copies, 2 * n as u64);
+        assert_eq!((counts.swaps, counts.writes), (0, 0));
+    }
+}
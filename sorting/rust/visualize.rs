@@ -0,0 +1,610 @@
+/*
+   Course: CS 41600
+   Professor: Dr. Dai
+   Date: 10/19/2026
+   File: visualize.rs
+
+   Description:
+      This program animates a sort in the terminal. It reads an operation
+      trace written by the --trace flag of bubbleSort.rs, insertionSort.rs,
+      selectionSort.rs, mergeSort.rs or quickSort.rs (see opTrace.rs), and
+      draws the array as vertical bars with ANSI escape codes, one frame
+      per event of the trace. The trace is read from the file given as an
+      argument or from standard input as it arrives, so a sort can be piped
+      straight in.
+
+      The elements of each frame are highlighted by what just happened to
+      them: yellow for the two values compared, red for the positions a
+      swap or write changed, magenta for the pivot of a partition, and cyan
+      for the range of the merge or partition in progress. When the trace
+      ends, every bar turns green. A status line names the event and counts
+      the comparisons, swaps and writes so far.
+
+      Passing --speed N shows N frames per second (20 by default; fractions
+      down to 0.01, one frame every 100 seconds, are allowed), and
+      --height N draws bars of up to N rows (16 by default). Each element
+      takes one column, so inputs of a few dozen values suit a terminal
+      best.
+
+   Usage:
+      ./bubbleSort --trace=trace.jsonl < input.txt > /dev/null
+      ./visualize trace.jsonl
+      ./quickSort --trace < input.txt 2>&1 > /dev/null | ./visualize --speed 60
+      ./visualize --speed 5 --height 24 < trace.jsonl
+*/
+
+mod op_counter;
+mod op_trace;
+
+use op_trace::Event;
+use std::env;
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+/// The number of frames shown per second unless --speed is given.
+const DEFAULT_SPEED: f64 = 20.0;
+
+/// The slowest speed accepted, in frames per second, which keeps the delay
+/// between frames within what a `Duration` can hold.
+const MIN_SPEED: f64 = 0.01;
+
+/// The height of the tallest bar in rows unless --height is given.
+const DEFAULT_HEIGHT: usize = 16;
+
+/// The character a bar is drawn with.
+const BAR: char = '█';
+
+/// How an element is highlighted in a frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mark {
+    /// Nothing is happening to the element
+    Plain,
+    /// The element lies in the range being merged or partitioned
+    Range,
+    /// The element was just compared
+    Compared,
+    /// The element was just swapped or written
+    Moved,
+    /// The element is the pivot of the partition just finished
+    Pivot,
+    /// The trace has ended and the element is in its final place
+    Sorted,
+}
+
+impl Mark {
+    /// Returns the ANSI escape code that colors an element with this mark.
+    fn color(self) -> &'static str {
+        match self {
+            Mark::Plain => "\x1b[0m",
+            Mark::Range => "\x1b[36m",
+            Mark::Compared => "\x1b[33m",
+            Mark::Moved => "\x1b[31m",
+            Mark::Pivot => "\x1b[35m",
+            Mark::Sorted => "\x1b[32m",
+        }
+    }
+}
+
+/// Options selected on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// Frames shown per second
+    speed: f64,
+    /// The height of the tallest bar in rows
+    height: usize,
+    /// The trace file, or None to read standard input
+    path: Option<String>,
+}
+
+/// The replayed state of a traced sort.
+struct Animation {
+    /// The name from the start event
+    algorithm: String,
+    /// The array after the events so far
+    array: Vec<i32>,
+    /// The smallest and largest input values, which scale the bars
+    scale: (i32, i32),
+    /// The range of the merge or partition in progress, inclusive
+    range: Option<(usize, usize)>,
+    /// Whether the end event has been seen
+    finished: bool,
+    /// Comparisons so far
+    comparisons: u64,
+    /// Swaps so far
+    swaps: u64,
+    /// Writes so far
+    writes: u64,
+}
+
+impl Animation {
+    /// Creates an animation of an empty array, to be filled by a start event.
+    fn new() -> Animation {
+        Animation {
+            algorithm: String::new(),
+            array: Vec::new(),
+            scale: (0, 0),
+            range: None,
+            finished: false,
+            comparisons: 0,
+            swaps: 0,
+            writes: 0,
+        }
+    }
+
+    /// Applies an event and returns the positions it highlights.
+    ///
+    /// # Arguments
+    /// * `event` - The next event of the trace
+    ///
+    /// # Returns
+    /// * `Vec<(usize, Mark)>` - The known positions the event touched
+    fn update(&mut self, event: &Event) -> Vec<(usize, Mark)> {
+        event.apply(&mut self.array);
+        let marks = |positions: &[Option<usize>], mark: Mark| -> Vec<(usize, Mark)> {
+            positions.iter().filter_map(|&i| i.map(|i| (i, mark))).collect()
+        };
+
+        match *event {
+            Event::Start { ref algorithm, ref values } => {
+                self.algorithm = algorithm.clone();
+                let min = values.iter().cloned().min().unwrap_or(0);
+                let max = values.iter().cloned().max().unwrap_or(0);
+                self.scale = (min, max);
+                Vec::new()
+            }
+            Event::Compare { i, j, .. } => {
+                self.comparisons += 1;
+                marks(&[i, j], Mark::Compared)
+            }
+            Event::Swap { i, j, .. } => {
+                self.swaps += 1;
+                marks(&[i, j], Mark::Moved)
+            }
+            Event::Write { i, .. } => {
+                self.writes += 1;
+                marks(&[i], Mark::Moved)
+            }
+            Event::Merge { left, right, .. } => {
+                self.range = left.and_then(|left| right.map(|right| (left, right)));
+                Vec::new()
+            }
+            Event::Partition { low, high, pivot } => {
+                self.range = low.and_then(|low| high.map(|high| (low, high)));
+                marks(&[pivot], Mark::Pivot)
+            }
+            Event::End => {
+                self.range = None;
+                self.finished = true;
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the mark of the element at `index` in the current frame.
+    fn mark(&self, index: usize, highlights: &[(usize, Mark)]) -> Mark {
+        if let Some(&(_, mark)) = highlights.iter().find(|&&(i, _)| i == index) {
+            mark
+        } else if self.finished {
+            Mark::Sorted
+        } else if self.range.is_some_and(|(low, high)| low <= index && index <= high) {
+            Mark::Range
+        } else {
+            Mark::Plain
+        }
+    }
+
+    /// Draws the current array as one frame.
+    ///
+    /// The frame starts by moving the cursor to the top left corner, and
+    /// every line clears whatever an earlier frame left to its right, so
+    /// frames can be written over each other.
+    ///
+    /// # Arguments
+    /// * `highlights` - The positions to highlight and how
+    /// * `height` - The height of the tallest bar in rows
+    /// * `status` - The description of the event shown by the frame
+    ///
+    /// # Returns
+    /// * `String` - The frame, including its escape codes
+    fn render(&self, highlights: &[(usize, Mark)], height: usize, status: &str) -> String {
+        let mut frame = String::from("\x1b[H");
+        let _ = writeln!(frame, "{}: {}\x1b[K", self.algorithm, status);
+
+        let (min, max) = self.scale;
+        let marks: Vec<Mark> = (0..self.array.len()).map(|i| self.mark(i, highlights)).collect();
+
+        for row in (1..=height).rev() {
+            let mut current = Mark::Plain;
+            for (&value, &mark) in self.array.iter().zip(&marks) {
+                if bar_height(value, min, max, height) >= row {
+                    if mark != current {
+                        frame.push_str(mark.color());
+                        current = mark;
+                    }
+                    frame.push(BAR);
+                } else {
+                    frame.push(' ');
+                }
+            }
+            frame.push_str("\x1b[0m\x1b[K\n");
+        }
+
+        let _ = writeln!(
+            frame,
+            "comparisons: {}  swaps: {}  writes: {}\x1b[K",
+            self.comparisons, self.swaps, self.writes
+        );
+        frame
+    }
+}
+
+/// Returns the number of rows of the bar for `value`.
+///
+/// The smallest value gets one row and the largest `height` rows, so
+/// negative values are drawn too. If all values are equal, every bar is
+/// full height. The scale comes from the input rather than the current
+/// array, which holds duplicates while a merge writes back, so the bars
+/// keep their heights throughout.
+///
+/// # Arguments
+/// * `value` - The value to draw
+/// * `min` - The smallest input value
+/// * `max` - The largest input value
+/// * `height` - The height of the tallest bar in rows
+fn bar_height(value: i32, min: i32, max: i32, height: usize) -> usize {
+    if max == min {
+        return height;
+    }
+    let offset = i64::from(value) - i64::from(min);
+    let span = i64::from(max) - i64::from(min);
+    1 + (offset * (height as i64 - 1) / span) as usize
+}
+
+/// Formats a position for the status line, or "?" if it is unknown.
+fn position(index: Option<usize>) -> String {
+    match index {
+        Some(index) => format!("[{}]", index),
+        None => "[?]".to_string(),
+    }
+}
+
+/// Describes an event in words for the status line.
+///
+/// # Arguments
+/// * `event` - The event shown by the frame
+///
+/// # Returns
+/// * `String` - A short description such as "compare [2]=5 with [3]=1"
+fn describe(event: &Event) -> String {
+    match *event {
+        Event::Start { ref values, .. } => format!("start with {} values", values.len()),
+        Event::Compare { i: Some(i), j: None, a, b } => {
+            format!("compare [{}]={} with held value {}", i, a, b)
+        }
+        Event::Compare { i, j, a, b } => {
+            format!("compare {}={} with {}={}", position(i), a, position(j), b)
+        }
+        Event::Swap { i, j, .. } => format!("swap {} and {}", position(i), position(j)),
+        Event::Write { i, value } => format!("write {} to {}", value, position(i)),
+        Event::Merge { left, mid, right } => format!(
+            "merge {}..{} with {}..{}",
+            position(left),
+            position(mid),
+            position(mid.map(|mid| mid + 1)),
+            position(right)
+        ),
+        Event::Partition { low, high, pivot } => format!(
+            "partition {}..{} around the pivot at {}",
+            position(low),
+            position(high),
+            position(pivot)
+        ),
+        Event::End => "sorted".to_string(),
+    }
+}
+
+/// Reads a trace and draws one frame per event.
+///
+/// The screen is cleared once, and each frame is drawn over the last one.
+///
+/// # Arguments
+/// * `reader` - The trace, one JSON event per line
+/// * `writer` - The terminal
+/// * `height` - The height of the tallest bar in rows
+/// * `delay` - The pause after each frame
+///
+/// # Returns
+/// * `io::Result<u64>` - The number of frames drawn
+///
+/// # Errors
+/// * Returns an error if reading or writing fails, or if a line of the
+///   trace is not an event; the message gives the line number
+fn animate<R: BufRead, W: Write>(
+    reader: R,
+    writer: &mut W,
+    height: usize,
+    delay: Duration,
+) -> io::Result<u64> {
+    let mut animation = Animation::new();
+    let mut frames = 0;
+    write!(writer, "\x1b[2J")?;
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = Event::parse(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {} of the trace: {}", number + 1, e),
+            )
+        })?;
+
+        let highlights = animation.update(&event);
+        writer.write_all(animation.render(&highlights, height, &describe(&event)).as_bytes())?;
+        writer.flush()?;
+        frames += 1;
+        if delay > Duration::from_secs(0) {
+            thread::sleep(delay);
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Parses the command-line arguments.
+///
+/// Recognizes `--speed N` and `--height N`, and at most one trace file.
+/// The speed must be at least `MIN_SPEED` frames per second and the
+/// height at least 2 rows, so that different values can be told apart.
+///
+/// # Arguments
+/// * `args` - The command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Result<Options, String>` - The selected options, or an error message
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        speed: DEFAULT_SPEED,
+        height: DEFAULT_HEIGHT,
+        path: None,
+    };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--speed" => {
+                let value = iter.next().ok_or("--speed requires a value")?;
+                options.speed = match value.parse::<f64>() {
+                    Ok(speed) if speed >= MIN_SPEED && speed.is_finite() => speed,
+                    _ => return Err(format!("Invalid speed: {}", value)),
+                };
+            }
+            "--height" => {
+                let value = iter.next().ok_or("--height requires a value")?;
+                options.height = match value.parse::<usize>() {
+                    Ok(height) if height >= 2 => height,
+                    _ => return Err(format!("Invalid height: {}", value)),
+                };
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument: {}", other));
+            }
+            path if options.path.is_none() => options.path = Some(path.to_string()),
+            path => return Err(format!("Unexpected argument: {} (only one trace)", path)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Main entry point for the visualizer.
+///
+/// Reads the trace from the given file or standard input and animates it
+/// on standard output. Exits with status code 1 if any errors occur.
+fn main() {
+    // Parse command-line options
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Read the trace from the file, or from standard input
+    let stdin = io::stdin();
+    let reader: Box<dyn BufRead> = match options.path {
+        Some(ref path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Error opening {}: {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => Box::new(stdin.lock()),
+    };
+
+    // Animate the trace at the requested speed
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    let delay = Duration::from_secs_f64(1.0 / options.speed);
+    if let Err(e) = animate(reader, &mut writer, options.height, delay) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Removes the escape codes from a frame, leaving the drawn characters.
+    fn strip_escapes(frame: &str) -> String {
+        let mut plain = String::new();
+        let mut chars = frame.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // Skip to the letter that ends the sequence
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                plain.push(c);
+            }
+        }
+        plain
+    }
+
+    /// A trace of bubble sort on [3, 1, 2], as bubbleSort --trace writes it.
+    const TRACE: &str = r#"{"step":0,"op":"start","algorithm":"bubble sort","values":[3,1,2]}
+{"step":1,"op":"compare","i":0,"j":1,"a":3,"b":1}
+{"step":2,"op":"swap","i":0,"j":1,"a":1,"b":3}
+{"step":3,"op":"compare","i":1,"j":2,"a":3,"b":2}
+{"step":4,"op":"swap","i":1,"j":2,"a":2,"b":3}
+{"step":5,"op":"compare","i":0,"j":1,"a":1,"b":2}
+{"step":6,"op":"end"}
+"#;
+
+    #[test]
+    fn test_bar_height() {
+        assert_eq!(bar_height(-10, -10, 10, 5), 1);
+        assert_eq!(bar_height(0, -10, 10, 5), 3);
+        assert_eq!(bar_height(10, -10, 10, 5), 5);
+        assert_eq!(bar_height(7, 7, 7, 5), 5);
+        assert_eq!(bar_height(i32::MAX, i32::MIN, i32::MAX, 16), 16);
+        assert_eq!(bar_height(i32::MIN, i32::MIN, i32::MAX, 16), 1);
+    }
+
+    #[test]
+    fn test_render_draws_bars() {
+        let mut animation = Animation::new();
+        animation.update(&Event::Start {
+            algorithm: "test".to_string(),
+            values: vec![1, 3, 2],
+        });
+        let frame = animation.render(&[], 3, "start");
+        assert_eq!(
+            strip_escapes(&frame),
+            "test: start\n █ \n ██\n███\ncomparisons: 0  swaps: 0  writes: 0\n"
+        );
+
+        // Overwriting the largest value, as a merge does, keeps the scale
+        animation.update(&Event::Write { i: Some(1), value: 2 });
+        let frame = animation.render(&[], 3, "write");
+        assert_eq!(
+            strip_escapes(&frame),
+            "test: write\n   \n ██\n███\ncomparisons: 0  swaps: 0  writes: 1\n"
+        );
+    }
+
+    #[test]
+    fn test_highlights_follow_the_events() {
+        let mut animation = Animation::new();
+        animation.update(&Event::Start {
+            algorithm: "test".to_string(),
+            values: vec![4, 3, 2, 1],
+        });
+
+        let highlights = animation.update(&Event::Compare { i: Some(2), j: None, a: 2, b: 9 });
+        assert_eq!(highlights, vec![(2, Mark::Compared)]);
+
+        let highlights =
+            animation.update(&Event::Partition { low: Some(1), high: Some(3), pivot: Some(2) });
+        assert_eq!(animation.mark(0, &highlights), Mark::Plain);
+        assert_eq!(animation.mark(1, &highlights), Mark::Range);
+        assert_eq!(animation.mark(2, &highlights), Mark::Pivot);
+
+        let highlights = animation.update(&Event::Swap { i: Some(0), j: Some(3), a: 1, b: 4 });
+        assert_eq!(animation.array, vec![1, 3, 2, 4]);
+        assert_eq!(animation.mark(3, &highlights), Mark::Moved);
+        assert_eq!(animation.mark(1, &highlights), Mark::Range);
+        assert!(animation.render(&highlights, 4, "").contains(Mark::Moved.color()));
+
+        animation.update(&Event::End);
+        assert!((0..4).all(|i| animation.mark(i, &[]) == Mark::Sorted));
+        assert_eq!((animation.comparisons, animation.swaps, animation.writes), (1, 1, 0));
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(&Event::Compare { i: Some(0), j: Some(1), a: 3, b: 1 }),
+            "compare [0]=3 with [1]=1"
+        );
+        assert_eq!(
+            describe(&Event::Compare { i: Some(4), j: None, a: 3, b: 1 }),
+            "compare [4]=3 with held value 1"
+        );
+        assert_eq!(describe(&Event::Write { i: None, value: 5 }), "write 5 to [?]");
+        assert_eq!(
+            describe(&Event::Merge { left: Some(0), mid: Some(3), right: Some(7) }),
+            "merge [0]..[3] with [4]..[7]"
+        );
+    }
+
+    #[test]
+    fn test_animate_draws_a_frame_per_event() {
+        let mut output = Vec::new();
+        let frames = animate(TRACE.as_bytes(), &mut output, 3, Duration::from_secs(0)).unwrap();
+        assert_eq!(frames, 7);
+
+        // The last frame shows the sorted array and the final counts
+        let output = String::from_utf8(output).unwrap();
+        let last = output.rsplit("\x1b[H").next().unwrap();
+        assert!(last.contains(Mark::Sorted.color()));
+        assert_eq!(
+            strip_escapes(last),
+            "bubble sort: sorted\n  █\n ██\n███\ncomparisons: 3  swaps: 2  writes: 0\n"
+        );
+    }
+
+    #[test]
+    fn test_animate_reports_bad_lines() {
+        let trace = format!("{}\nnot json\n", TRACE.lines().next().unwrap());
+        let error = animate(trace.as_bytes(), &mut Vec::new(), 3, Duration::from_secs(0));
+        let message = error.unwrap_err().to_string();
+        assert!(message.starts_with("line 2 of the trace"), "{}", message);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |list: &[&str]| -> Vec<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        };
+
+        assert_eq!(
+            parse_args(&args(&[])),
+            Ok(Options {
+                speed: DEFAULT_SPEED,
+                height: DEFAULT_HEIGHT,
+                path: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--speed", "0.5", "t.jsonl", "--height", "8"])),
+            Ok(Options {
+                speed: 0.5,
+                height: 8,
+                path: Some("t.jsonl".to_string()),
+            })
+        );
+        assert!(parse_args(&args(&["--speed", "0"])).is_err());
+        assert!(parse_args(&args(&["--speed", "1e-30"])).is_err());
+        assert!(parse_args(&args(&["--speed", "inf"])).is_err());
+        assert_eq!(parse_args(&args(&["--speed", "0.01"])).map(|o| o.speed), Ok(MIN_SPEED));
+        assert!(parse_args(&args(&["--speed", "fast"])).is_err());
+        assert!(parse_args(&args(&["--speed"])).is_err());
+        assert!(parse_args(&args(&["--height", "1"])).is_err());
+        assert!(parse_args(&args(&["a.jsonl", "b.jsonl"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+}
@@ -31,6 +31,10 @@ DATA_DIR = SCRIPT_DIR / "data" / "input_data" / "10000"
 
 ALGORITHMS = ["bubble", "insertion", "selection", "merge", "quick"]
 
+# Algorithms that only some languages implement. They are tested whenever a
+# language that provides them is tested without --algorithm.
+EXTRA_ALGORITHMS = ["bitonic", "odd_even_merge", "smooth", "tournament", "tree", "patience", "auto"]
+
 DISTRIBUTIONS = [
     "random",
     "sorted",
@@ -107,6 +111,13 @@ def get_language_config():
         "selection": "selection_sort",
         "merge": "merge_sort",
         "quick": "quick_sort",
+        "bitonic": "bitonic_sort",
+        "odd_even_merge": "odd_even_merge_sort",
+        "smooth": "smooth_sort",
+        "tournament": "tournament_sort",
+        "tree": "tree_sort",
+        "patience": "patience_sort",
+        "auto": "auto_sort",
     }
     rust_algos = {}
     for algo, name in rust_names.items():
@@ -318,7 +329,7 @@ def interactive_mode(configs):
         return
 
     languages = pick_from_list("Choose a language to test:", available)
-    algorithms = pick_from_list("Choose an algorithm to test:", ALGORITHMS)
+    algorithms = pick_from_list("Choose an algorithm to test:", ALGORITHMS + EXTRA_ALGORITHMS)
 
     run_tests(configs, languages, algorithms)
 
@@ -346,8 +357,12 @@ def has_source_files(language, algo_configs):
 # MAIN
 # ============================================================
 
-def run_tests(configs, languages, algorithms):
-    """Run tests for the given languages and algorithms."""
+def run_tests(configs, languages, algorithms=None):
+    """Run tests for the given languages and algorithms.
+
+    If no algorithms are given, every algorithm configured for each language
+    is tested.
+    """
     total_passed = 0
     total_failed = 0
 
@@ -361,7 +376,7 @@ def run_tests(configs, languages, algorithms):
         print(f"  Testing: {lang}")
         print(f"{'='*48}")
 
-        for algo in algorithms:
+        for algo in algorithms or lang_config:
             if algo not in lang_config:
                 print(f"\n  ⚠  Algorithm '{algo}' not configured for {lang}")
                 continue
@@ -401,7 +416,7 @@ Examples:
     )
     parser.add_argument(
         "--algorithm", "-a", nargs="+",
-        choices=ALGORITHMS,
+        choices=ALGORITHMS + EXTRA_ALGORITHMS,
         help="Algorithm(s) to test (e.g. bubble merge)",
     )
     parser.add_argument(
@@ -414,15 +429,14 @@ Examples:
 
     if args.all:
         languages = [l for l in configs if has_source_files(l, configs[l])]
-        algorithms = ALGORITHMS
         if not languages:
             print("No languages with source files found!")
             sys.exit(1)
-        failures = run_tests(configs, languages, algorithms)
+        failures = run_tests(configs, languages)
         sys.exit(min(failures, 1))
 
     elif args.language:
-        algorithms = args.algorithm or ALGORITHMS
+        algorithms = args.algorithm
         failures = run_tests(configs, args.language, algorithms)
         sys.exit(min(failures, 1))
 